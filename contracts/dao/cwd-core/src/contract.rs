@@ -1,22 +1,39 @@
+use admin_nomination::{
+    execute_accept_admin_nomination, execute_nominate_admin, execute_withdraw_admin_nomination,
+    query_nomination, ADMIN,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
-    StdResult, SubMsg,
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdError, StdResult, SubMsg, SubMsgResult, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
+use exec_control::pause::{get_max_pause_duration, validate_duration, validate_reason, PauseError};
+use std::cmp::Ordering;
 
-use cw_paginate::{paginate_map, paginate_map_values};
+use cw_paginate::{paginate_map, paginate_map_keys, paginate_snapshot_map_values};
 use cwd_interface::{voting, ModuleInstantiateInfo};
 use neutron_sdk::bindings::msg::NeutronMsg;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::query::{DumpStateResponse, GetItemResponse, PauseInfoResponse, SubDao};
+use crate::migration::{migrate_from_compatible, migrate_from_v1};
+use crate::msg::{
+    ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, PausableExecuteMsg, QueryMsg,
+};
+use crate::query::{
+    AdminNominationResponse, Cw20BalanceResponse, DumpStateAtHeightResponse, DumpStateResponse,
+    GetItemResponse, PauseInfoResponse, SubDao,
+};
 use crate::state::{
-    Config, ProposalModule, ProposalModuleStatus, ACTIVE_PROPOSAL_MODULE_COUNT, CONFIG, ITEMS,
-    PAUSED, PROPOSAL_MODULES, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_REGISTRY_MODULE,
+    Config, PendingReconfiguration, ProposalModule, ProposalModuleStatus,
+    ACTIVE_PROPOSAL_MODULE_COUNT, CONFIG, CW20_LIST, DEFAULT_LIMIT, EMERGENCY_PROPOSAL_MODULES,
+    HISTORY_START_HEIGHT, ITEMS, PAUSABLE_CONTRACTS, PAUSED, PAUSERS, PENDING_PAUSES,
+    PENDING_RECONFIGURATION, PROPOSAL_MODULES, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT,
+    VOTING_REGISTRY_MODULE,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-subdao-core";
@@ -25,6 +42,9 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const PROPOSAL_MODULE_REPLY_ID: u64 = 0;
 const VOTE_MODULE_INSTANTIATE_REPLY_ID: u64 = 1;
 const VOTE_MODULE_UPDATE_REPLY_ID: u64 = 2;
+const PAUSE_ALL_REPLY_ID: u64 = 3;
+const RECONFIGURE_VOTING_MODULE_REPLY_ID: u64 = 4;
+const RECONFIGURE_PROPOSAL_MODULE_REPLY_ID: u64 = 5;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -42,6 +62,7 @@ pub fn instantiate(
     };
     config.validate()?;
     CONFIG.save(deps.storage, &config)?;
+    ADMIN.save(deps.storage, &env.contract.address)?;
 
     let vote_module_msg = msg
         .voting_registry_module_instantiate_info
@@ -63,8 +84,9 @@ pub fn instantiate(
         ITEMS.save(deps.storage, key, &value)?;
     }
 
-    TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &0)?;
-    ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &0)?;
+    TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &0, env.block.height)?;
+    ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &0, env.block.height)?;
+    HISTORY_START_HEIGHT.save(deps.storage, &env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
@@ -80,18 +102,28 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response<NeutronMsg>, ContractError> {
-    // No actions can be performed while the DAO is paused.
+    // Only emergency actions can be performed while the DAO is paused.
     if let Some(expiration) = PAUSED.may_load(deps.storage)? {
         if !expiration.is_expired(&env.block) {
-            return Err(ContractError::Paused {});
+            return execute_paused(deps, env, info, msg);
         }
     }
 
     match msg {
         ExecuteMsg::ExecuteProposalHook { msgs } => {
-            execute_proposal_hook(deps.as_ref(), info.sender, msgs)
+            execute_proposal_hook(deps, env, info.sender, msgs)
+        }
+        ExecuteMsg::NominateAdmin { admin } => {
+            Ok(execute_nominate_admin(deps, info.sender, admin)?)
+        }
+        ExecuteMsg::AcceptAdminNomination {} => {
+            Ok(execute_accept_admin_nomination(deps, env, info.sender)?)
+        }
+        ExecuteMsg::WithdrawAdminNomination {} => {
+            Ok(execute_withdraw_admin_nomination(deps, info.sender)?)
         }
         ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::SetItem { key, addr } => execute_set_item(deps, env, info.sender, key, addr),
         ExecuteMsg::UpdateConfig { config } => {
@@ -103,12 +135,97 @@ pub fn execute(
         ExecuteMsg::UpdateProposalModules { to_add, to_disable } => {
             execute_update_proposal_modules(deps, env, info.sender, to_add, to_disable)
         }
+        ExecuteMsg::ReconfigureModules {
+            voting_registry_module,
+            proposal_modules,
+        } => execute_reconfigure_modules(
+            deps,
+            env,
+            info.sender,
+            voting_registry_module,
+            proposal_modules,
+        ),
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::Receive(receive_msg) => {
+            execute_receive_cw20(deps, env, info.sender, receive_msg)
+        }
+        ExecuteMsg::UpdateCw20List { to_add, to_remove } => {
+            execute_update_cw20_list(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdateEmergencyProposalModules { to_add, to_remove } => {
+            execute_update_emergency_proposal_modules(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdatePausableContracts { to_add, to_remove } => {
+            execute_update_pausable_contracts(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::UpdatePausers { to_add, to_remove } => {
+            execute_update_pausers(deps, env, info.sender, to_add, to_remove)
+        }
+        ExecuteMsg::PauseAll {
+            duration,
+            reason,
+            operations,
+        } => execute_pause_all(deps, env, info.sender, duration, reason, operations),
     }
 }
 
+/// Handles the messages allowed while the DAO is paused: proposal
+/// hooks of the emergency proposal modules, the messages those hooks
+/// dispatch to the DAO itself and `PauseAll`.
+fn execute_paused(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    match msg {
+        ExecuteMsg::ExecuteProposalHook { msgs } => {
+            if !EMERGENCY_PROPOSAL_MODULES.has(deps.storage, info.sender.clone()) {
+                return Err(ContractError::Paused {});
+            }
+            validate_emergency_msgs(&msgs, &env.contract.address)?;
+            execute_proposal_hook(deps, env, info.sender, msgs)
+        }
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+        ExecuteMsg::UpdateConfig { config } => {
+            execute_update_config(deps, env, info.sender, config)
+        }
+        ExecuteMsg::PauseAll {
+            duration,
+            reason,
+            operations,
+        } => execute_pause_all(deps, env, info.sender, duration, reason, operations),
+        _ => Err(ContractError::Paused {}),
+    }
+}
+
+/// Checks that every message is either a contract migration or a call
+/// to the DAO to unpause or update its config.
+fn validate_emergency_msgs(
+    msgs: &[CosmosMsg<NeutronMsg>],
+    dao: &Addr,
+) -> Result<(), ContractError> {
+    for msg in msgs {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Migrate { .. }) => {}
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) if contract_addr == dao.as_str() && funds.is_empty() => {
+                match from_binary::<ExecuteMsg>(msg) {
+                    Ok(ExecuteMsg::Unpause {}) | Ok(ExecuteMsg::UpdateConfig { .. }) => {}
+                    _ => return Err(ContractError::EmergencyMsgNotAllowed {}),
+                }
+            }
+            _ => return Err(ContractError::EmergencyMsgNotAllowed {}),
+        }
+    }
+    Ok(())
+}
+
 pub fn execute_pause(
     deps: DepsMut,
     env: Env,
@@ -129,8 +246,25 @@ pub fn execute_pause(
         .add_attribute("until", until.to_string()))
 }
 
+pub fn execute_unpause(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_unpause")
+        .add_attribute("sender", sender))
+}
+
 pub fn execute_proposal_hook(
-    deps: Deps,
+    deps: DepsMut,
+    env: Env,
     sender: Addr,
     msgs: Vec<CosmosMsg<NeutronMsg>>,
 ) -> Result<Response<NeutronMsg>, ContractError> {
@@ -143,6 +277,13 @@ pub fn execute_proposal_hook(
         return Err(ContractError::ModuleDisabledCannotExecute { address: sender });
     }
 
+    // Register the tokens the proposal transfers to the DAO so that
+    // they show up in the treasury balances.
+    for token in cw20_transfers_to(&msgs, &env.contract.address) {
+        let token = deps.api.addr_validate(&token)?;
+        CW20_LIST.save(deps.storage, token, &Empty {})?;
+    }
+
     Ok(Response::default()
         .add_attribute("action", "execute_proposal_hook")
         .add_messages(msgs))
@@ -219,13 +360,13 @@ pub fn execute_update_proposal_modules(
         }
 
         module.status = ProposalModuleStatus::Disabled {};
-        PROPOSAL_MODULES.save(deps.storage, addr, &module)?;
+        PROPOSAL_MODULES.save(deps.storage, addr, &module, env.block.height)?;
     }
 
     // If disabling this module will cause there to be no active modules, return error.
     // We don't check the active count before disabling because there may erroneously be
     // modules in to_disable which are already disabled.
-    ACTIVE_PROPOSAL_MODULE_COUNT.update(deps.storage, |count| {
+    ACTIVE_PROPOSAL_MODULE_COUNT.update(deps.storage, env.block.height, |count| {
         if count <= disable_count && to_add.is_empty() {
             return Err(ContractError::NoActiveProposalModules {});
         }
@@ -243,6 +384,109 @@ pub fn execute_update_proposal_modules(
         .add_submessages(to_add))
 }
 
+pub fn execute_reconfigure_modules(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    voting_registry_module: ModuleInstantiateInfo,
+    proposal_modules: Vec<ModuleInstantiateInfo>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if proposal_modules.is_empty() {
+        return Err(ContractError::NoActiveProposalModules {});
+    }
+    if PENDING_RECONFIGURATION.exists(deps.storage) {
+        return Err(ContractError::ReconfigurationInProgress {});
+    }
+
+    // The modules are swapped in once the last of them replies. Since
+    // the submessages only reply on success, a failing instantiation
+    // reverts the whole reconfiguration.
+    PENDING_RECONFIGURATION.save(
+        deps.storage,
+        &PendingReconfiguration {
+            remaining: proposal_modules.len() as u32 + 1,
+            voting_registry_module: None,
+            proposal_modules: vec![],
+        },
+    )?;
+
+    let voting_registry_module = SubMsg::reply_on_success(
+        voting_registry_module.into_wasm_msg(env.contract.address.clone()),
+        RECONFIGURE_VOTING_MODULE_REPLY_ID,
+    );
+    let proposal_modules: Vec<SubMsg<NeutronMsg>> = proposal_modules
+        .into_iter()
+        .map(|info| info.into_wasm_msg(env.contract.address.clone()))
+        .map(|wasm| SubMsg::reply_on_success(wasm, RECONFIGURE_PROPOSAL_MODULE_REPLY_ID))
+        .collect();
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_reconfigure_modules")
+        .add_submessage(voting_registry_module)
+        .add_submessages(proposal_modules))
+}
+
+/// Checks that the newly instantiated module belongs to this DAO.
+fn validate_module_dao(deps: Deps, env: &Env, module: &Addr) -> Result<(), ContractError> {
+    let dao: Addr = deps
+        .querier
+        .query_wasm_smart(module, &voting::Query::Dao {})?;
+    if dao != env.contract.address {
+        return Err(ContractError::InvalidModuleDao {
+            address: module.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Replaces the voting module and the proposal modules with the ones
+/// instantiated by `ReconfigureModules`. All of the current proposal
+/// modules are disabled and lose their emergency permissions.
+fn commit_reconfiguration(
+    deps: DepsMut,
+    env: &Env,
+    pending: PendingReconfiguration,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let height = env.block.height;
+    let voting_registry_module = pending
+        .voting_registry_module
+        .ok_or(ContractError::KeyMissing {})?;
+    VOTING_REGISTRY_MODULE.save(deps.storage, &voting_registry_module, height)?;
+
+    let current = PROPOSAL_MODULES
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, ProposalModule)>>>()?;
+    for (addr, mut module) in current {
+        EMERGENCY_PROPOSAL_MODULES.remove(deps.storage, addr.clone());
+        if module.status == ProposalModuleStatus::Enabled {
+            module.status = ProposalModuleStatus::Disabled;
+            PROPOSAL_MODULES.save(deps.storage, addr, &module, height)?;
+        }
+    }
+
+    let total_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
+    let added_count = pending.proposal_modules.len() as u32;
+    for (idx, address) in pending.proposal_modules.into_iter().enumerate() {
+        let prefix = derive_proposal_module_prefix(total_module_count as usize + idx)?;
+        let module = ProposalModule {
+            address: address.clone(),
+            prefix,
+            status: ProposalModuleStatus::Enabled,
+        };
+        PROPOSAL_MODULES.save(deps.storage, address, &module, height)?;
+    }
+    ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &added_count, height)?;
+    TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &(total_module_count + added_count), height)?;
+    PENDING_RECONFIGURATION.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "commit_reconfiguration")
+        .add_attribute("voting_registry_module", voting_registry_module))
+}
+
 pub fn execute_set_item(
     deps: DepsMut,
     env: Env,
@@ -294,12 +538,12 @@ pub fn execute_update_sub_daos_list(
 
     for addr in to_remove {
         let addr = deps.api.addr_validate(&addr)?;
-        SUBDAO_LIST.remove(deps.storage, &addr);
+        SUBDAO_LIST.remove(deps.storage, &addr, env.block.height)?;
     }
 
     for subdao in to_add {
         let addr = deps.api.addr_validate(&subdao.addr)?;
-        SUBDAO_LIST.save(deps.storage, &addr, &subdao.charter)?;
+        SUBDAO_LIST.save(deps.storage, &addr, &subdao.charter, env.block.height)?;
     }
 
     Ok(Response::default()
@@ -307,11 +551,247 @@ pub fn execute_update_sub_daos_list(
         .add_attribute("sender", sender))
 }
 
+pub fn execute_receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    // Only tokens sent by the DAO itself, i.e. by its proposals, or by
+    // its proposal modules are registered, so that anyone sending
+    // tokens can not grow the list. Other tokens may be registered
+    // with `UpdateCw20List`.
+    let from = deps.api.addr_validate(&receive_msg.sender)?;
+    if from == env.contract.address || PROPOSAL_MODULES.has(deps.storage, from) {
+        // The sender of a receive hook is the token contract itself.
+        // Make sure it is a cw20 token contract before registering it.
+        let _info: TokenInfoResponse = deps
+            .querier
+            .query_wasm_smart(sender.clone(), &Cw20QueryMsg::TokenInfo {})?;
+        CW20_LIST.save(deps.storage, sender.clone(), &Empty {})?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_receive_cw20")
+        .add_attribute("token", sender)
+        .add_attribute("from", receive_msg.sender)
+        .add_attribute("amount", receive_msg.amount))
+}
+
+pub fn execute_update_cw20_list(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for token in to_add {
+        let token = deps.api.addr_validate(&token)?;
+        // Make sure the address is a cw20 token contract.
+        let _info: TokenInfoResponse = deps
+            .querier
+            .query_wasm_smart(token.clone(), &Cw20QueryMsg::TokenInfo {})?;
+        CW20_LIST.save(deps.storage, token, &Empty {})?;
+    }
+
+    for token in to_remove {
+        let token = deps.api.addr_validate(&token)?;
+        CW20_LIST.remove(deps.storage, token);
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_cw20_list")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_update_emergency_proposal_modules(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        if !PROPOSAL_MODULES.has(deps.storage, addr.clone()) {
+            return Err(ContractError::ProposalModuleDoesNotExist { address: addr });
+        }
+        EMERGENCY_PROPOSAL_MODULES.save(deps.storage, addr, &Empty {})?;
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        EMERGENCY_PROPOSAL_MODULES.remove(deps.storage, addr);
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_emergency_proposal_modules")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_update_pausable_contracts(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        if addr == env.contract.address {
+            return Err(ContractError::InvalidPausableContract { address: addr });
+        }
+        PAUSABLE_CONTRACTS.save(deps.storage, addr, &Empty {})?;
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        PAUSABLE_CONTRACTS.remove(deps.storage, addr);
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_pausable_contracts")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_update_pausers(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_add: Vec<String>,
+    to_remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in to_add {
+        let addr = deps.api.addr_validate(&addr)?;
+        PAUSERS.save(deps.storage, addr, &Empty {})?;
+    }
+
+    for addr in to_remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        PAUSERS.remove(deps.storage, addr);
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_pausers")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_pause_all(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    duration: Duration,
+    reason: Option<String>,
+    operations: Option<Vec<String>>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if env.contract.address != sender && !PAUSERS.has(deps.storage, sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_duration(duration, get_max_pause_duration(deps.storage, &duration)?)?;
+    validate_reason(&reason)?;
+
+    // A pause in effect is only ever extended, so that a pauser can't
+    // lift a pause early by pausing for a shorter duration.
+    let mut until = duration.after(&env.block);
+    if let Some(current) = PAUSED.may_load(deps.storage)? {
+        if !current.is_expired(&env.block) {
+            match until.partial_cmp(&current) {
+                Some(Ordering::Greater) => {}
+                Some(_) => until = current,
+                None => {
+                    return Err(ContractError::PauseError(PauseError::InvalidDuration(
+                        "pause duration must be set in the same units as the current pause"
+                            .to_string(),
+                    )))
+                }
+            }
+        }
+    }
+    PAUSED.save(deps.storage, &until)?;
+
+    let contracts = PAUSABLE_CONTRACTS
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    let msg = to_binary(&PausableExecuteMsg::Pause {
+        duration,
+        reason,
+        operations,
+    })?;
+    let mut submessages = Vec::with_capacity(contracts.len());
+    for contract in contracts {
+        // Replies arrive in the order the submessages are sent, so
+        // they are matched against this queue.
+        PENDING_PAUSES.push_back(deps.storage, &contract)?;
+        let wasm = WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        submessages.push(SubMsg::reply_always(wasm, PAUSE_ALL_REPLY_ID));
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_pause_all")
+        .add_attribute("sender", sender)
+        .add_attribute("until", until.to_string())
+        .add_submessages(submessages))
+}
+
+/// Returns the addresses of the cw20 tokens which the given messages
+/// transfer to the `recipient` on behalf of another owner. Tokens
+/// sent with `Send` register through the receive hook instead.
+fn cw20_transfers_to(msgs: &[CosmosMsg<NeutronMsg>], recipient: &Addr) -> Vec<String> {
+    msgs.iter()
+        .filter_map(|msg| match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => match from_binary::<Cw20ExecuteMsg>(msg) {
+                Ok(Cw20ExecuteMsg::TransferFrom { recipient: to, .. })
+                    if to == recipient.as_str() =>
+                {
+                    Some(contract_addr.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Admin {} => query_admin(deps),
+        QueryMsg::AdminNomination {} => query_admin_nomination(deps),
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::DumpState {} => query_dump_state(deps, env),
+        QueryMsg::DumpStateAtHeight { height } => query_dump_state_at_height(deps, height),
+        QueryMsg::ProposalModulesAtHeight {
+            height,
+            start_after,
+            limit,
+        } => query_proposal_modules_at_height(deps, height, start_after, limit),
+        QueryMsg::ListSubDaosAtHeight {
+            height,
+            start_after,
+            limit,
+        } => query_list_sub_daos_at_height(deps, height, start_after, limit),
         QueryMsg::GetItem { key } => query_get_item(deps, key),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ListItems { start_after, limit } => query_list_items(deps, start_after, limit),
@@ -332,6 +812,18 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::GetSubDao { address } => query_sub_dao(deps, address),
         QueryMsg::DaoURI {} => query_dao_uri(deps),
+        QueryMsg::Cw20TokenList { start_after, limit } => query_cw20_list(deps, start_after, limit),
+        QueryMsg::Cw20Balances { start_after, limit } => {
+            query_cw20_balances(deps, env, start_after, limit)
+        }
+        QueryMsg::NativeBalances {} => query_native_balances(deps, env),
+        QueryMsg::EmergencyProposalModules { start_after, limit } => {
+            query_emergency_proposal_modules(deps, start_after, limit)
+        }
+        QueryMsg::PausableContracts { start_after, limit } => {
+            query_pausable_contracts(deps, start_after, limit)
+        }
+        QueryMsg::Pausers { start_after, limit } => query_pausers(deps, start_after, limit),
     }
 }
 
@@ -340,6 +832,16 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
     to_binary(&config)
 }
 
+pub fn query_admin(deps: Deps) -> StdResult<Binary> {
+    let admin = ADMIN.load(deps.storage)?;
+    to_binary(&admin)
+}
+
+pub fn query_admin_nomination(deps: Deps) -> StdResult<Binary> {
+    let nomination = query_nomination(deps.storage)?;
+    to_binary(&AdminNominationResponse { nomination })
+}
+
 pub fn query_voting_module(deps: Deps) -> StdResult<Binary> {
     let voting_module = VOTING_REGISTRY_MODULE.load(deps.storage)?;
     to_binary(&voting_module)
@@ -363,7 +865,7 @@ pub fn query_proposal_modules(
     //
     // Even if this does lock up one can determine the existing
     // proposal modules by looking at past transactions on chain.
-    to_binary(&paginate_map_values(
+    to_binary(&paginate_snapshot_map_values(
         deps,
         &PROPOSAL_MODULES,
         start_after
@@ -381,7 +883,7 @@ pub fn query_active_proposal_modules(
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let values = paginate_map_values(
+    let values = paginate_snapshot_map_values(
         deps,
         &PROPOSAL_MODULES,
         start_after
@@ -421,6 +923,7 @@ pub fn query_paused(deps: Deps, env: Env) -> StdResult<Binary> {
 
 pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
+    let admin = ADMIN.load(deps.storage)?;
     let voting_registry_module = VOTING_REGISTRY_MODULE.load(deps.storage)?;
     let proposal_modules = PROPOSAL_MODULES
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
@@ -432,6 +935,7 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     let total_proposal_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
     to_binary(&DumpStateResponse {
         config,
+        admin,
         version,
         pause_info,
         proposal_modules,
@@ -441,6 +945,120 @@ pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     })
 }
 
+pub fn query_dump_state_at_height(deps: Deps, height: u64) -> StdResult<Binary> {
+    ensure_history_recorded(deps, height)?;
+    let voting_module = VOTING_REGISTRY_MODULE.may_load_at_height(deps.storage, height)?;
+    let active_proposal_module_count = ACTIVE_PROPOSAL_MODULE_COUNT
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    let total_proposal_module_count = TOTAL_PROPOSAL_MODULE_COUNT
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+
+    to_binary(&DumpStateAtHeightResponse {
+        height,
+        voting_module,
+        active_proposal_module_count,
+        total_proposal_module_count,
+    })
+}
+
+pub fn query_proposal_modules_at_height(
+    deps: Deps,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    ensure_history_recorded(deps, height)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let keys = PROPOSAL_MODULES.changelog().keys(
+        deps.storage,
+        start_after.map(|addr| Bound::exclusive((addr, u64::MAX))),
+        None,
+        cosmwasm_std::Order::Ascending,
+    );
+    let proposal_modules = load_at_height(keys, limit, |addr| {
+        PROPOSAL_MODULES.may_load_at_height(deps.storage, addr, height)
+    })?;
+
+    to_binary(&proposal_modules)
+}
+
+pub fn query_list_sub_daos_at_height(
+    deps: Deps,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    ensure_history_recorded(deps, height)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let keys = SUBDAO_LIST.changelog().keys(
+        deps.storage,
+        start_after
+            .as_ref()
+            .map(|addr| Bound::exclusive((addr, u64::MAX))),
+        None,
+        cosmwasm_std::Order::Ascending,
+    );
+    let sub_daos = load_at_height(keys, limit, |addr| {
+        Ok(SUBDAO_LIST
+            .may_load_at_height(deps.storage, &addr, height)?
+            .map(|charter| SubDao {
+                addr: addr.into_string(),
+                charter,
+            }))
+    })?;
+
+    to_binary(&sub_daos)
+}
+
+/// Fails for heights before the history of the core contract is
+/// recorded from, the state there is unknown.
+fn ensure_history_recorded(deps: Deps, height: u64) -> StdResult<()> {
+    let start = HISTORY_START_HEIGHT.load(deps.storage)?;
+    if height < start {
+        return Err(StdError::generic_err(format!(
+            "state history is only recorded from height {start}"
+        )));
+    }
+    Ok(())
+}
+
+/// Loads the entries of a snapshot map present at a height, walking
+/// the keys of its changelog. Every key written since the history is
+/// recorded has a changelog entry, so entries removed since the height
+/// are found too. Stops once `limit` entries are found.
+fn load_at_height<T>(
+    changelog_keys: impl Iterator<Item = StdResult<(Addr, u64)>>,
+    limit: Option<u32>,
+    mut load: impl FnMut(Addr) -> StdResult<Option<T>>,
+) -> StdResult<Vec<T>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let mut entries = vec![];
+    let mut last: Option<Addr> = None;
+    for key in changelog_keys {
+        let (addr, _) = key?;
+        // The changelog holds one key per change of an entry.
+        if last.as_ref() == Some(&addr) {
+            continue;
+        }
+        last = Some(addr.clone());
+        if let Some(entry) = load(addr)? {
+            entries.push(entry);
+            if entries.len() >= limit {
+                break;
+            }
+        }
+    }
+    Ok(entries)
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
     address: String,
@@ -496,7 +1114,7 @@ pub fn query_list_sub_daos(
         .map(|addr| deps.api.addr_validate(&addr))
         .transpose()?;
 
-    let subdaos = cw_paginate::paginate_map(
+    let subdaos = cw_paginate::paginate_snapshot_map(
         deps,
         &SUBDAO_LIST,
         start_at.as_ref(),
@@ -532,14 +1150,137 @@ pub fn query_dao_uri(deps: Deps) -> StdResult<Binary> {
     to_binary(&config.dao_uri)
 }
 
+pub fn query_emergency_proposal_modules(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    to_binary(&paginate_map_keys(
+        deps,
+        &EMERGENCY_PROPOSAL_MODULES,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_pausable_contracts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    to_binary(&paginate_map_keys(
+        deps,
+        &PAUSABLE_CONTRACTS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_pausers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    to_binary(&paginate_map_keys(
+        deps,
+        &PAUSERS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_cw20_list(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    to_binary(&paginate_map_keys(
+        deps,
+        &CW20_LIST,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?)
+}
+
+pub fn query_cw20_balances(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let cw20_list = paginate_map_keys(
+        deps,
+        &CW20_LIST,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+    // Tokens are registered automatically when they are sent to the
+    // DAO, so a misbehaving token must not break the query for all
+    // others. Tokens whose balance can't be queried are skipped.
+    let balances: Vec<Cw20BalanceResponse> = cw20_list
+        .into_iter()
+        .filter_map(|addr| {
+            let balance: BalanceResponse = deps
+                .querier
+                .query_wasm_smart(
+                    addr.clone(),
+                    &Cw20QueryMsg::Balance {
+                        address: env.contract.address.to_string(),
+                    },
+                )
+                .ok()?;
+            Some(Cw20BalanceResponse {
+                addr,
+                balance: balance.balance,
+            })
+        })
+        .collect();
+    to_binary(&balances)
+}
+
+pub fn query_native_balances(deps: Deps, env: Env) -> StdResult<Binary> {
+    to_binary(&deps.querier.query_all_balances(env.contract.address)?)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    match msg {
+        MigrateMsg::FromV1 { dao_uri, sub_daos } => {
+            migrate_from_v1(deps.branch(), &env, &stored, dao_uri, sub_daos)?
+        }
+        MigrateMsg::FromCompatible {} => migrate_from_compatible(deps.branch(), &env, &stored)?,
+    }
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+
+    Ok(Response::default()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response<NeutronMsg>, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response<NeutronMsg>, ContractError> {
     match msg.id {
         PROPOSAL_MODULE_REPLY_ID => {
             let res = parse_reply_instantiate_data(msg)?;
@@ -553,12 +1294,24 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response<NeutronMsg
                 prefix,
             };
 
-            PROPOSAL_MODULES.save(deps.storage, prop_module_addr, &prop_module)?;
+            PROPOSAL_MODULES.save(
+                deps.storage,
+                prop_module_addr,
+                &prop_module,
+                env.block.height,
+            )?;
 
             // Save active and total proposal module counts.
-            ACTIVE_PROPOSAL_MODULE_COUNT
-                .update::<_, StdError>(deps.storage, |count| Ok(count + 1))?;
-            TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &(total_module_count + 1))?;
+            ACTIVE_PROPOSAL_MODULE_COUNT.update::<_, StdError>(
+                deps.storage,
+                env.block.height,
+                |count| Ok(count + 1),
+            )?;
+            TOTAL_PROPOSAL_MODULE_COUNT.save(
+                deps.storage,
+                &(total_module_count + 1),
+                env.block.height,
+            )?;
 
             Ok(Response::default().add_attribute("prop_module".to_string(), res.contract_address))
         }
@@ -574,7 +1327,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response<NeutronMsg
                 return Err(ContractError::MultipleVotingModules {});
             }
 
-            VOTING_REGISTRY_MODULE.save(deps.storage, &voting_registry_addr)?;
+            VOTING_REGISTRY_MODULE.save(deps.storage, &voting_registry_addr, env.block.height)?;
 
             Ok(Response::default().add_attribute("voting_regsitry_module", voting_registry_addr))
         }
@@ -582,10 +1335,45 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response<NeutronMsg
             let res = parse_reply_instantiate_data(msg)?;
             let voting_registry_addr = deps.api.addr_validate(&res.contract_address)?;
 
-            VOTING_REGISTRY_MODULE.save(deps.storage, &voting_registry_addr)?;
+            VOTING_REGISTRY_MODULE.save(deps.storage, &voting_registry_addr, env.block.height)?;
 
             Ok(Response::default().add_attribute("voting_registry_module", voting_registry_addr))
         }
+        RECONFIGURE_VOTING_MODULE_REPLY_ID | RECONFIGURE_PROPOSAL_MODULE_REPLY_ID => {
+            let id = msg.id;
+            let res = parse_reply_instantiate_data(msg)?;
+            let module_addr = deps.api.addr_validate(&res.contract_address)?;
+            validate_module_dao(deps.as_ref(), &env, &module_addr)?;
+
+            let mut pending = PENDING_RECONFIGURATION
+                .may_load(deps.storage)?
+                .ok_or(ContractError::UnknownReplyID {})?;
+            if id == RECONFIGURE_VOTING_MODULE_REPLY_ID {
+                pending.voting_registry_module = Some(module_addr);
+            } else {
+                pending.proposal_modules.push(module_addr);
+            }
+            pending.remaining -= 1;
+
+            if pending.remaining == 0 {
+                return commit_reconfiguration(deps, &env, pending);
+            }
+            PENDING_RECONFIGURATION.save(deps.storage, &pending)?;
+
+            Ok(Response::default().add_attribute("reconfigured_module", res.contract_address))
+        }
+        PAUSE_ALL_REPLY_ID => {
+            let contract = PENDING_PAUSES
+                .pop_front(deps.storage)?
+                .ok_or(ContractError::UnknownReplyID {})?;
+
+            Ok(match msg.result {
+                SubMsgResult::Ok(_) => Response::default().add_attribute("paused", contract),
+                SubMsgResult::Err(err) => Response::default()
+                    .add_attribute("pause_failed", contract)
+                    .add_attribute("error", err),
+            })
+        }
         _ => Err(ContractError::UnknownReplyID {}),
     }
 }
@@ -608,7 +1396,38 @@ pub(crate) fn derive_proposal_module_prefix(mut dividend: usize) -> StdResult<St
 
 #[cfg(test)]
 mod test {
-    use crate::contract::derive_proposal_module_prefix;
+    use crate::contract::{
+        commit_reconfiguration, cw20_transfers_to, derive_proposal_module_prefix, execute,
+        execute_pause_all, instantiate, query, query_dump_state_at_height, reply,
+        PAUSE_ALL_REPLY_ID,
+    };
+    use crate::error::ContractError;
+    use crate::msg::{ExecuteMsg, InstantiateMsg, PausableExecuteMsg, QueryMsg};
+    use crate::query::{
+        AdminNominationResponse, Cw20BalanceResponse, DumpStateAtHeightResponse, SubDao,
+    };
+    use crate::state::{
+        Config, PendingReconfiguration, ProposalModule, ProposalModuleStatus,
+        ACTIVE_PROPOSAL_MODULE_COUNT, CONFIG, EMERGENCY_PROPOSAL_MODULES, HISTORY_START_HEIGHT,
+        PAUSABLE_CONTRACTS, PAUSED, PAUSERS, PENDING_RECONFIGURATION, PROPOSAL_MODULES,
+        SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_REGISTRY_MODULE,
+    };
+    use admin_nomination::{NominationError, ADMIN};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockQuerier};
+    use cosmwasm_std::{
+        attr, coins, from_binary, to_binary, Addr, BankMsg, Binary, Coin, ContractInfoResponse,
+        ContractResult, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
+        StdError, StdResult, SubMsgResponse, SubMsgResult, SystemError, SystemResult, Uint128,
+        WasmMsg, WasmQuery,
+    };
+    use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+    use cw_multi_test::{custom_app, BasicApp, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
+    use cw_utils::{Duration, Expiration};
+    use cwd_interface::{Admin, ModuleInstantiateInfo};
+    use exec_control::pause::PauseError;
+    use neutron_sdk::bindings::msg::NeutronMsg;
     use std::collections::HashSet;
 
     #[test]
@@ -641,4 +1460,1274 @@ mod test {
             seen.insert(prefix);
         }
     }
+
+    #[test]
+    fn test_cw20_transfers_to() {
+        let dao = Addr::unchecked("dao");
+        let transfer = |token: &str, recipient: &str| {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::new(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        };
+        let msgs = vec![
+            transfer("token1", "dao"),
+            transfer("token2", "someone"),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token3".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: "someone".to_string(),
+                    recipient: "dao".to_string(),
+                    amount: Uint128::new(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao".to_string(),
+                amount: vec![],
+            }),
+        ];
+
+        // The DAO transferring its own tokens to itself doesn't add
+        // anything to the treasury.
+        assert_eq!(cw20_transfers_to(&msgs, &dao), vec!["token3".to_string()]);
+    }
+
+    #[test]
+    fn test_pause_all() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let pauser = Addr::unchecked("security_dao");
+        for contract in ["reserve", "distribution"] {
+            PAUSABLE_CONTRACTS
+                .save(deps.as_mut().storage, Addr::unchecked(contract), &Empty {})
+                .unwrap();
+        }
+
+        assert_eq!(
+            execute_pause_all(
+                deps.as_mut(),
+                env.clone(),
+                pauser.clone(),
+                Duration::Height(10),
+                None,
+                None,
+            )
+            .unwrap_err(),
+            ContractError::Unauthorized {}
+        );
+
+        PAUSERS
+            .save(deps.as_mut().storage, pauser.clone(), &Empty {})
+            .unwrap();
+        let res = execute_pause_all(
+            deps.as_mut(),
+            env.clone(),
+            pauser,
+            Duration::Height(10),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            PAUSED.load(deps.as_ref().storage).unwrap(),
+            Duration::Height(10).after(&env.block)
+        );
+        let paused: Vec<_> = res
+            .messages
+            .iter()
+            .map(|submsg| match &submsg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => contract_addr.clone(),
+                _ => panic!("unexpected message"),
+            })
+            .collect();
+        assert_eq!(paused, vec!["distribution", "reserve"]);
+
+        let res = reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: PAUSE_ALL_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.attributes, vec![attr("paused", "distribution")]);
+
+        let res = reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: PAUSE_ALL_REPLY_ID,
+                result: SubMsgResult::Err("already paused".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("pause_failed", "reserve"),
+                attr("error", "already paused")
+            ]
+        );
+
+        assert_eq!(
+            reply(
+                deps.as_mut(),
+                env,
+                Reply {
+                    id: PAUSE_ALL_REPLY_ID,
+                    result: SubMsgResult::Err("unexpected".to_string()),
+                },
+            )
+            .unwrap_err(),
+            ContractError::UnknownReplyID {}
+        );
+    }
+
+    #[test]
+    fn test_pause_all_duration() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let dao = env.contract.address.clone();
+        PAUSABLE_CONTRACTS
+            .save(deps.as_mut().storage, Addr::unchecked("reserve"), &Empty {})
+            .unwrap();
+
+        for duration in [Duration::Height(0), Duration::Height(200001)] {
+            assert!(matches!(
+                execute_pause_all(
+                    deps.as_mut(),
+                    env.clone(),
+                    dao.clone(),
+                    duration,
+                    None,
+                    None
+                )
+                .unwrap_err(),
+                ContractError::PauseError(PauseError::InvalidDuration(_))
+            ));
+        }
+        assert!(PAUSED.may_load(deps.as_ref().storage).unwrap().is_none());
+
+        // The reason and the operations are forwarded to the pausable
+        // contracts.
+        let res = execute_pause_all(
+            deps.as_mut(),
+            env.clone(),
+            dao.clone(),
+            Duration::Height(100),
+            Some("incident".to_string()),
+            Some(vec!["distribute".to_string()]),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reserve".to_string(),
+                msg: to_binary(&PausableExecuteMsg::Pause {
+                    duration: Duration::Height(100),
+                    reason: Some("incident".to_string()),
+                    operations: Some(vec!["distribute".to_string()]),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        // A shorter pause does not shorten the current one.
+        env.block.height += 10;
+        execute_pause_all(
+            deps.as_mut(),
+            env.clone(),
+            dao.clone(),
+            Duration::Height(10),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            PAUSED.load(deps.as_ref().storage).unwrap(),
+            Expiration::AtHeight(env.block.height + 90)
+        );
+
+        // A pause in other units can't be compared to the current one.
+        assert!(matches!(
+            execute_pause_all(
+                deps.as_mut(),
+                env.clone(),
+                dao.clone(),
+                Duration::Time(3600),
+                None,
+                None
+            )
+            .unwrap_err(),
+            ContractError::PauseError(PauseError::InvalidDuration(_))
+        ));
+
+        // A longer pause extends it.
+        execute_pause_all(
+            deps.as_mut(),
+            env.clone(),
+            dao,
+            Duration::Height(200),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            PAUSED.load(deps.as_ref().storage).unwrap(),
+            Expiration::AtHeight(env.block.height + 200)
+        );
+    }
+
+    #[test]
+    fn test_commit_reconfiguration() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let height = env.block.height;
+        let storage = deps.as_mut().storage;
+        for (idx, (module, status)) in [
+            ("old1", ProposalModuleStatus::Enabled),
+            ("old2", ProposalModuleStatus::Disabled),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let module = ProposalModule {
+                address: Addr::unchecked(module),
+                prefix: derive_proposal_module_prefix(idx).unwrap(),
+                status,
+            };
+            PROPOSAL_MODULES
+                .save(storage, module.address.clone(), &module, height)
+                .unwrap();
+        }
+        TOTAL_PROPOSAL_MODULE_COUNT
+            .save(storage, &2, height)
+            .unwrap();
+        ACTIVE_PROPOSAL_MODULE_COUNT
+            .save(storage, &1, height)
+            .unwrap();
+        EMERGENCY_PROPOSAL_MODULES
+            .save(storage, Addr::unchecked("old1"), &Empty {})
+            .unwrap();
+        let pending = PendingReconfiguration {
+            remaining: 0,
+            voting_registry_module: Some(Addr::unchecked("registry")),
+            proposal_modules: vec![Addr::unchecked("new1"), Addr::unchecked("new2")],
+        };
+        PENDING_RECONFIGURATION.save(storage, &pending).unwrap();
+
+        commit_reconfiguration(deps.as_mut(), &env, pending).unwrap();
+
+        let storage = deps.as_ref().storage;
+        assert_eq!(
+            VOTING_REGISTRY_MODULE.load(storage).unwrap(),
+            Addr::unchecked("registry")
+        );
+        let modules = PROPOSAL_MODULES
+            .range(storage, None, None, cosmwasm_std::Order::Ascending)
+            .map(|item| {
+                item.map(|(_, module)| (module.address.to_string(), module.prefix, module.status))
+            })
+            .collect::<cosmwasm_std::StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            modules,
+            vec![
+                (
+                    "new1".to_string(),
+                    "C".to_string(),
+                    ProposalModuleStatus::Enabled
+                ),
+                (
+                    "new2".to_string(),
+                    "D".to_string(),
+                    ProposalModuleStatus::Enabled
+                ),
+                (
+                    "old1".to_string(),
+                    "A".to_string(),
+                    ProposalModuleStatus::Disabled
+                ),
+                (
+                    "old2".to_string(),
+                    "B".to_string(),
+                    ProposalModuleStatus::Disabled
+                ),
+            ]
+        );
+        assert_eq!(ACTIVE_PROPOSAL_MODULE_COUNT.load(storage).unwrap(), 2);
+        assert_eq!(TOTAL_PROPOSAL_MODULE_COUNT.load(storage).unwrap(), 4);
+        assert!(!EMERGENCY_PROPOSAL_MODULES.has(storage, Addr::unchecked("old1")));
+        assert!(!PENDING_RECONFIGURATION.exists(storage));
+    }
+
+    #[test]
+    fn test_dump_state_at_height() {
+        let mut deps = mock_dependencies();
+        let storage = deps.as_mut().storage;
+        HISTORY_START_HEIGHT.save(storage, &10).unwrap();
+        let module = |address: &str, prefix: &str, status| ProposalModule {
+            address: Addr::unchecked(address),
+            prefix: prefix.to_string(),
+            status,
+        };
+
+        VOTING_REGISTRY_MODULE
+            .save(storage, &Addr::unchecked("registry"), 10)
+            .unwrap();
+        PROPOSAL_MODULES
+            .save(
+                storage,
+                Addr::unchecked("module1"),
+                &module("module1", "A", ProposalModuleStatus::Enabled),
+                10,
+            )
+            .unwrap();
+        ACTIVE_PROPOSAL_MODULE_COUNT.save(storage, &1, 10).unwrap();
+        TOTAL_PROPOSAL_MODULE_COUNT.save(storage, &1, 10).unwrap();
+        SUBDAO_LIST
+            .save(
+                storage,
+                &Addr::unchecked("subdao"),
+                &Some("charter".to_string()),
+                10,
+            )
+            .unwrap();
+
+        // At height 20 the module is swapped and the SubDAO removed.
+        VOTING_REGISTRY_MODULE
+            .save(storage, &Addr::unchecked("registry2"), 20)
+            .unwrap();
+        PROPOSAL_MODULES
+            .save(
+                storage,
+                Addr::unchecked("module1"),
+                &module("module1", "A", ProposalModuleStatus::Disabled),
+                20,
+            )
+            .unwrap();
+        PROPOSAL_MODULES
+            .save(
+                storage,
+                Addr::unchecked("module2"),
+                &module("module2", "B", ProposalModuleStatus::Enabled),
+                20,
+            )
+            .unwrap();
+        TOTAL_PROPOSAL_MODULE_COUNT.save(storage, &2, 20).unwrap();
+        SUBDAO_LIST
+            .remove(storage, &Addr::unchecked("subdao"), 20)
+            .unwrap();
+
+        let dump = |height| -> DumpStateAtHeightResponse {
+            from_binary(&query_dump_state_at_height(deps.as_ref(), height).unwrap()).unwrap()
+        };
+        let modules = |height, start_after: Option<&str>, limit| -> Vec<ProposalModule> {
+            let msg = QueryMsg::ProposalModulesAtHeight {
+                height,
+                start_after: start_after.map(str::to_string),
+                limit,
+            };
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+        };
+        let sub_daos = |height| -> Vec<SubDao> {
+            let msg = QueryMsg::ListSubDaosAtHeight {
+                height,
+                start_after: None,
+                limit: None,
+            };
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+        };
+
+        assert_eq!(
+            query_dump_state_at_height(deps.as_ref(), 5).unwrap_err(),
+            StdError::generic_err("state history is only recorded from height 10")
+        );
+        assert_eq!(
+            dump(10),
+            DumpStateAtHeightResponse {
+                height: 10,
+                voting_module: None,
+                active_proposal_module_count: 0,
+                total_proposal_module_count: 0,
+            }
+        );
+        assert_eq!(modules(10, None, None), vec![]);
+        assert_eq!(sub_daos(10), vec![]);
+
+        assert_eq!(
+            dump(15),
+            DumpStateAtHeightResponse {
+                height: 15,
+                voting_module: Some(Addr::unchecked("registry")),
+                active_proposal_module_count: 1,
+                total_proposal_module_count: 1,
+            }
+        );
+        assert_eq!(
+            modules(15, None, None),
+            vec![module("module1", "A", ProposalModuleStatus::Enabled)]
+        );
+        assert_eq!(
+            sub_daos(15),
+            vec![SubDao {
+                addr: "subdao".to_string(),
+                charter: Some("charter".to_string()),
+            }]
+        );
+
+        assert_eq!(
+            dump(25),
+            DumpStateAtHeightResponse {
+                height: 25,
+                voting_module: Some(Addr::unchecked("registry2")),
+                active_proposal_module_count: 1,
+                total_proposal_module_count: 2,
+            }
+        );
+        assert_eq!(
+            modules(25, None, None),
+            vec![
+                module("module1", "A", ProposalModuleStatus::Disabled),
+                module("module2", "B", ProposalModuleStatus::Enabled),
+            ]
+        );
+        assert_eq!(
+            modules(25, None, Some(1)),
+            vec![module("module1", "A", ProposalModuleStatus::Disabled)]
+        );
+        assert_eq!(
+            modules(25, Some("module1"), Some(1)),
+            vec![module("module2", "B", ProposalModuleStatus::Enabled)]
+        );
+        assert_eq!(sub_daos(25), vec![]);
+    }
+
+    /// Makes the DAO in `mock_env` its own contract admin.
+    fn set_dao_as_contract_admin(querier: &mut MockQuerier) {
+        querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { contract_addr } => {
+                let mut info = ContractInfoResponse::new(1, "creator");
+                info.admin = Some(contract_addr.clone());
+                SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+    }
+
+    fn query_nomination(deps: Deps) -> Option<Addr> {
+        let res: AdminNominationResponse =
+            from_binary(&query(deps, mock_env(), QueryMsg::AdminNomination {}).unwrap()).unwrap();
+        res.nomination
+    }
+
+    #[test]
+    fn test_admin_nomination() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let dao = env.contract.address.to_string();
+        ADMIN
+            .save(deps.as_mut().storage, &env.contract.address)
+            .unwrap();
+        set_dao_as_contract_admin(&mut deps.querier);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("nominee", &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "nominee".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::Unauthorized {})
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "typo".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query_nomination(deps.as_ref()),
+            Some(Addr::unchecked("typo"))
+        );
+
+        // A pending nomination has to be withdrawn before nominating
+        // someone else.
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "nominee".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::PendingNomination {})
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::WithdrawAdminNomination {},
+        )
+        .unwrap();
+        assert_eq!(query_nomination(deps.as_ref()), None);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("typo", &[]),
+            ExecuteMsg::AcceptAdminNomination {},
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::NoNomination {})
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "nominee".to_string(),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("nominee", &[]),
+            ExecuteMsg::AcceptAdminNomination {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr: dao.clone(),
+                admin: "nominee".to_string(),
+            })
+        );
+
+        let admin: Addr =
+            from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Admin {}).unwrap()).unwrap();
+        assert_eq!(admin, Addr::unchecked("nominee"));
+        assert_eq!(query_nomination(deps.as_ref()), None);
+
+        // The DAO is no longer the admin.
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "other".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::Unauthorized {})
+        );
+    }
+
+    /// Mocks two cw20 tokens: `token` answers all queries while `broken`
+    /// answers token info queries but fails balance queries. All other
+    /// addresses are not contracts.
+    fn mock_cw20_tokens(querier: &mut MockQuerier) {
+        querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                if contract_addr != "token" && contract_addr != "broken" {
+                    return SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    });
+                }
+                let res = match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                        name: contract_addr.clone(),
+                        symbol: "TKN".to_string(),
+                        decimals: 6,
+                        total_supply: Uint128::new(1000),
+                    })
+                    .unwrap(),
+                    Cw20QueryMsg::Balance { .. } if contract_addr == "token" => {
+                        to_binary(&BalanceResponse {
+                            balance: Uint128::new(100),
+                        })
+                        .unwrap()
+                    }
+                    _ => return SystemResult::Ok(ContractResult::Err("broken".to_string())),
+                };
+                SystemResult::Ok(ContractResult::Ok(res))
+            }
+            _ => unimplemented!(),
+        });
+    }
+
+    fn query_cw20_list(deps: Deps) -> Vec<Addr> {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::Cw20TokenList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_update_cw20_list() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let dao = env.contract.address.to_string();
+        mock_cw20_tokens(&mut deps.querier);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("someone", &[]),
+            ExecuteMsg::UpdateCw20List {
+                to_add: vec!["token".to_string()],
+                to_remove: vec![],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Only cw20 token contracts may be added.
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::UpdateCw20List {
+                to_add: vec!["not_a_token".to_string()],
+                to_remove: vec![],
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::UpdateCw20List {
+                to_add: vec!["token".to_string(), "broken".to_string()],
+                to_remove: vec![],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query_cw20_list(deps.as_ref()),
+            vec![Addr::unchecked("broken"), Addr::unchecked("token")]
+        );
+
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info(&dao, &[]),
+            ExecuteMsg::UpdateCw20List {
+                to_add: vec![],
+                to_remove: vec!["broken".to_string()],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query_cw20_list(deps.as_ref()),
+            vec![Addr::unchecked("token")]
+        );
+    }
+
+    #[test]
+    fn test_receive_cw20() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        mock_cw20_tokens(&mut deps.querier);
+        let receive = |sender: &str| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: sender.to_string(),
+                amount: Uint128::new(10),
+                msg: Default::default(),
+            })
+        };
+
+        let dao = env.contract.address.to_string();
+
+        // A receive hook sent by an account doesn't register anything.
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("someone", &[]),
+            receive(&dao),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+        assert!(query_cw20_list(deps.as_ref()).is_empty());
+
+        // Tokens sent by others are accepted without being registered.
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("token", &[]),
+            receive("someone"),
+        )
+        .unwrap();
+        assert!(query_cw20_list(deps.as_ref()).is_empty());
+
+        // Tokens sent by the DAO or its proposal modules are registered.
+        PROPOSAL_MODULES
+            .save(
+                deps.as_mut().storage,
+                Addr::unchecked("proposal_module"),
+                &ProposalModule {
+                    address: Addr::unchecked("proposal_module"),
+                    prefix: "A".to_string(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+                env.block.height,
+            )
+            .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("token", &[]),
+            receive(&dao),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("broken", &[]),
+            receive("proposal_module"),
+        )
+        .unwrap();
+        assert_eq!(
+            query_cw20_list(deps.as_ref()),
+            vec![Addr::unchecked("broken"), Addr::unchecked("token")]
+        );
+
+        // The token failing its balance query is left out.
+        let balances: Vec<Cw20BalanceResponse> = from_binary(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::Cw20Balances {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            balances,
+            vec![Cw20BalanceResponse {
+                addr: Addr::unchecked("token"),
+                balance: Uint128::new(100),
+            }]
+        );
+    }
+
+    /// Registers an enabled `emergency` and an enabled `regular` proposal
+    /// module and pauses the DAO.
+    fn setup_paused_dao(deps: DepsMut, env: &Env) {
+        CONFIG
+            .save(
+                deps.storage,
+                &Config {
+                    name: "name".to_string(),
+                    description: "description".to_string(),
+                    dao_uri: None,
+                },
+            )
+            .unwrap();
+        for (prefix, module) in [("A", "emergency"), ("B", "regular")] {
+            PROPOSAL_MODULES
+                .save(
+                    deps.storage,
+                    Addr::unchecked(module),
+                    &ProposalModule {
+                        address: Addr::unchecked(module),
+                        prefix: prefix.to_string(),
+                        status: ProposalModuleStatus::Enabled,
+                    },
+                    env.block.height,
+                )
+                .unwrap();
+        }
+        EMERGENCY_PROPOSAL_MODULES
+            .save(deps.storage, Addr::unchecked("emergency"), &Empty {})
+            .unwrap();
+        PAUSED
+            .save(deps.storage, &Expiration::AtHeight(env.block.height + 10))
+            .unwrap();
+    }
+
+    fn dao_call(env: &Env, msg: &ExecuteMsg, funds: &[Coin]) -> CosmosMsg<NeutronMsg> {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(msg).unwrap(),
+            funds: funds.to_vec(),
+        })
+    }
+
+    #[test]
+    fn test_emergency_msgs_while_paused() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        setup_paused_dao(deps.as_mut(), &env);
+
+        let allowed = vec![
+            CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: "voting".to_string(),
+                new_code_id: 2,
+                msg: to_binary(&Empty {}).unwrap(),
+            }),
+            dao_call(&env, &ExecuteMsg::Unpause {}, &[]),
+            dao_call(
+                &env,
+                &ExecuteMsg::UpdateConfig {
+                    config: Config {
+                        name: "new name".to_string(),
+                        description: "description".to_string(),
+                        dao_uri: None,
+                    },
+                },
+                &[],
+            ),
+        ];
+
+        // Regular proposal modules can't execute anything while the DAO
+        // is paused.
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("regular", &[]),
+            ExecuteMsg::ExecuteProposalHook {
+                msgs: allowed.clone(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Paused {});
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("emergency", &[]),
+            ExecuteMsg::ExecuteProposalHook {
+                msgs: allowed.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+            allowed
+        );
+
+        // Anything else is rejected, even when bundled with allowed
+        // messages.
+        let forbidden = vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "someone".to_string(),
+                amount: coins(100, "untrn"),
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "other".to_string(),
+                msg: to_binary(&ExecuteMsg::Unpause {}).unwrap(),
+                funds: vec![],
+            }),
+            dao_call(&env, &ExecuteMsg::Unpause {}, &coins(1, "untrn")),
+            dao_call(
+                &env,
+                &ExecuteMsg::SetItem {
+                    key: "key".to_string(),
+                    addr: "addr".to_string(),
+                },
+                &[],
+            ),
+            dao_call(
+                &env,
+                &ExecuteMsg::UpdateCw20List {
+                    to_add: vec![],
+                    to_remove: vec![],
+                },
+                &[],
+            ),
+        ];
+        for msg in forbidden {
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("emergency", &[]),
+                ExecuteMsg::ExecuteProposalHook {
+                    msgs: vec![allowed[0].clone(), msg],
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::EmergencyMsgNotAllowed {});
+        }
+
+        // The DAO itself may unpause, after which everything works again.
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(env.contract.address.as_str(), &[]),
+            ExecuteMsg::Unpause {},
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("regular", &[]),
+            ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "someone".to_string(),
+                    amount: coins(100, "untrn"),
+                })],
+            },
+        )
+        .unwrap();
+    }
+
+    #[cw_serde]
+    struct MockModuleInstantiateMsg {
+        /// Reported by the `Dao {}` query instead of the instantiator.
+        dao: Option<String>,
+        /// Makes the instantiation fail.
+        fail: bool,
+        /// Dispatched when the module is instantiated.
+        msgs: Vec<CosmosMsg<NeutronMsg>>,
+    }
+
+    #[cw_serde]
+    enum MockModuleExecuteMsg {
+        /// Passes the messages to the DAO as if a proposal had passed.
+        Execute { msgs: Vec<CosmosMsg<NeutronMsg>> },
+    }
+
+    #[cw_serde]
+    enum MockModuleQueryMsg {
+        Dao {},
+    }
+
+    const MOCK_MODULE_DAO: Item<Addr> = Item::new("dao");
+
+    fn mock_module_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: MockModuleInstantiateMsg,
+    ) -> StdResult<Response<NeutronMsg>> {
+        if msg.fail {
+            return Err(StdError::generic_err("instantiation failed"));
+        }
+        let dao = match msg.dao {
+            Some(dao) => deps.api.addr_validate(&dao)?,
+            None => info.sender,
+        };
+        MOCK_MODULE_DAO.save(deps.storage, &dao)?;
+        Ok(Response::default().add_messages(msg.msgs))
+    }
+
+    fn mock_module_execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: MockModuleExecuteMsg,
+    ) -> StdResult<Response<NeutronMsg>> {
+        let MockModuleExecuteMsg::Execute { msgs } = msg;
+        Ok(Response::default().add_message(WasmMsg::Execute {
+            contract_addr: MOCK_MODULE_DAO.load(deps.storage)?.into_string(),
+            msg: to_binary(&ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        }))
+    }
+
+    fn mock_module_query(deps: Deps, _env: Env, msg: MockModuleQueryMsg) -> StdResult<Binary> {
+        match msg {
+            MockModuleQueryMsg::Dao {} => to_binary(&MOCK_MODULE_DAO.load(deps.storage)?),
+        }
+    }
+
+    fn mock_module(code_id: u64, msg: MockModuleInstantiateMsg) -> ModuleInstantiateInfo {
+        ModuleInstantiateInfo {
+            code_id,
+            msg: to_binary(&msg).unwrap(),
+            admin: Some(Admin::CoreModule {}),
+            label: "module".to_string(),
+        }
+    }
+
+    fn valid_module() -> MockModuleInstantiateMsg {
+        MockModuleInstantiateMsg {
+            dao: None,
+            fail: false,
+            msgs: vec![],
+        }
+    }
+
+    /// Instantiates a DAO whose voting and proposal modules are mock
+    /// modules. Returns the app, the DAO and the mock module code ID.
+    fn setup_reconfigure_test() -> (BasicApp<NeutronMsg>, Addr, u64) {
+        let mut app = custom_app::<NeutronMsg, Empty, _>(|_, _, _| {});
+        let core_id = app.store_code(Box::new(
+            ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+        ));
+        let module_id = app.store_code(Box::new(ContractWrapper::new(
+            mock_module_execute,
+            mock_module_instantiate,
+            mock_module_query,
+        )));
+        let dao = app
+            .instantiate_contract(
+                core_id,
+                Addr::unchecked("creator"),
+                &InstantiateMsg {
+                    name: "DAO".to_string(),
+                    description: "A DAO.".to_string(),
+                    voting_registry_module_instantiate_info: mock_module(module_id, valid_module()),
+                    proposal_modules_instantiate_info: vec![mock_module(module_id, valid_module())],
+                    initial_items: None,
+                    dao_uri: None,
+                },
+                &[],
+                "dao",
+                None,
+            )
+            .unwrap();
+        (app, dao, module_id)
+    }
+
+    /// Executes the messages through the proposal module. Returns the
+    /// root cause of the error on failure.
+    fn execute_via_module(
+        app: &mut BasicApp<NeutronMsg>,
+        module: &Addr,
+        msgs: Vec<CosmosMsg<NeutronMsg>>,
+    ) -> Result<(), String> {
+        app.execute_contract(
+            Addr::unchecked("proposer"),
+            module.clone(),
+            &MockModuleExecuteMsg::Execute { msgs },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|err| err.root_cause().to_string())
+    }
+
+    fn reconfigure_msg(
+        dao: &Addr,
+        voting_registry_module: ModuleInstantiateInfo,
+        proposal_modules: Vec<ModuleInstantiateInfo>,
+    ) -> CosmosMsg<NeutronMsg> {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: dao.to_string(),
+            msg: to_binary(&ExecuteMsg::ReconfigureModules {
+                voting_registry_module,
+                proposal_modules,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    }
+
+    fn query_modules(app: &BasicApp<NeutronMsg>, dao: &Addr) -> (Addr, Vec<ProposalModule>) {
+        let voting_module = app
+            .wrap()
+            .query_wasm_smart(dao, &QueryMsg::VotingModule {})
+            .unwrap();
+        let proposal_modules = app
+            .wrap()
+            .query_wasm_smart(
+                dao,
+                &QueryMsg::ProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        (voting_module, proposal_modules)
+    }
+
+    #[test]
+    fn test_reconfigure_modules() {
+        let (mut app, dao, module_id) = setup_reconfigure_test();
+        let (old_voting_module, old_proposal_modules) = query_modules(&app, &dao);
+        let old_proposal_module = old_proposal_modules[0].address.clone();
+
+        let update_emergency = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: dao.to_string(),
+            msg: to_binary(&ExecuteMsg::UpdateEmergencyProposalModules {
+                to_add: vec![old_proposal_module.to_string()],
+                to_remove: vec![],
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        execute_via_module(&mut app, &old_proposal_module, vec![update_emergency]).unwrap();
+
+        let reconfigure = reconfigure_msg(
+            &dao,
+            mock_module(module_id, valid_module()),
+            vec![
+                mock_module(module_id, valid_module()),
+                mock_module(module_id, valid_module()),
+            ],
+        );
+        execute_via_module(&mut app, &old_proposal_module, vec![reconfigure]).unwrap();
+
+        let (voting_module, proposal_modules) = query_modules(&app, &dao);
+        assert_ne!(voting_module, old_voting_module);
+        let mut modules = proposal_modules
+            .into_iter()
+            .map(|module| {
+                (
+                    module.prefix,
+                    module.status,
+                    module.address == old_proposal_module,
+                )
+            })
+            .collect::<Vec<_>>();
+        modules.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            modules,
+            vec![
+                ("A".to_string(), ProposalModuleStatus::Disabled, true),
+                ("B".to_string(), ProposalModuleStatus::Enabled, false),
+                ("C".to_string(), ProposalModuleStatus::Enabled, false),
+            ]
+        );
+        let emergency_modules: Vec<Addr> = app
+            .wrap()
+            .query_wasm_smart(
+                &dao,
+                &QueryMsg::EmergencyProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(emergency_modules.is_empty());
+
+        // The replaced module may no longer execute anything.
+        let err = execute_via_module(&mut app, &old_proposal_module, vec![]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ModuleDisabledCannotExecute {
+                address: old_proposal_module
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_reconfigure_modules_rollback() {
+        let (mut app, dao, module_id) = setup_reconfigure_test();
+        let old_modules = query_modules(&app, &dao);
+        let proposal_module = old_modules.1[0].address.clone();
+
+        // A module that belongs to another DAO is rejected.
+        let reconfigure = reconfigure_msg(
+            &dao,
+            mock_module(module_id, valid_module()),
+            vec![mock_module(
+                module_id,
+                MockModuleInstantiateMsg {
+                    dao: Some("other_dao".to_string()),
+                    ..valid_module()
+                },
+            )],
+        );
+        let err = execute_via_module(&mut app, &proposal_module, vec![reconfigure]).unwrap_err();
+        assert!(err.ends_with("does not belong to this DAO."), "{err}");
+        assert_eq!(query_modules(&app, &dao), old_modules);
+
+        // A single failed instantiation reverts the modules that were
+        // already instantiated.
+        let reconfigure = reconfigure_msg(
+            &dao,
+            mock_module(module_id, valid_module()),
+            vec![
+                mock_module(module_id, valid_module()),
+                mock_module(
+                    module_id,
+                    MockModuleInstantiateMsg {
+                        fail: true,
+                        ..valid_module()
+                    },
+                ),
+            ],
+        );
+        let err = execute_via_module(&mut app, &proposal_module, vec![reconfigure]).unwrap_err();
+        assert_eq!(err, "Generic error: instantiation failed");
+        assert_eq!(query_modules(&app, &dao), old_modules);
+
+        // Nothing is left pending, so the DAO can still reconfigure.
+        let reconfigure = reconfigure_msg(
+            &dao,
+            mock_module(module_id, valid_module()),
+            vec![mock_module(module_id, valid_module())],
+        );
+        execute_via_module(&mut app, &proposal_module, vec![reconfigure]).unwrap();
+        assert_ne!(query_modules(&app, &dao), old_modules);
+    }
+
+    #[test]
+    fn test_reconfigure_modules_in_progress() {
+        let (mut app, dao, module_id) = setup_reconfigure_test();
+        let old_modules = query_modules(&app, &dao);
+        let proposal_module = old_modules.1[0].address.clone();
+
+        // The new module calls back into the DAO through the old
+        // proposal module, which is still enabled at that point.
+        let nested = reconfigure_msg(
+            &dao,
+            mock_module(module_id, valid_module()),
+            vec![mock_module(module_id, valid_module())],
+        );
+        let reenter = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: proposal_module.to_string(),
+            msg: to_binary(&MockModuleExecuteMsg::Execute { msgs: vec![nested] }).unwrap(),
+            funds: vec![],
+        });
+        let reconfigure = reconfigure_msg(
+            &dao,
+            mock_module(module_id, valid_module()),
+            vec![mock_module(
+                module_id,
+                MockModuleInstantiateMsg {
+                    msgs: vec![reenter],
+                    ..valid_module()
+                },
+            )],
+        );
+        let err = execute_via_module(&mut app, &proposal_module, vec![reconfigure]).unwrap_err();
+        assert_eq!(err, ContractError::ReconfigurationInProgress {}.to_string());
+        assert_eq!(query_modules(&app, &dao), old_modules);
+    }
 }
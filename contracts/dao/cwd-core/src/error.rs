@@ -1,5 +1,7 @@
+use admin_nomination::NominationError;
 use cosmwasm_std::{Addr, StdError};
 use cw_utils::ParseReplyError;
+use exec_control::pause::PauseError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -10,6 +12,9 @@ pub enum ContractError {
     #[error(transparent)]
     ParseReplyError(#[from] ParseReplyError),
 
+    #[error(transparent)]
+    PauseError(#[from] PauseError),
+
     #[error("config name cannot be empty.")]
     NameIsEmpty {},
 
@@ -45,4 +50,25 @@ pub enum ContractError {
 
     #[error("Proposal module with address is disabled and cannot execute messages.")]
     ModuleDisabledCannotExecute { address: Addr },
+
+    #[error(transparent)]
+    Nomination(#[from] NominationError),
+
+    #[error("Only unpause, migrate and config update messages can be executed while the contract is paused.")]
+    EmergencyMsgNotAllowed {},
+
+    #[error("Contract with address ({address}) cannot be registered as a pausable contract.")]
+    InvalidPausableContract { address: Addr },
+
+    #[error("Module with address ({address}) does not belong to this DAO.")]
+    InvalidModuleDao { address: Addr },
+
+    #[error("Modules are already being reconfigured.")]
+    ReconfigurationInProgress {},
+
+    #[error("Cannot migrate from {contract} version {version} with this message.")]
+    InvalidMigrationSource { contract: String, version: String },
+
+    #[error("Cannot migrate from version {from} to the older version {to}.")]
+    DowngradeNotAllowed { from: String, to: String },
 }
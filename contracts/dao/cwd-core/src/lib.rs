@@ -11,10 +11,12 @@
 //! In additon to the wiki spec this contract may also pause. To do so a
 //! `Pause` message must by executed by a proposal module. Pausing the
 //! core module will stop all actions on the module for the duration of
-//! the pause.
+//! the pause. The only exception are emergency proposal modules, which
+//! may still unpause the DAO, update its config and migrate contracts.
 
 pub mod contract;
 mod error;
+mod migration;
 pub mod msg;
 pub mod query;
 pub mod state;
@@ -0,0 +1,340 @@
+use admin_nomination::ADMIN;
+use cosmwasm_std::{Addr, DepsMut, Env, Order, StdError, StdResult};
+use cw2::ContractVersion;
+use cw_core_v1 as v1;
+use semver::Version;
+
+use crate::contract::{derive_proposal_module_prefix, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::query::SubDao;
+use crate::state::{
+    Config, ProposalModule, ProposalModuleStatus, ACTIVE_PROPOSAL_MODULE_COUNT, CONFIG,
+    HISTORY_START_HEIGHT, PROPOSAL_MODULES, SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT,
+};
+use crate::ContractError;
+
+/// The name DAO DAO v1 core contracts are stored under.
+pub(crate) const V1_CONTRACT_NAME: &str = "crates.io:cw-core";
+
+/// A state transform bringing the storage up to the layout of the
+/// contract version it is registered with.
+type MigrationStep = fn(DepsMut, &Env) -> StdResult<()>;
+
+/// The state transforms of the compatible contract versions, oldest
+/// first. A migration runs every transform registered with a version
+/// newer than the stored one and not newer than the one being
+/// migrated to. New transforms are appended here.
+const MIGRATION_STEPS: &[(&str, MigrationStep)] =
+    &[("0.3.0", set_default_admin), ("0.3.0", start_state_history)];
+
+/// Converts the state of a DAO DAO v1 core contract to the layout of
+/// the first version of this contract, then runs every registered
+/// state transform.
+pub(crate) fn migrate_from_v1(
+    mut deps: DepsMut,
+    env: &Env,
+    stored: &ContractVersion,
+    dao_uri: Option<String>,
+    sub_daos: Vec<SubDao>,
+) -> Result<(), ContractError> {
+    if stored.contract != V1_CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationSource {
+            contract: stored.contract.clone(),
+            version: stored.version.clone(),
+        });
+    }
+
+    // Config: the v1 image and auto-registration settings are dropped,
+    // the DAOstar URI is added.
+    let v1_config = v1::state::CONFIG.load(deps.storage)?;
+    let config = Config {
+        name: v1_config.name,
+        description: v1_config.description,
+        dao_uri,
+    };
+    config.validate()?;
+    CONFIG.save(deps.storage, &config)?;
+    v1::state::CONFIG.remove(deps.storage);
+
+    // Proposal modules: v1 only stores the addresses and all of the
+    // modules are enabled.
+    let addresses = v1::state::PROPOSAL_MODULES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for (idx, address) in addresses.iter().enumerate() {
+        let module = ProposalModule {
+            address: address.clone(),
+            prefix: derive_proposal_module_prefix(idx)?,
+            status: ProposalModuleStatus::Enabled,
+        };
+        PROPOSAL_MODULES.save(deps.storage, address.clone(), &module, env.block.height)?;
+        v1::state::PROPOSAL_MODULES.remove(deps.storage, address.clone());
+    }
+    let module_count = addresses.len() as u32;
+    TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &module_count, env.block.height)?;
+    ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &module_count, env.block.height)?;
+
+    // SubDAOs: v1 has no list of SubDAOs, so it is seeded from the
+    // migrate message.
+    for subdao in sub_daos {
+        let addr = deps.api.addr_validate(&subdao.addr)?;
+        SUBDAO_LIST.save(deps.storage, &addr, &subdao.charter, env.block.height)?;
+    }
+
+    run_migration_steps(
+        deps.branch(),
+        env,
+        &Version::new(0, 0, 0),
+        &parse_version(CONTRACT_VERSION)?,
+    )
+}
+
+/// Runs the state transforms needed to bring the storage of a
+/// compatible contract of the `stored` version up to the current
+/// version. Downgrades are refused.
+pub(crate) fn migrate_from_compatible(
+    deps: DepsMut,
+    env: &Env,
+    stored: &ContractVersion,
+) -> Result<(), ContractError> {
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationSource {
+            contract: stored.contract.clone(),
+            version: stored.version.clone(),
+        });
+    }
+
+    let from = parse_version(&stored.version)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(ContractError::DowngradeNotAllowed {
+            from: stored.version.clone(),
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    run_migration_steps(deps, env, &from, &to)
+}
+
+/// Runs the registered state transforms with versions in the
+/// (`from`, `to`] range, oldest first.
+fn run_migration_steps(
+    mut deps: DepsMut,
+    env: &Env,
+    from: &Version,
+    to: &Version,
+) -> Result<(), ContractError> {
+    for (version, step) in MIGRATION_STEPS {
+        let version = parse_version(version)?;
+        if &version > from && &version <= to {
+            step(deps.branch(), env)?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|err| StdError::generic_err(format!("invalid version {version}: {err}")))
+}
+
+/// 0.3.0 introduces the DAO admin. Contracts instantiated before the
+/// admin was introduced are their own admin.
+fn set_default_admin(deps: DepsMut, env: &Env) -> StdResult<()> {
+    if ADMIN.may_load(deps.storage)?.is_none() {
+        ADMIN.save(deps.storage, &env.contract.address)?;
+    }
+    Ok(())
+}
+
+/// 0.3.0 starts recording the history of the voting module, the
+/// proposal modules and the SubDAOs. The entries stored before are
+/// written again so that each of them has a changelog entry to be
+/// found by the queries of past state. The state before the migration
+/// block is unknown, the history starts at the next block.
+fn start_state_history(deps: DepsMut, env: &Env) -> StdResult<()> {
+    let modules = PROPOSAL_MODULES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (address, module) in modules {
+        PROPOSAL_MODULES.save(deps.storage, address, &module, env.block.height)?;
+    }
+    let sub_daos = SUBDAO_LIST
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (address, charter) in sub_daos {
+        SUBDAO_LIST.save(deps.storage, &address, &charter, env.block.height)?;
+    }
+    HISTORY_START_HEIGHT.save(deps.storage, &(env.block.height + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::Empty;
+
+    fn version(contract: &str, version: &str) -> ContractVersion {
+        ContractVersion {
+            contract: contract.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_migrate_from_compatible() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        assert_eq!(
+            migrate_from_compatible(deps.as_mut(), &env, &version(CONTRACT_NAME, "99.0.0"))
+                .unwrap_err(),
+            ContractError::DowngradeNotAllowed {
+                from: "99.0.0".to_string(),
+                to: CONTRACT_VERSION.to_string(),
+            }
+        );
+        assert_eq!(
+            migrate_from_compatible(deps.as_mut(), &env, &version(V1_CONTRACT_NAME, "0.1.0"))
+                .unwrap_err(),
+            ContractError::InvalidMigrationSource {
+                contract: V1_CONTRACT_NAME.to_string(),
+                version: "0.1.0".to_string(),
+            }
+        );
+
+        // 0.2.0 stores the proposal modules without their history.
+        let module = ProposalModule {
+            address: Addr::unchecked("module"),
+            prefix: "A".to_string(),
+            status: ProposalModuleStatus::Enabled,
+        };
+        cw_storage_plus::Map::<Addr, ProposalModule>::new("proposal_modules_v2")
+            .save(deps.as_mut().storage, module.address.clone(), &module)
+            .unwrap();
+
+        migrate_from_compatible(deps.as_mut(), &env, &version(CONTRACT_NAME, "0.2.0")).unwrap();
+        assert_eq!(
+            ADMIN.load(deps.as_ref().storage).unwrap(),
+            env.contract.address
+        );
+        assert_eq!(
+            HISTORY_START_HEIGHT.load(deps.as_ref().storage).unwrap(),
+            env.block.height + 1
+        );
+        assert_eq!(
+            PROPOSAL_MODULES
+                .changelog()
+                .keys(deps.as_ref().storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec![(module.address.clone(), env.block.height)]
+        );
+        assert_eq!(
+            PROPOSAL_MODULES
+                .may_load_at_height(
+                    deps.as_ref().storage,
+                    module.address.clone(),
+                    env.block.height + 1
+                )
+                .unwrap(),
+            Some(module)
+        );
+    }
+
+    #[test]
+    fn test_migrate_from_v1() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        v1::state::CONFIG
+            .save(
+                deps.as_mut().storage,
+                &v1::state::Config {
+                    name: "name".to_string(),
+                    description: "description".to_string(),
+                    image_url: None,
+                    automatically_add_cw20s: true,
+                    automatically_add_cw721s: true,
+                },
+            )
+            .unwrap();
+        for module in ["module1", "module2"] {
+            v1::state::PROPOSAL_MODULES
+                .save(deps.as_mut().storage, Addr::unchecked(module), &Empty {})
+                .unwrap();
+        }
+
+        assert_eq!(
+            migrate_from_v1(
+                deps.as_mut(),
+                &env,
+                &version(CONTRACT_NAME, "0.2.0"),
+                None,
+                vec![],
+            )
+            .unwrap_err(),
+            ContractError::InvalidMigrationSource {
+                contract: CONTRACT_NAME.to_string(),
+                version: "0.2.0".to_string(),
+            }
+        );
+
+        migrate_from_v1(
+            deps.as_mut(),
+            &env,
+            &version(V1_CONTRACT_NAME, "0.1.0"),
+            Some("https://dao.uri".to_string()),
+            vec![SubDao {
+                addr: "subdao".to_string(),
+                charter: Some("charter".to_string()),
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(
+            CONFIG.load(deps.as_ref().storage).unwrap(),
+            Config {
+                name: "name".to_string(),
+                description: "description".to_string(),
+                dao_uri: Some("https://dao.uri".to_string()),
+            }
+        );
+        let modules = PROPOSAL_MODULES
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, module)| module))
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            modules,
+            vec![
+                ProposalModule {
+                    address: Addr::unchecked("module1"),
+                    prefix: "A".to_string(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+                ProposalModule {
+                    address: Addr::unchecked("module2"),
+                    prefix: "B".to_string(),
+                    status: ProposalModuleStatus::Enabled,
+                },
+            ]
+        );
+        assert_eq!(
+            TOTAL_PROPOSAL_MODULE_COUNT
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            SUBDAO_LIST
+                .load(deps.as_ref().storage, &Addr::unchecked("subdao"))
+                .unwrap(),
+            Some("charter".to_string())
+        );
+        assert_eq!(
+            ADMIN.load(deps.as_ref().storage).unwrap(),
+            env.contract.address
+        );
+    }
+}
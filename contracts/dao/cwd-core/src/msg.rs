@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, CosmosMsg};
+use cosmwasm_std::{Addr, Coin, CosmosMsg};
+use cw20::Cw20ReceiveMsg;
 use cw_utils::Duration;
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
@@ -53,9 +54,22 @@ pub enum ExecuteMsg {
     /// Callable by proposal modules. The DAO will execute the
     /// messages in the hook in order.
     ExecuteProposalHook { msgs: Vec<CosmosMsg<NeutronMsg>> },
+    /// Callable by the admin. Nominates a new admin. The nominee
+    /// must accept the nomination with `AcceptAdminNomination`
+    /// before it takes effect.
+    NominateAdmin { admin: String },
+    /// Callable by the nominated admin. Makes the sender the new
+    /// admin of the DAO. If the DAO is its own contract admin, the
+    /// contract admin is transferred to the sender as well.
+    AcceptAdminNomination {},
+    /// Callable by the admin. Withdraws the pending admin
+    /// nomination.
+    WithdrawAdminNomination {},
     /// Pauses the DAO for a set duration.
     /// When paused the DAO is unable to execute proposals
     Pause { duration: Duration },
+    /// Callable by the core contract. Unpauses the DAO.
+    Unpause {},
     /// Removes an item from the governance contract's item map.
     RemoveItem { key: String },
     /// Adds an item to the governance contract's item map. If the
@@ -78,11 +92,78 @@ pub enum ExecuteMsg {
     /// voting module with a new one instantiated by the governance
     /// contract.
     UpdateVotingModule { module: ModuleInstantiateInfo },
+    /// Callable by the core contract. Instantiates a new voting
+    /// registry module and a new set of proposal modules, then
+    /// replaces the current voting module with the new one and
+    /// disables all current proposal modules in favour of the new
+    /// ones. Each new module must report this DAO in its `Dao {}`
+    /// query. Nothing is changed unless all of the modules are
+    /// instantiated and valid.
+    ReconfigureModules {
+        voting_registry_module: ModuleInstantiateInfo,
+        proposal_modules: Vec<ModuleInstantiateInfo>,
+    },
     /// Update the core module to add/remove SubDAOs and their charters
     UpdateSubDaos {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Executed when the contract receives a cw20 token. If the
+    /// tokens were sent by the DAO or one of its proposal modules, the
+    /// token is registered in the treasury's cw20 list if it answers
+    /// cw20 token info queries.
+    Receive(Cw20ReceiveMsg),
+    /// Callable by the core contract. Updates the list of cw20
+    /// tokens held by the treasury.
+    UpdateCw20List {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Updates the list of proposal
+    /// modules whose `ExecuteProposalHook` may still run while the
+    /// DAO is paused. Such hooks are limited to unpausing the DAO,
+    /// updating its config and migrating contracts.
+    UpdateEmergencyProposalModules {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Updates the list of contracts
+    /// paused along with the DAO by `PauseAll`.
+    UpdatePausableContracts {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract. Updates the list of addresses
+    /// allowed to call `PauseAll`.
+    UpdatePausers {
+        to_add: Vec<String>,
+        to_remove: Vec<String>,
+    },
+    /// Callable by the core contract and the pausers. Pauses the DAO
+    /// and sends `Pause` to every registered pausable contract with
+    /// the given `reason` and `operations`. A pause in effect is
+    /// never shortened. A contract failing to pause does not prevent
+    /// the others from being paused; the outcome for each contract is
+    /// reported in the response attributes.
+    PauseAll {
+        duration: Duration,
+        reason: Option<String>,
+        /// The operations to pause on the pausable contracts. `None`
+        /// pauses them entirely. The DAO itself is always paused
+        /// entirely.
+        operations: Option<Vec<String>>,
+    },
+}
+
+/// The message `PauseAll` sends to each pausable contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PausableExecuteMsg {
+    Pause {
+        duration: Duration,
+        reason: Option<String>,
+        operations: Option<Vec<String>>,
+    },
 }
 
 #[voting_query]
@@ -93,12 +174,36 @@ pub enum QueryMsg {
     /// Gets the contract's config. Returns Config.
     #[returns(Config)]
     Config {},
+    /// Gets the current admin of the DAO. Returns Addr.
+    #[returns(Addr)]
+    Admin {},
+    /// Gets the pending admin nomination, if any.
+    #[returns(crate::query::AdminNominationResponse)]
+    AdminNomination {},
     /// Dumps all of the core contract's state in a single
     /// query. Useful for frontends as performance for queries is more
     /// limited by network times than compute times. Returns
     /// `DumpStateResponse`.
     #[returns(crate::query::DumpStateResponse)]
     DumpState {},
+    /// Dumps the voting module and the proposal module counts of the
+    /// core contract as they were at the beginning of the block at
+    /// `height`. History is only recorded from the version introducing
+    /// this query onwards, earlier heights are rejected. Returns
+    /// `DumpStateAtHeightResponse`.
+    #[returns(crate::query::DumpStateAtHeightResponse)]
+    DumpStateAtHeight { height: u64 },
+    /// Gets the proposal modules associated with the contract at the
+    /// beginning of the block at `height`, both enabled and disabled.
+    /// start_after is bound exclusive and asks for a string address,
+    /// at most 30 modules are returned if no limit is given. Returns
+    /// Vec<ProposalModule>.
+    #[returns(Vec<crate::state::ProposalModule>)]
+    ProposalModulesAtHeight {
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Gets the address associated with an item key.
     #[returns(crate::query::GetItemResponse)]
     GetItem { key: String },
@@ -138,14 +243,72 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Returns the SubDAOs with their charters at the beginning of the
+    /// block at `height`. start_after is bound exclusive and asks for a
+    /// string address, at most 30 SubDAOs are returned if no limit is
+    /// given.
+    #[returns(Vec<SubDao>)]
+    ListSubDaosAtHeight {
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns the SubDAO for a specific address if it in the list
     #[returns(SubDao)]
     GetSubDao { address: String },
     /// Implements the DAO Star standard: https://daostar.one/EIP
     #[returns(Option<String>)]
     DaoURI {},
+    /// Lists the addresses of the cw20 tokens registered with the
+    /// treasury.
+    #[returns(Vec<Addr>)]
+    Cw20TokenList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the treasury's balances of the registered cw20 tokens.
+    /// Tokens whose balance can't be queried are left out.
+    #[returns(Vec<crate::query::Cw20BalanceResponse>)]
+    Cw20Balances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Gets the treasury's balances of native tokens.
+    #[returns(Vec<Coin>)]
+    NativeBalances {},
+    /// Lists the proposal modules allowed to execute messages while
+    /// the DAO is paused.
+    #[returns(Vec<Addr>)]
+    EmergencyProposalModules {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the contracts paused along with the DAO by `PauseAll`.
+    #[returns(Vec<Addr>)]
+    PausableContracts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the addresses allowed to call `PauseAll`.
+    #[returns(Vec<Addr>)]
+    Pausers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub enum MigrateMsg {
+    /// Migrates a DAO DAO v1 core contract.
+    FromV1 {
+        /// Implements the DAO Star standard: https://daostar.one/EIP
+        dao_uri: Option<String>,
+        /// SubDAOs to register with the DAO, as v1 keeps no list of
+        /// SubDAOs.
+        sub_daos: Vec<SubDao>,
+    },
+    /// Migrates from an older version of this contract. Downgrades
+    /// are refused.
+    FromCompatible {},
+}
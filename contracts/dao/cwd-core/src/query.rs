@@ -12,6 +12,8 @@ use crate::state::{Config, ProposalModule};
 pub struct DumpStateResponse {
     /// The governance contract's config.
     pub config: Config,
+    /// The admin of the governance contract.
+    pub admin: Addr,
     // True if the contract is currently paused.
     pub pause_info: PauseInfoResponse,
     /// The governance contract's version.
@@ -27,6 +29,21 @@ pub struct DumpStateResponse {
     pub total_proposal_module_count: u32,
 }
 
+/// Relevant state for the governance module at a past height. Returned
+/// by the `DumpStateAtHeight` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DumpStateAtHeightResponse {
+    /// The height the state was dumped at.
+    pub height: u64,
+    /// The voting module associated with the governance contract, if
+    /// one was set at the height.
+    pub voting_module: Option<Addr>,
+    /// The number of active proposal modules.
+    pub active_proposal_module_count: u32,
+    /// The total number of proposal modules.
+    pub total_proposal_module_count: u32,
+}
+
 /// Information about if the contract is currently paused.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum PauseInfoResponse {
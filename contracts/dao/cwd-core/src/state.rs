@@ -1,6 +1,6 @@
 use crate::ContractError;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Deque, Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -61,25 +61,94 @@ pub const CONFIG: Item<Config> = Item::new("config_v2");
 /// the DAO has never been paused.
 pub const PAUSED: Item<Expiration> = Item::new("paused");
 
+/// Proposal modules which are allowed to execute a restricted set of
+/// messages while the DAO is paused.
+pub const EMERGENCY_PROPOSAL_MODULES: Map<Addr, Empty> = Map::new("emergency_proposal_modules");
+
+/// Contracts paused along with the DAO by `PauseAll`.
+pub const PAUSABLE_CONTRACTS: Map<Addr, Empty> = Map::new("pausable_contracts");
+
+/// Addresses other than the DAO itself allowed to call `PauseAll`.
+pub const PAUSERS: Map<Addr, Empty> = Map::new("pausers");
+
+/// Contracts `PauseAll` has sent `Pause` to and which are yet to
+/// reply, in the order the messages were sent.
+pub const PENDING_PAUSES: Deque<Addr> = Deque::new("pending_pauses");
+
 /// The voting module associated with this contract.
-pub const VOTING_REGISTRY_MODULE: Item<Addr> = Item::new("voting_module");
+pub const VOTING_REGISTRY_MODULE: SnapshotItem<Addr> = SnapshotItem::new(
+    "voting_module",
+    "voting_module__checkpoints",
+    "voting_module__changelog",
+    Strategy::EveryBlock,
+);
 
 /// The proposal modules associated with this contract.
 /// When we change the data format of this map, we update the key (previously "proposal_modules")
 /// to create a new namespace for the changed state.
-pub const PROPOSAL_MODULES: Map<Addr, ProposalModule> = Map::new("proposal_modules_v2");
+pub const PROPOSAL_MODULES: SnapshotMap<Addr, ProposalModule> = SnapshotMap::new(
+    "proposal_modules_v2",
+    "proposal_modules_v2__checkpoints",
+    "proposal_modules_v2__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The modules instantiated so far by a `ReconfigureModules` call.
+/// The modules replace the current ones once all of them are
+/// instantiated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PendingReconfiguration {
+    /// The number of modules which are yet to be instantiated.
+    pub remaining: u32,
+    /// The new voting registry module, once instantiated.
+    pub voting_registry_module: Option<Addr>,
+    /// The new proposal modules instantiated so far, in order.
+    pub proposal_modules: Vec<Addr>,
+}
+
+/// The `ReconfigureModules` call in progress, if any.
+pub const PENDING_RECONFIGURATION: Item<PendingReconfiguration> =
+    Item::new("pending_reconfiguration");
 
 /// The count of active proposal modules associated with this contract.
-pub const ACTIVE_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("active_proposal_module_count");
+pub const ACTIVE_PROPOSAL_MODULE_COUNT: SnapshotItem<u32> = SnapshotItem::new(
+    "active_proposal_module_count",
+    "active_proposal_module_count__checkpoints",
+    "active_proposal_module_count__changelog",
+    Strategy::EveryBlock,
+);
 
 /// The count of total proposal modules associated with this contract.
-pub const TOTAL_PROPOSAL_MODULE_COUNT: Item<u32> = Item::new("total_proposal_module_count");
+pub const TOTAL_PROPOSAL_MODULE_COUNT: SnapshotItem<u32> = SnapshotItem::new(
+    "total_proposal_module_count",
+    "total_proposal_module_count__checkpoints",
+    "total_proposal_module_count__changelog",
+    Strategy::EveryBlock,
+);
 
 // General purpose KV store for DAO associated state.
 pub const ITEMS: Map<String, String> = Map::new("items");
 
+/// Set of cw20 tokens that have been registered with this contract's
+/// treasury.
+pub const CW20_LIST: Map<Addr, Empty> = Map::new("cw20s");
+
 /// List of SubDAOs associated to this DAO. Each SubDAO has an optional charter.
-pub const SUBDAO_LIST: Map<&Addr, Option<String>> = Map::new("sub_daos");
+pub const SUBDAO_LIST: SnapshotMap<&Addr, Option<String>> = SnapshotMap::new(
+    "sub_daos",
+    "sub_daos__checkpoints",
+    "sub_daos__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The first height the history of the voting module, the proposal
+/// modules and the SubDAOs is recorded from. Set at instantiation and
+/// by the migration introducing the history.
+pub const HISTORY_START_HEIGHT: Item<u64> = Item::new("history_start_height");
+
+/// The default number of entries returned by the paginated queries
+/// of past state.
+pub const DEFAULT_LIMIT: u32 = 30;
 
 #[cfg(test)]
 mod tests {
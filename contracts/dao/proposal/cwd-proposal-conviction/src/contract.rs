@@ -0,0 +1,752 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cwd_hooks::Hooks;
+use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use cwd_voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use cwd_voting::reply::{mask_proposal_execution_proposal_id, TaggedReplyId};
+use cwd_voting::status::Status;
+use cwd_voting::voting::{get_total_power, get_voting_power};
+use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::msg::MigrateMsg;
+use crate::proposal::ConvictionProposal;
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    proposal::advance_proposal_id,
+    query::{ProposalListResponse, ProposalResponse, StakeInfo, StakeListResponse, StakeResponse},
+    state::{
+        Config, CONFIG, MAX_DECAY, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, STAKED_POWER, STAKES,
+    },
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-proposal-conviction";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    validate_conviction_params(msg.decay, msg.max_ratio, msg.weight)?;
+
+    let dao = info.sender;
+
+    let config = Config {
+        dao: dao.clone(),
+        denom: msg.denom,
+        decay: msg.decay,
+        max_ratio: msg.max_ratio,
+        weight: msg.weight,
+    };
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", dao))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            beneficiary,
+            requested_amount,
+        } => execute_propose(
+            deps,
+            env,
+            info,
+            title,
+            description,
+            beneficiary,
+            requested_amount,
+        ),
+        ExecuteMsg::Stake {
+            proposal_id,
+            amount,
+        } => execute_stake(deps, env, info, proposal_id, amount),
+        ExecuteMsg::Unstake {
+            proposal_id,
+            amount,
+        } => execute_unstake(deps, env, info, proposal_id, amount),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            denom,
+            decay,
+            max_ratio,
+            weight,
+        } => execute_update_config(deps, info, denom, decay, max_ratio, weight),
+        ExecuteMsg::AddProposalHook { address } => {
+            execute_add_proposal_hook(deps, env, info, address)
+        }
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_proposal_hook(deps, env, info, address)
+        }
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    beneficiary: String,
+    requested_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if requested_amount.is_zero() {
+        return Err(ContractError::ZeroRequestedAmount {});
+    }
+
+    // Only members may create proposals.
+    let power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        config.dao,
+        Some(env.block.height),
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    let proposal = ConvictionProposal {
+        title,
+        description,
+        proposer: info.sender.clone(),
+        beneficiary: deps.api.addr_validate(&beneficiary)?,
+        requested_amount,
+        denom: config.denom,
+        start_height: env.block.height,
+        status: Status::Open,
+        staked: Uint128::zero(),
+        conviction: Uint128::zero(),
+        last_update_height: env.block.height,
+    };
+    let id = advance_proposal_id(deps.storage)?;
+
+    // Limit the size of proposals so that they may always be queried,
+    // see `execute_propose` of `cwd-proposal-single`.
+    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, info.sender.as_str())?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("requested_amount", requested_amount))
+}
+
+pub fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroStake {});
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    // Members may spread their voting power across proposals, but may
+    // not stake more than they have. The power is read at the start
+    // of the proposal, so that moving tokens to another address does
+    // not let them be staked on it again.
+    let power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        config.dao,
+        Some(prop.start_height),
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+    let staked_power = STAKED_POWER
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    let available = power.saturating_sub(staked_power);
+    if amount > available {
+        return Err(ContractError::InsufficientVotingPower {
+            stake: amount.u128(),
+            available: available.u128(),
+        });
+    }
+
+    // Conviction accrued so far is counted with the previous stake.
+    sync_conviction(deps.as_ref(), &env, &config, proposal_id, &mut prop)?;
+
+    STAKES.update(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        |stake| -> StdResult<_> { Ok(stake.unwrap_or_default().checked_add(amount)?) },
+    )?;
+    STAKED_POWER.save(
+        deps.storage,
+        info.sender.clone(),
+        &staked_power.checked_add(amount)?,
+    )?;
+
+    prop.staked = effective_staked(deps.as_ref(), &config, proposal_id, env.block.height)?;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "stake")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("conviction", prop.conviction))
+}
+
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroStake {});
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let stake = STAKES
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .unwrap_or_default();
+    if amount > stake {
+        return Err(ContractError::InsufficientStake {
+            amount: amount.u128(),
+            staked: stake.u128(),
+        });
+    }
+
+    // Stakes may be withdrawn from proposals that are no longer open,
+    // in which case their conviction is left as is.
+    sync_conviction(deps.as_ref(), &env, &config, proposal_id, &mut prop)?;
+
+    let stake = stake - amount;
+    if stake.is_zero() {
+        STAKES.remove(deps.storage, (proposal_id, info.sender.clone()));
+    } else {
+        STAKES.save(deps.storage, (proposal_id, info.sender.clone()), &stake)?;
+    }
+    STAKED_POWER.update(
+        deps.storage,
+        info.sender.clone(),
+        |staked_power| -> StdResult<_> {
+            Ok(staked_power.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+
+    prop.staked = effective_staked(deps.as_ref(), &config, proposal_id, env.block.height)?;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "unstake")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("conviction", prop.conviction))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    sync_conviction(deps.as_ref(), &env, &config, proposal_id, &mut prop)?;
+    let threshold = query_threshold(deps.as_ref(), &env, &config, &prop)?;
+    if !prop.is_passed(threshold) {
+        return Err(ContractError::NotPassed { id: proposal_id });
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Executed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let funding: CosmosMsg<NeutronMsg> = BankMsg::Send {
+        to_address: prop.beneficiary.to_string(),
+        amount: coins(prop.requested_amount.u128(), prop.denom),
+    }
+    .into();
+    let execute_message = WasmMsg::Execute {
+        contract_addr: config.dao.to_string(),
+        msg: to_binary(&cwd_core::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![funding],
+        })?,
+        funds: vec![],
+    };
+    let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("conviction", prop.conviction)
+        .add_attribute("dao", config.dao))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer && info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    sync_conviction(deps.as_ref(), &env, &config, proposal_id, &mut prop)?;
+    let old_status = prop.status;
+    prop.status = Status::Closed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_conviction_params(decay, max_ratio, weight)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            dao: config.dao,
+            denom,
+            decay,
+            max_ratio,
+            weight,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+/// Checks that the conviction parameters of the config describe
+/// reachable thresholds.
+pub fn validate_conviction_params(
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Result<(), ContractError> {
+    if decay > MAX_DECAY {
+        return Err(ContractError::InvalidDecay {});
+    }
+    if max_ratio.is_zero() || max_ratio > Decimal::one() {
+        return Err(ContractError::InvalidMaxRatio {});
+    }
+    if weight.is_zero() {
+        return Err(ContractError::InvalidWeight {});
+    }
+    Ok(())
+}
+
+/// Sums the stakes on proposal `proposal_id`, counting each staker only
+/// up to their voting power at `height`. A staker whose voting power
+/// dropped below the power they staked across all proposals has each of
+/// their stakes scaled down by the same ratio, so that tokens moved to
+/// another address are not counted for both.
+fn effective_staked(
+    deps: Deps,
+    config: &Config,
+    proposal_id: u64,
+    height: u64,
+) -> StdResult<Uint128> {
+    STAKES
+        .prefix(proposal_id)
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .try_fold(Uint128::zero(), |total, item| {
+            let (staker, stake) = item?;
+            let staked_power = STAKED_POWER.load(deps.storage, staker.clone())?;
+            let power = get_voting_power(deps, staker, config.dao.clone(), Some(height))?;
+            let stake = if power < staked_power {
+                // Can not overflow, as the result is less than `stake`.
+                stake.multiply_ratio(power, staked_power)
+            } else {
+                stake
+            };
+            Ok(total.checked_add(stake)?)
+        })
+}
+
+/// Counts the stakes on `proposal` as they are backed by voting power
+/// at the current block, and brings its conviction up to date with
+/// them.
+fn sync_conviction(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    proposal_id: u64,
+    proposal: &mut ConvictionProposal,
+) -> StdResult<()> {
+    proposal.staked = effective_staked(deps, config, proposal_id, env.block.height)?;
+    proposal.update_conviction(env.block.height, config.decay)
+}
+
+/// Returns the threshold of `proposal` given the current balance of
+/// the DAO and total voting power.
+fn query_threshold(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    proposal: &ConvictionProposal,
+) -> StdResult<Option<Uint128>> {
+    let treasury = deps
+        .querier
+        .query_balance(&config.dao, &proposal.denom)?
+        .amount;
+    let total_power = get_total_power(deps, config.dao.clone(), Some(env.block.height))?;
+    Ok(config.threshold(proposal.requested_amount, treasury, total_power))
+}
+
+pub fn add_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validated_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .add_hook(storage, validated_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn remove_hook(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    validate_address: Addr,
+) -> Result<(), ContractError> {
+    hooks
+        .remove_hook(storage, validate_address)
+        .map_err(ContractError::HookError)?;
+    Ok(())
+}
+
+pub fn execute_add_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "add_proposal_hook")
+        .add_attribute("address", address))
+}
+
+pub fn execute_remove_proposal_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let validated_address = deps.api.addr_validate(&address)?;
+
+    remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "remove_proposal_hook")
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ProposalCount {} => query_proposal_count(deps),
+        QueryMsg::GetStake {
+            proposal_id,
+            staker,
+        } => query_stake(deps, proposal_id, staker),
+        QueryMsg::ListStakes {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_stakes(deps, proposal_id, start_after, limit),
+        QueryMsg::StakedPower { address } => query_staked_power(deps, address),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&config)
+}
+
+pub fn query_dao(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&config.dao)
+}
+
+/// Brings the conviction of `proposal` up to date with the current
+/// block and pairs it with its current threshold.
+fn proposal_response(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    id: u64,
+    mut proposal: ConvictionProposal,
+) -> StdResult<ProposalResponse> {
+    sync_conviction(deps, env, config, id, &mut proposal)?;
+    let threshold = query_threshold(deps, env, config, &proposal)?;
+    Ok(ProposalResponse {
+        id,
+        proposal,
+        threshold,
+    })
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal_response(deps, &env, &config, id, proposal)?)
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let proposals = PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            proposal_response(deps, &env, &config, id, proposal)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+pub fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let max = start_before.map(Bound::exclusive);
+    let proposals = PROPOSALS
+        .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
+        .take(limit as usize)
+        .map(|item| {
+            let (id, proposal) = item?;
+            proposal_response(deps, &env, &config, id, proposal)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
+    let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
+    to_binary(&proposal_count)
+}
+
+pub fn query_stake(deps: Deps, proposal_id: u64, staker: String) -> StdResult<Binary> {
+    let staker = deps.api.addr_validate(&staker)?;
+    let stake = STAKES
+        .may_load(deps.storage, (proposal_id, staker))?
+        .unwrap_or_default();
+    to_binary(&StakeResponse { stake })
+}
+
+pub fn query_list_stakes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let stakes = STAKES
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (staker, amount) = item?;
+            Ok(StakeInfo { staker, amount })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&StakeListResponse { stakes })
+}
+
+pub fn query_staked_power(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let staked_power = STAKED_POWER
+        .may_load(deps.storage, address)?
+        .unwrap_or_default();
+    to_binary(&staked_power)
+}
+
+pub fn query_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&cwd_interface::voting::InfoResponse { info })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+                Some(mut prop) => {
+                    prop.status = Status::ExecutionFailed;
+
+                    Ok(prop)
+                }
+                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
+            })?;
+
+            Ok(Response::new().add_attribute("proposal_execution_failed", proposal_id.to_string()))
+        }
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+        }
+        // This module neither sends vote hooks nor has a pre-propose
+        // module.
+        _ => Err(ContractError::InvalidReplyID { id: msg.id }),
+    }
+}
@@ -0,0 +1,63 @@
+use std::u64;
+
+use cosmwasm_std::{OverflowError, StdError};
+use cwd_hooks::HookError;
+use cwd_voting::reply::error::TagError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("proposal is not open ({id})")]
+    NotOpen { id: u64 },
+
+    #[error("not registered to vote (no voting power)")]
+    NotRegistered {},
+
+    #[error("proposal has not reached its conviction threshold ({id})")]
+    NotPassed { id: u64 },
+
+    #[error("requested amount must be greater than zero")]
+    ZeroRequestedAmount {},
+
+    #[error("stake amount must be greater than zero")]
+    ZeroStake {},
+
+    #[error("staking ({stake}) would exceed available voting power ({available})")]
+    InsufficientVotingPower { stake: u128, available: u128 },
+
+    #[error("unstaking ({amount}) exceeds the stake on the proposal ({staked})")]
+    InsufficientStake { amount: u128, staked: u128 },
+
+    #[error("decay must be at most 0.9999")]
+    InvalidDecay {},
+
+    #[error("max ratio must be greater than zero and at most one")]
+    InvalidMaxRatio {},
+
+    #[error("weight must be greater than zero")]
+    InvalidWeight {},
+
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+}
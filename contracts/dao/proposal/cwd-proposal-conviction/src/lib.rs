@@ -0,0 +1,37 @@
+//! # cwd-proposal-conviction
+//!
+//! A proposal module for a DAO DAO DAO which funds proposals from the
+//! DAO's treasury using conviction voting. Rather than voting within a
+//! voting period, members stake their voting power on the proposals
+//! they support for as long as they like. The conviction of a proposal
+//! accrues over blocks from the voting power staked on it, and the
+//! proposal may be executed once its conviction reaches a threshold
+//! derived from the share of the treasury it requests.
+//!
+//! ## Hooks
+//!
+//! This module supports hooks for proposal status changes. One may
+//! register a contract to receive these hooks with the
+//! `AddProposalHook` method. Upon registration the contract will
+//! receive messages whenever a proposal is created or its status
+//! changes (for example, when the proposal is executed).
+//!
+//! The format for these hook messages can be located in the
+//! `proposal-hooks` package located in `packages/proposal-hooks`.
+//!
+//! To stop an invalid hook receiver from locking the proposal module
+//! receivers will be removed from the hook list if they error when
+//! handling a hook.
+
+pub mod contract;
+mod error;
+pub mod msg;
+pub mod proposal;
+pub mod query;
+
+pub mod state;
+
+pub use crate::error::ContractError;
+
+#[cfg(test)]
+pub mod testing;
@@ -0,0 +1,165 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cwd_interface::voting::InfoResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cwd_macros::{info_query, proposal_module_query};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The denom proposals request funding in.
+    pub denom: String,
+    /// The share of its conviction a proposal retains from one block
+    /// to the next. Must be less than one.
+    pub decay: Decimal,
+    /// The share of the DAO's balance a single proposal may request.
+    /// Must be greater than zero and at most one.
+    pub max_ratio: Decimal,
+    /// Scales the conviction threshold of all proposals. Must be
+    /// greater than zero.
+    pub weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a proposal requesting funds from the DAO. Only members
+    /// with voting power may create proposals.
+    Propose {
+        /// The title of the proposal.
+        title: String,
+        /// A description of the proposal.
+        description: String,
+        /// The address the requested funds are sent to once the
+        /// proposal passes.
+        beneficiary: String,
+        /// The amount of the configured denom requested from the DAO.
+        requested_amount: Uint128,
+    },
+    /// Stakes voting power of the sender on an open proposal. The
+    /// voting power a member stakes across all proposals may not
+    /// exceed their current voting power.
+    Stake {
+        /// The ID of the proposal to stake on.
+        proposal_id: u64,
+        /// The amount of voting power to stake.
+        amount: Uint128,
+    },
+    /// Withdraws voting power the sender staked on a proposal, making
+    /// it available to stake on other proposals.
+    Unstake {
+        /// The ID of the proposal to unstake from.
+        proposal_id: u64,
+        /// The amount of voting power to unstake.
+        amount: Uint128,
+    },
+    /// Sends the requested funds of a proposal whose conviction has
+    /// reached its threshold to its beneficiary.
+    Execute {
+        /// The ID of the proposal to execute.
+        proposal_id: u64,
+    },
+    /// Closes an open proposal. Only the proposer or the DAO may call
+    /// this method.
+    Close {
+        /// The ID of the proposal to close.
+        proposal_id: u64,
+    },
+    /// Updates the governance module's config. Only the DAO may call
+    /// this method.
+    UpdateConfig {
+        /// The denom new proposals request funding in.
+        denom: String,
+        /// The share of its conviction a proposal retains from one
+        /// block to the next. Must be less than one.
+        decay: Decimal,
+        /// The share of the DAO's balance a single proposal may
+        /// request. Must be greater than zero and at most one.
+        max_ratio: Decimal,
+        /// Scales the conviction threshold of all proposals. Must be
+        /// greater than zero.
+        weight: Decimal,
+    },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String },
+}
+
+#[proposal_module_query]
+#[info_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the governance module's config. Returns `state::Config`.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal, including its current
+    /// conviction and threshold. Returns `query::ProposalResponse`.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been created in this
+    /// module. Returns `query::ProposalListResponse`.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists all of the proposals that have been created in this
+    /// module in descending order of proposal ID. Returns
+    /// `query::ProposalListResponse`.
+    #[returns(crate::query::ProposalListResponse)]
+    ReverseProposals {
+        /// The proposal ID to start listing proposals before. For
+        /// example, if this is set to 6 proposals with IDs 5 and
+        /// lower will be returned.
+        start_before: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in this
+    /// module.
+    #[returns(u64)]
+    ProposalCount {},
+    /// Returns the voting power a member staked on a proposal.
+    /// Returns `query::StakeResponse`.
+    #[returns(crate::query::StakeResponse)]
+    GetStake { proposal_id: u64, staker: String },
+    /// Lists the stakes on a proposal. Returns
+    /// `query::StakeListResponse`.
+    #[returns(crate::query::StakeListResponse)]
+    ListStakes {
+        /// The proposal to list the stakes of.
+        proposal_id: u64,
+        /// The staker to start listing stakes after. Ordering is done
+        /// alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of stakes to return in response to this
+        /// query. If no limit is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Returns the voting power a member has staked across all
+    /// proposals.
+    #[returns(Uint128)]
+    StakedPower { address: String },
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(cwd_hooks::HooksResponse)]
+    ProposalHooks {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {}
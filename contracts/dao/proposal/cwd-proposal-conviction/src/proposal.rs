@@ -0,0 +1,135 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Decimal, OverflowError, OverflowOperation, StdError, StdResult, Storage, Uint128,
+};
+use cwd_voting::status::Status;
+
+use crate::state::PROPOSAL_COUNT;
+
+#[cw_serde]
+pub struct ConvictionProposal {
+    pub title: String,
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The address the requested funds are sent to once the proposal
+    /// passes.
+    pub beneficiary: Addr,
+    /// The amount of `denom` requested from the DAO.
+    pub requested_amount: Uint128,
+    /// The denom the funds are requested in.
+    pub denom: String,
+    /// The block height at which this proposal was created.
+    pub start_height: u64,
+    pub status: Status,
+    /// The voting power staked on this proposal as of
+    /// `last_update_height`, counting each staker only up to their
+    /// voting power at that height.
+    pub staked: Uint128,
+    /// The conviction of this proposal as of `last_update_height`.
+    pub conviction: Uint128,
+    /// The block height at which the conviction of this proposal was
+    /// last updated.
+    pub last_update_height: u64,
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+/// Returns the conviction of a proposal with `staked` voting power
+/// behind it after `blocks` blocks. Each block the conviction decays
+/// by `decay` and the staked power is added to it, so it approaches
+/// `staked / (1 - decay)` over time. Errors instead of overflowing.
+pub fn conviction_after(
+    conviction: Uint128,
+    staked: Uint128,
+    decay: Decimal,
+    blocks: u64,
+) -> StdResult<Uint128> {
+    // `decay` is less than one, so raising it to a large power
+    // saturates at zero rather than overflowing.
+    let retained = decay
+        .checked_pow(u32::try_from(blocks).unwrap_or(u32::MAX))
+        .unwrap_or_default();
+    let accrued = (Decimal::one() - retained)
+        .checked_div(Decimal::one() - decay)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    checked_mul_decimal(conviction, retained)?
+        .checked_add(checked_mul_decimal(staked, accrued)?)
+        .map_err(StdError::overflow)
+}
+
+/// Multiplies `amount` by `factor`, rounding down.
+fn checked_mul_decimal(amount: Uint128, factor: Decimal) -> StdResult<Uint128> {
+    amount
+        .checked_multiply_ratio(factor.atomics(), Decimal::one().atomics())
+        .map_err(|_| StdError::overflow(OverflowError::new(OverflowOperation::Mul, amount, factor)))
+}
+
+impl ConvictionProposal {
+    /// Brings the conviction of the proposal up to date with the
+    /// block at `height`. Conviction only accrues while the proposal
+    /// is open.
+    pub fn update_conviction(&mut self, height: u64, decay: Decimal) -> StdResult<()> {
+        if self.status != Status::Open || height <= self.last_update_height {
+            return Ok(());
+        }
+        self.conviction = conviction_after(
+            self.conviction,
+            self.staked,
+            decay,
+            height - self.last_update_height,
+        )?;
+        self.last_update_height = height;
+        Ok(())
+    }
+
+    /// Whether the conviction of the proposal has reached
+    /// `threshold`, as returned by `Config::threshold`.
+    pub fn is_passed(&self, threshold: Option<Uint128>) -> bool {
+        threshold.map_or(false, |threshold| {
+            !self.conviction.is_zero() && self.conviction >= threshold
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conviction_after() {
+        let decay = Decimal::percent(90);
+        let staked = Uint128::new(1_000);
+
+        // One block adds the staked power.
+        assert_eq!(
+            conviction_after(Uint128::zero(), staked, decay, 1).unwrap(),
+            Uint128::new(1_000)
+        );
+        // Stepping block by block matches stepping all at once.
+        let mut stepped = Uint128::zero();
+        for _ in 0..5 {
+            stepped = conviction_after(stepped, staked, decay, 1).unwrap();
+        }
+        assert_eq!(
+            stepped,
+            conviction_after(Uint128::zero(), staked, decay, 5).unwrap()
+        );
+        // Conviction approaches `staked / (1 - decay)`.
+        assert_eq!(
+            conviction_after(Uint128::zero(), staked, decay, u64::MAX).unwrap(),
+            Uint128::new(10_000)
+        );
+        // Without stake conviction decays.
+        assert_eq!(
+            conviction_after(Uint128::new(10_000), Uint128::zero(), decay, 2).unwrap(),
+            Uint128::new(8_100)
+        );
+        // Conviction that does not fit errors rather than panicking.
+        conviction_after(Uint128::MAX, Uint128::MAX, decay, 1).unwrap_err();
+    }
+}
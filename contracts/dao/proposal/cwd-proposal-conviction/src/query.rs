@@ -0,0 +1,48 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::ConvictionProposal;
+
+/// Information about a proposal returned by proposal queries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    /// The proposal, with its conviction brought up to date with the
+    /// current block.
+    pub proposal: ConvictionProposal,
+    /// The conviction the proposal must reach to pass given the
+    /// current balance of the DAO and total voting power. None if the
+    /// proposal requests too large a share of the DAO's balance to
+    /// pass.
+    pub threshold: Option<Uint128>,
+}
+
+/// A list of proposals returned by `ListProposals` and
+/// `ReverseProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+/// Information about voting power staked on a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct StakeInfo {
+    /// The address that staked.
+    pub staker: Addr,
+    /// The voting power staked.
+    pub amount: Uint128,
+}
+
+/// The voting power a member staked on a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct StakeResponse {
+    pub stake: Uint128,
+}
+
+/// A list of stakes returned by `ListStakes`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct StakeListResponse {
+    pub stakes: Vec<StakeInfo>,
+}
@@ -0,0 +1,73 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use cwd_hooks::Hooks;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::ConvictionProposal;
+
+/// The highest `decay` a config may set, 0.9999. Bounds the conviction
+/// a proposal may accrue to `10_000` times the voting power staked on
+/// it.
+pub const MAX_DECAY: Decimal = Decimal::raw(999_900_000_000_000_000);
+
+/// The governance module's configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The address of the DAO that this governance module is
+    /// associated with. Proposals are funded from its balance.
+    pub dao: Addr,
+    /// The denom proposals request funding in.
+    pub denom: String,
+    /// The share of its conviction a proposal retains from one block
+    /// to the next. Must be at most `MAX_DECAY`.
+    pub decay: Decimal,
+    /// The share of the DAO's balance a single proposal may request.
+    /// Proposals requesting this share or more can not pass.
+    pub max_ratio: Decimal,
+    /// Scales the conviction threshold of all proposals. Higher values
+    /// require more voting power to be staked for longer.
+    pub weight: Decimal,
+}
+
+impl Config {
+    /// Returns the conviction a proposal requesting `requested` out of
+    /// a treasury of `treasury` must reach to pass, or `None` if the
+    /// proposal may not pass because it requests `max_ratio` of the
+    /// treasury or more.
+    ///
+    /// The threshold is `weight * total_power / ((1 - decay) *
+    /// (max_ratio - requested / treasury)^2)`, so it grows quickly as
+    /// the requested share of the treasury approaches `max_ratio`.
+    pub fn threshold(
+        &self,
+        requested: Uint128,
+        treasury: Uint128,
+        total_power: Uint128,
+    ) -> Option<Uint128> {
+        let share = Decimal::checked_from_ratio(requested, treasury).ok()?;
+        if share >= self.max_ratio {
+            return None;
+        }
+        let margin = self.max_ratio - share;
+        let divisor = (Decimal::one() - self.decay)
+            .checked_mul(margin.checked_mul(margin).ok()?)
+            .ok()?;
+        let factor = self.weight.checked_div(divisor).ok()?;
+        total_power
+            .checked_multiply_ratio(factor.atomics(), Decimal::one().atomics())
+            .ok()
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, ConvictionProposal> = Map::new("proposals");
+/// The voting power staked on each proposal, keyed by proposal ID and
+/// staker.
+pub const STAKES: Map<(u64, Addr), Uint128> = Map::new("stakes");
+/// The voting power each member has staked across all proposals.
+pub const STAKED_POWER: Map<Addr, Uint128> = Map::new("staked_power");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
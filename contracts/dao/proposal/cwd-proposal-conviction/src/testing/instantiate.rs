@@ -0,0 +1,106 @@
+use cosmwasm_std::{to_binary, Addr, Coin, Uint128};
+use cw_multi_test::{next_block, BankSudo, BasicApp, Executor, SudoMsg};
+use cwd_interface::{Admin, ModuleInstantiateInfo};
+use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::{
+    msg::InstantiateMsg,
+    testing::contracts::{
+        cw_core_contract, neutron_vault_contract, proposal_conviction_contract,
+        voting_registry_contract,
+    },
+    testing::tests::CREATOR_ADDR,
+};
+
+const DAO_ADDR: &str = "dao";
+
+/// Instantiates a DAO with the conviction proposal module, whose
+/// members bond `initial_balances` of `ujuno` in a `neutron-vault`.
+pub(crate) fn instantiate_with_native_bonded_balances_governance(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_module_instantiate: InstantiateMsg,
+    initial_balances: Vec<(&str, u128)>,
+) -> Addr {
+    let voting_vault_code_id = app.store_code(neutron_vault_contract());
+
+    let vault_intantiate = neutron_vault::msg::InstantiateMsg {
+        name: "Test vault".to_string(),
+        description: "based neutron vault".to_string(),
+        owner: DAO_ADDR.to_string(),
+        denom: "ujuno".to_string(),
+    };
+
+    let vault_addr = app
+        .instantiate_contract(
+            voting_vault_code_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &vault_intantiate,
+            &[],
+            "neutron vault",
+            None,
+        )
+        .unwrap();
+
+    let proposal_module_code_id = app.store_code(proposal_conviction_contract());
+    let voting_registry_id = app.store_code(voting_registry_contract());
+    let core_contract_id = app.store_code(cw_core_contract());
+
+    let instantiate_core = cwd_core::msg::InstantiateMsg {
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs".to_string(),
+        dao_uri: None,
+        voting_registry_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: voting_registry_id,
+            msg: to_binary(&neutron_voting_registry::msg::InstantiateMsg {
+                owner: DAO_ADDR.to_string(),
+                voting_vaults: vec![vault_addr.to_string()],
+            })
+            .unwrap(),
+            admin: None,
+            label: "DAO DAO voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: proposal_module_code_id,
+            label: "DAO DAO governance module.".to_string(),
+            admin: Some(Admin::CoreModule {}),
+            msg: to_binary(&proposal_module_instantiate).unwrap(),
+        }],
+        initial_items: None,
+    };
+
+    let core_addr = app
+        .instantiate_contract(
+            core_contract_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &instantiate_core,
+            &[],
+            "DAO DAO",
+            None,
+        )
+        .unwrap();
+
+    for (address, amount) in initial_balances {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: address.to_string(),
+            amount: vec![Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(amount),
+            }],
+        }))
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(address),
+            vault_addr.clone(),
+            &neutron_vault::msg::ExecuteMsg::Bond {},
+            &[Coin {
+                amount: Uint128::new(amount),
+                denom: "ujuno".to_string(),
+            }],
+        )
+        .unwrap();
+    }
+
+    app.update_block(next_block);
+
+    core_addr
+}
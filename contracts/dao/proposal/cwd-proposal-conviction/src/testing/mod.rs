@@ -0,0 +1,4 @@
+pub mod contracts;
+pub mod instantiate;
+pub mod queries;
+pub mod tests;
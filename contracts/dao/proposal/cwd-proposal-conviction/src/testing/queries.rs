@@ -0,0 +1,107 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::BasicApp;
+use cwd_core::state::{ProposalModule, ProposalModuleStatus};
+use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::{
+    msg::QueryMsg,
+    query::{ProposalResponse, StakeListResponse},
+    state::Config,
+};
+
+pub fn query_conviction_proposal_module(app: &BasicApp<NeutronMsg>, core_addr: &Addr) -> Addr {
+    let modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &cwd_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    // Filter out disabled modules.
+    let modules = modules
+        .into_iter()
+        .filter(|module| module.status == ProposalModuleStatus::Enabled)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        modules.len(),
+        1,
+        "wrong proposal module count. expected 1, got {}",
+        modules.len()
+    );
+
+    modules.into_iter().next().unwrap().address
+}
+
+/// Returns the address of the first voting vault of the DAO.
+pub fn query_voting_vault(app: &BasicApp<NeutronMsg>, core_addr: &Addr) -> Addr {
+    let registry: Addr = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cwd_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let vaults: Vec<neutron_voting_registry::msg::VotingVault> = app
+        .wrap()
+        .query_wasm_smart(
+            registry,
+            &neutron_voting_registry::msg::QueryMsg::VotingVaults {},
+        )
+        .unwrap();
+    Addr::unchecked(&vaults[0].address)
+}
+
+pub fn query_proposal_config(app: &BasicApp<NeutronMsg>, proposal_conviction: &Addr) -> Config {
+    app.wrap()
+        .query_wasm_smart(proposal_conviction, &QueryMsg::Config {})
+        .unwrap()
+}
+
+pub fn query_proposal(
+    app: &BasicApp<NeutronMsg>,
+    proposal_conviction: &Addr,
+    id: u64,
+) -> ProposalResponse {
+    app.wrap()
+        .query_wasm_smart(proposal_conviction, &QueryMsg::Proposal { proposal_id: id })
+        .unwrap()
+}
+
+pub fn query_list_stakes(
+    app: &BasicApp<NeutronMsg>,
+    proposal_conviction: &Addr,
+    proposal_id: u64,
+) -> StakeListResponse {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_conviction,
+            &QueryMsg::ListStakes {
+                proposal_id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+}
+
+pub fn query_staked_power(
+    app: &BasicApp<NeutronMsg>,
+    proposal_conviction: &Addr,
+    address: &str,
+) -> Uint128 {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_conviction,
+            &QueryMsg::StakedPower {
+                address: address.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+pub fn query_balance_native(app: &BasicApp<NeutronMsg>, who: &str, denom: &str) -> Uint128 {
+    let res = app.wrap().query_balance(who, denom).unwrap();
+    res.amount
+}
@@ -0,0 +1,606 @@
+use cosmwasm_std::{coins, Addr, Api, Decimal, Empty, Storage, Uint128};
+use cw_multi_test::{custom_app, BankSudo, BasicApp, Executor, Router, SudoMsg};
+use cwd_hooks::{HookError, HooksResponse};
+use cwd_voting::status::Status;
+use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::StakeInfo,
+    state::{Config, MAX_DECAY},
+    testing::{
+        contracts::proposal_conviction_contract,
+        instantiate::instantiate_with_native_bonded_balances_governance,
+        queries::{
+            query_balance_native, query_conviction_proposal_module, query_list_stakes,
+            query_proposal, query_proposal_config, query_staked_power, query_voting_vault,
+        },
+    },
+    ContractError,
+};
+
+pub const CREATOR_ADDR: &str = "creator";
+const MEMBER_ADDR: &str = "member";
+const BENEFICIARY_ADDR: &str = "beneficiary";
+const TREASURY_DENOM: &str = "untrn";
+
+pub(crate) fn no_init<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>(
+    _: &mut Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>,
+    _: &dyn Api,
+    _: &mut dyn Storage,
+) {
+}
+
+fn get_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        denom: TREASURY_DENOM.to_string(),
+        decay: Decimal::percent(90),
+        max_ratio: Decimal::percent(20),
+        weight: Decimal::from_ratio(25u128, 10_000u128),
+    }
+}
+
+/// Sets up a DAO with 100_000_000 voting power, 80% of which belongs
+/// to the creator, and a treasury of 1_000_000 untrn. Returns the app,
+/// the DAO and its conviction proposal module.
+fn setup_test() -> (BasicApp<NeutronMsg>, Addr, Addr) {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        get_instantiate_msg(),
+        vec![(CREATOR_ADDR, 80_000_000), (MEMBER_ADDR, 20_000_000)],
+    );
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: coins(1_000_000, TREASURY_DENOM),
+    }))
+    .unwrap();
+    let proposal_module = query_conviction_proposal_module(&app, &core_addr);
+    (app, core_addr, proposal_module)
+}
+
+fn make_proposal(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_module: &Addr,
+    proposer: &str,
+    requested_amount: u128,
+) -> u64 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            beneficiary: BENEFICIARY_ADDR.to_string(),
+            requested_amount: Uint128::new(requested_amount),
+        },
+        &[],
+    )
+    .unwrap();
+    app.wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::ProposalCount {})
+        .unwrap()
+}
+
+fn stake(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_module: &Addr,
+    sender: &str,
+    proposal_id: u64,
+    amount: u128,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_module.clone(),
+        &ExecuteMsg::Stake {
+            proposal_id,
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn unstake(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_module: &Addr,
+    sender: &str,
+    proposal_id: u64,
+    amount: u128,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_module.clone(),
+        &ExecuteMsg::Unstake {
+            proposal_id,
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn execute_proposal(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_module: &Addr,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(MEMBER_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn advance_blocks(app: &mut BasicApp<NeutronMsg>, blocks: u64) {
+    app.update_block(|block| {
+        block.height += blocks;
+        block.time = block.time.plus_seconds(5 * blocks);
+    });
+}
+
+#[test]
+fn test_propose() {
+    let (mut app, core_addr, proposal_module) = setup_test();
+
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(
+        config,
+        Config {
+            dao: core_addr,
+            denom: TREASURY_DENOM.to_string(),
+            decay: Decimal::percent(90),
+            max_ratio: Decimal::percent(20),
+            weight: Decimal::from_ratio(25u128, 10_000u128),
+        }
+    );
+
+    let id = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.id, 1);
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked(MEMBER_ADDR));
+    assert_eq!(
+        proposal.proposal.beneficiary,
+        Addr::unchecked(BENEFICIARY_ADDR)
+    );
+    assert_eq!(proposal.proposal.requested_amount, Uint128::new(10_000));
+    assert_eq!(proposal.proposal.denom, TREASURY_DENOM);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.conviction, Uint128::zero());
+    // 0.0025 * 100_000_000 / ((1 - 0.9) * (0.2 - 10_000 / 1_000_000)^2)
+    assert_eq!(proposal.threshold, Some(Uint128::new(69_252_077)));
+
+    // Only members may propose.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(BENEFICIARY_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                beneficiary: BENEFICIARY_ADDR.to_string(),
+                requested_amount: Uint128::new(10_000),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(MEMBER_ADDR),
+            proposal_module,
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                beneficiary: BENEFICIARY_ADDR.to_string(),
+                requested_amount: Uint128::zero(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ZeroRequestedAmount {}));
+}
+
+#[test]
+fn test_conviction_accrues_and_executes() {
+    let (mut app, core_addr, proposal_module) = setup_test();
+    let id = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+
+    // 10% of the voting power converges to a conviction of
+    // 100_000_000, crossing the threshold after 12 blocks.
+    stake(&mut app, &proposal_module, CREATOR_ADDR, id, 10_000_000).unwrap();
+
+    advance_blocks(&mut app, 11);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert!(proposal.proposal.conviction < proposal.threshold.unwrap());
+    let err = execute_proposal(&mut app, &proposal_module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed { id: 1 }));
+
+    advance_blocks(&mut app, 1);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert!(proposal.proposal.conviction >= proposal.threshold.unwrap());
+    execute_proposal(&mut app, &proposal_module, id).unwrap();
+
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(
+        query_balance_native(&app, BENEFICIARY_ADDR, TREASURY_DENOM),
+        Uint128::new(10_000)
+    );
+    assert_eq!(
+        query_balance_native(&app, core_addr.as_str(), TREASURY_DENOM),
+        Uint128::new(990_000)
+    );
+
+    // Executed proposals can not be executed again.
+    let err = execute_proposal(&mut app, &proposal_module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
+
+    // The stake may be withdrawn from the executed proposal.
+    unstake(&mut app, &proposal_module, CREATOR_ADDR, id, 10_000_000).unwrap();
+    assert_eq!(
+        query_staked_power(&app, &proposal_module, CREATOR_ADDR),
+        Uint128::zero()
+    );
+    assert!(query_list_stakes(&app, &proposal_module, id)
+        .stakes
+        .is_empty());
+}
+
+#[test]
+fn test_unstaking_decays_conviction() {
+    let (mut app, _core_addr, proposal_module) = setup_test();
+    let id = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+
+    stake(&mut app, &proposal_module, CREATOR_ADDR, id, 10_000_000).unwrap();
+    advance_blocks(&mut app, 1);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.conviction, Uint128::new(10_000_000));
+
+    unstake(&mut app, &proposal_module, CREATOR_ADDR, id, 10_000_000).unwrap();
+    advance_blocks(&mut app, 2);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.staked, Uint128::zero());
+    assert_eq!(proposal.proposal.conviction, Uint128::new(8_100_000));
+}
+
+#[test]
+fn test_stake_limits() {
+    let (mut app, _core_addr, proposal_module) = setup_test();
+    let first = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+    let second = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 20_000);
+
+    // Voting power may be spread across proposals, but not staked
+    // twice.
+    stake(&mut app, &proposal_module, MEMBER_ADDR, first, 15_000_000).unwrap();
+    let err = stake(&mut app, &proposal_module, MEMBER_ADDR, second, 5_000_001).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientVotingPower {
+            stake: 5_000_001,
+            available: 5_000_000
+        }
+    ));
+    stake(&mut app, &proposal_module, MEMBER_ADDR, second, 5_000_000).unwrap();
+    assert_eq!(
+        query_staked_power(&app, &proposal_module, MEMBER_ADDR),
+        Uint128::new(20_000_000)
+    );
+
+    // Unstaking frees voting power for other proposals.
+    unstake(&mut app, &proposal_module, MEMBER_ADDR, first, 5_000_000).unwrap();
+    stake(&mut app, &proposal_module, MEMBER_ADDR, second, 5_000_000).unwrap();
+    assert_eq!(
+        query_list_stakes(&app, &proposal_module, second).stakes,
+        vec![StakeInfo {
+            staker: Addr::unchecked(MEMBER_ADDR),
+            amount: Uint128::new(10_000_000),
+        }]
+    );
+
+    let err = unstake(&mut app, &proposal_module, MEMBER_ADDR, first, 10_000_001).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InsufficientStake {
+            amount: 10_000_001,
+            staked: 10_000_000
+        }
+    ));
+
+    let err = stake(&mut app, &proposal_module, BENEFICIARY_ADDR, first, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    let err = stake(&mut app, &proposal_module, CREATOR_ADDR, first, 0).unwrap_err();
+    assert!(matches!(err, ContractError::ZeroStake {}));
+
+    let err = stake(&mut app, &proposal_module, CREATOR_ADDR, 3, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NoSuchProposal { id: 3 }));
+}
+
+#[test]
+fn test_stake_moved_tokens() {
+    let (mut app, core_addr, proposal_module) = setup_test();
+    let first = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+    stake(&mut app, &proposal_module, MEMBER_ADDR, first, 20_000_000).unwrap();
+    advance_blocks(&mut app, 1);
+
+    // The member moves their tokens to another address, which bonds
+    // them.
+    let vault = query_voting_vault(&app, &core_addr);
+    app.execute_contract(
+        Addr::unchecked(MEMBER_ADDR),
+        vault.clone(),
+        &neutron_vault::msg::ExecuteMsg::Unbond {
+            amount: Uint128::new(20_000_000),
+        },
+        &[],
+    )
+    .unwrap();
+    app.send_tokens(
+        Addr::unchecked(MEMBER_ADDR),
+        Addr::unchecked(BENEFICIARY_ADDR),
+        &coins(20_000_000, "ujuno"),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(BENEFICIARY_ADDR),
+        vault,
+        &neutron_vault::msg::ExecuteMsg::Bond {},
+        &coins(20_000_000, "ujuno"),
+    )
+    .unwrap();
+    advance_blocks(&mut app, 1);
+
+    // The tokens can not be staked on the proposal again, as the
+    // power is read at its start.
+    let err = stake(&mut app, &proposal_module, BENEFICIARY_ADDR, first, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    // Proposals created after the move count the new holder.
+    let second = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, 10_000);
+    advance_blocks(&mut app, 1);
+    stake(
+        &mut app,
+        &proposal_module,
+        BENEFICIARY_ADDR,
+        second,
+        20_000_000,
+    )
+    .unwrap();
+    let err = stake(&mut app, &proposal_module, MEMBER_ADDR, second, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    // The stake the member left on the first proposal is no longer
+    // backed by voting power, so the moved tokens are not counted on
+    // both proposals.
+    assert_eq!(
+        query_staked_power(&app, &proposal_module, MEMBER_ADDR),
+        Uint128::new(20_000_000)
+    );
+    let proposal = query_proposal(&app, &proposal_module, first);
+    assert_eq!(proposal.proposal.staked, Uint128::zero());
+    assert_eq!(
+        query_proposal(&app, &proposal_module, second)
+            .proposal
+            .staked,
+        Uint128::new(20_000_000)
+    );
+
+    // 20_000_000 staked for 100 blocks would pass either proposal,
+    // but only the second one has it.
+    advance_blocks(&mut app, 100);
+    let err = execute_proposal(&mut app, &proposal_module, first).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed { id: 1 }));
+    execute_proposal(&mut app, &proposal_module, second).unwrap();
+}
+
+#[test]
+fn test_request_above_max_ratio() {
+    let (mut app, _core_addr, proposal_module) = setup_test();
+
+    // 20% of the treasury may never be granted.
+    let id = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 200_000);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.threshold, None);
+
+    stake(&mut app, &proposal_module, CREATOR_ADDR, id, 80_000_000).unwrap();
+    stake(&mut app, &proposal_module, MEMBER_ADDR, id, 20_000_000).unwrap();
+    advance_blocks(&mut app, 1_000);
+    let err = execute_proposal(&mut app, &proposal_module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotPassed { id: 1 }));
+}
+
+#[test]
+fn test_close() {
+    let (mut app, core_addr, proposal_module) = setup_test();
+    let id = make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+    stake(&mut app, &proposal_module, CREATOR_ADDR, id, 80_000_000).unwrap();
+
+    // Only the proposer or the DAO may close a proposal.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Close { proposal_id: id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked(MEMBER_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Close { proposal_id: id },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    let err = stake(&mut app, &proposal_module, MEMBER_ADDR, id, 1).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
+    advance_blocks(&mut app, 10);
+    let err = execute_proposal(&mut app, &proposal_module, id).unwrap_err();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
+    assert_eq!(
+        query_balance_native(&app, core_addr.as_str(), TREASURY_DENOM),
+        Uint128::new(1_000_000)
+    );
+}
+
+#[test]
+fn test_config_validation() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let code_id = app.store_code(proposal_conviction_contract());
+
+    let instantiate = |app: &mut BasicApp<NeutronMsg>, msg: InstantiateMsg| -> ContractError {
+        app.instantiate_contract(
+            code_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &msg,
+            &[],
+            "conviction",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap()
+    };
+
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            decay: Decimal::one(),
+            ..get_instantiate_msg()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidDecay {}));
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            decay: MAX_DECAY + Decimal::raw(1),
+            ..get_instantiate_msg()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidDecay {}));
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            max_ratio: Decimal::zero(),
+            ..get_instantiate_msg()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidMaxRatio {}));
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            max_ratio: Decimal::percent(101),
+            ..get_instantiate_msg()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidMaxRatio {}));
+    let err = instantiate(
+        &mut app,
+        InstantiateMsg {
+            weight: Decimal::zero(),
+            ..get_instantiate_msg()
+        },
+    );
+    assert!(matches!(err, ContractError::InvalidWeight {}));
+
+    // Only the DAO may update the config.
+    let (mut app, core_addr, proposal_module) = setup_test();
+    let update = ExecuteMsg::UpdateConfig {
+        denom: "ujuno".to_string(),
+        decay: Decimal::percent(99),
+        max_ratio: Decimal::percent(10),
+        weight: Decimal::percent(1),
+    };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &update,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(core_addr.clone(), proposal_module.clone(), &update, &[])
+        .unwrap();
+    let config = query_proposal_config(&app, &proposal_module);
+    assert_eq!(config.denom, "ujuno");
+    assert_eq!(config.decay, Decimal::percent(99));
+}
+
+#[test]
+fn test_proposal_hooks() {
+    let (mut app, core_addr, proposal_module) = setup_test();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::AddProposalHook {
+                address: "proposalhook".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // The DAO itself can not handle proposal hooks, so it is removed
+    // from the consumers once a proposal is created.
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::AddProposalHook {
+            address: core_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::AddProposalHook {
+                address: core_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::HookError(HookError::HookAlreadyRegistered {})
+    ));
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![core_addr.to_string()]);
+
+    make_proposal(&mut app, &proposal_module, MEMBER_ADDR, 10_000);
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert!(hooks.hooks.is_empty());
+}
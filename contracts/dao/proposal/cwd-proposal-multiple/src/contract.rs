@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult,
-    Storage, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use cw2::set_contract_version;
@@ -15,10 +15,12 @@ use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use cwd_vote_hooks::new_vote_hooks;
 use cwd_voting::{
     multiple_choice::{
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
+        ApprovalVote, ExecutionMode, MultipleChoiceOptions, MultipleChoiceVote,
+        MultipleChoiceVotes, RankedChoiceVote, VotingStrategy, MAX_NUM_CHOICES,
+        MAX_NUM_CHOICES_LIMIT,
     },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
-    proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
+    proposal::{validate_rationale, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
     },
@@ -29,10 +31,17 @@ use cwd_voting::{
 use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::{MultipleChoiceProposal, VoteResult},
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    proposal::{
+        LegacyMultipleChoiceProposal, MultipleChoiceProposal, OptionExecution,
+        OptionExecutionStatus,
+    },
+    query::{
+        ProposalListResponse, ProposalOptionsResponse, ProposalResponse, VoteInfo,
+        VoteListResponse, VoteResponse,
+    },
     state::{
-        Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+        may_load_proposal, may_load_proposal_mut, Ballot, Config, BALLOTS, CONFIG,
+        LEGACY_PROPOSALS, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, PROPOSAL_OPTIONS, VOTE_HOOKS,
     },
     ContractError,
 };
@@ -50,6 +59,8 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     msg.voting_strategy.validate()?;
+    validate_execution_mode(&msg.voting_strategy, msg.execution_mode)?;
+    validate_max_num_choices(&msg.voting_strategy, msg.max_num_choices)?;
 
     let dao = info.sender;
 
@@ -68,6 +79,9 @@ pub fn instantiate(
         allow_revoting: msg.allow_revoting,
         dao,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        max_rationale_length: msg.max_rationale_length,
+        execution_mode: msg.execution_mode,
+        max_num_choices: msg.max_num_choices,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -104,7 +118,21 @@ pub fn execute(
             choices,
             proposer,
         ),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::RankedVote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_ranked_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::ApprovalVote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_approval_vote(deps, env, info, proposal_id, vote, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
@@ -115,6 +143,9 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            max_rationale_length,
+            execution_mode,
+            max_num_choices,
         } => execute_update_config(
             deps,
             info,
@@ -125,6 +156,9 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            max_rationale_length,
+            execution_mode,
+            max_num_choices,
         ),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
@@ -189,7 +223,9 @@ pub fn execute_propose(
     }
 
     // Validate options.
-    let checked_multiple_choice_options = options.into_checked()?.options;
+    let checked_multiple_choice_options = options
+        .into_checked(config.max_num_choices.unwrap_or(MAX_NUM_CHOICES))?
+        .options;
 
     let expiration = config.max_voting_period.after(&env.block);
     let total_power = get_total_power(deps.as_ref(), config.dao, None)?;
@@ -207,8 +243,11 @@ pub fn execute_propose(
             total_power,
             status: Status::Open,
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
+            ranked_votes: None,
+            approval_power: None,
             allow_revoting: config.allow_revoting,
-            choices: checked_multiple_choice_options,
+            execution_mode: config.execution_mode,
+            option_executions: None,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -233,6 +272,9 @@ pub fn execute_propose(
     //
     // `to_vec` is the method used by cosmwasm to convert a struct
     // into it's byte representation in storage.
+    //
+    // Options are stored apart from the proposal and limited on their
+    // own, so that they can be paged through.
     let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
     if proposal_size > MAX_PROPOSAL_SIZE {
         return Err(ContractError::ProposalTooLarge {
@@ -240,6 +282,16 @@ pub fn execute_propose(
             max: MAX_PROPOSAL_SIZE,
         });
     }
+    for option in checked_multiple_choice_options {
+        let option_size = cosmwasm_std::to_vec(&option)?.len() as u64;
+        if option_size > MAX_PROPOSAL_SIZE {
+            return Err(ContractError::ProposalTooLarge {
+                size: option_size,
+                max: MAX_PROPOSAL_SIZE,
+            });
+        }
+        PROPOSAL_OPTIONS.save(deps.storage, (id, option.index), &option)?;
+    }
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
 
@@ -281,16 +333,133 @@ pub fn execute_vote(
     info: MessageInfo,
     proposal_id: u64,
     vote: MultipleChoiceVote,
+    rationale: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    cast_vote(
+        deps,
+        env,
+        info,
+        proposal_id,
+        CastVote::Single(vote),
+        rationale,
+    )
+}
+
+pub fn execute_ranked_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: RankedChoiceVote,
+    rationale: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    cast_vote(
+        deps,
+        env,
+        info,
+        proposal_id,
+        CastVote::Ranked(vote),
+        rationale,
+    )
+}
+
+pub fn execute_approval_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: ApprovalVote,
+    rationale: Option<String>,
+) -> Result<Response<Empty>, ContractError> {
+    cast_vote(
+        deps,
+        env,
+        info,
+        proposal_id,
+        CastVote::Approval(vote),
+        rationale,
+    )
+}
+
+/// A vote cast with one of the voting methods of the module.
+enum CastVote {
+    Single(MultipleChoiceVote),
+    Ranked(RankedChoiceVote),
+    Approval(ApprovalVote),
+}
+
+/// Checks that `cast_vote` is valid for the voting strategy of `prop`
+/// and returns its most preferred option along with its ranking or
+/// approvals. Single choice votes on ranked choice and approval
+/// proposals rank or approve only the selected option.
+fn check_vote(
+    prop: &MultipleChoiceProposal,
+    cast_vote: CastVote,
+) -> Result<
+    (
+        MultipleChoiceVote,
+        Option<RankedChoiceVote>,
+        Option<ApprovalVote>,
+    ),
+    ContractError,
+> {
+    let num_choices = prop.num_choices();
+    match (&prop.voting_strategy, cast_vote) {
+        (_, CastVote::Single(vote)) if vote.option_id as usize >= num_choices => {
+            Err(ContractError::InvalidVote {})
+        }
+        (VotingStrategy::SingleChoice { .. }, CastVote::Single(vote)) => Ok((vote, None, None)),
+        (VotingStrategy::RankedChoice { .. }, CastVote::Single(vote)) => Ok((
+            vote,
+            Some(RankedChoiceVote {
+                ranking: vec![vote.option_id],
+            }),
+            None,
+        )),
+        (VotingStrategy::Approval { .. }, CastVote::Single(vote)) => Ok((
+            vote,
+            None,
+            Some(ApprovalVote {
+                option_ids: vec![vote.option_id],
+            }),
+        )),
+        (VotingStrategy::RankedChoice { .. }, CastVote::Ranked(ranked_vote)) => {
+            match ranked_vote.first_choice() {
+                Some(vote) if ranked_vote.is_valid(num_choices) => {
+                    Ok((vote, Some(ranked_vote), None))
+                }
+                _ => Err(ContractError::InvalidVote {}),
+            }
+        }
+        (VotingStrategy::Approval { max_selections, .. }, CastVote::Approval(approval_vote)) => {
+            if !approval_vote.is_valid(num_choices, *max_selections) {
+                return Err(ContractError::InvalidVote {});
+            }
+            let vote = MultipleChoiceVote {
+                option_id: approval_vote.option_ids[0],
+            };
+            Ok((vote, None, Some(approval_vote)))
+        }
+        (_, CastVote::Ranked(_)) => Err(ContractError::RankedVoteNotAllowed {}),
+        (_, CastVote::Approval(_)) => Err(ContractError::ApprovalVoteNotAllowed {}),
+    }
+}
+
+fn cast_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    cast_vote: CastVote,
+    rationale: Option<String>,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut prop = PROPOSALS
-        .may_load(deps.storage, proposal_id)?
+    validate_rationale(rationale.as_deref(), config.max_rationale_length)?;
+    let mut prop = may_load_proposal_mut(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     // Check that this is a valid vote.
-    if vote.option_id as usize >= prop.choices.len() {
-        return Err(ContractError::InvalidVote {});
-    }
+    let (vote, ranked_vote, approval_vote) = check_vote(&prop, cast_vote)?;
 
     if prop.current_status(&env.block)? != Status::Open {
         return Err(ContractError::NotOpen { id: proposal_id });
@@ -306,24 +475,29 @@ pub fn execute_vote(
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(
+    let ballot = BALLOTS.update(
         deps.storage,
         (proposal_id, info.sender.clone()),
         |bal| match bal {
             Some(current_ballot) => {
                 if prop.allow_revoting {
-                    if current_ballot.vote == vote {
+                    if current_ballot.vote == vote
+                        && current_ballot.ranked_vote == ranked_vote
+                        && current_ballot.approval_vote == approval_vote
+                    {
                         // Don't allow casting the same vote more than
                         // once. This seems liable to be confusing
                         // behavior.
                         Err(ContractError::AlreadyCast {})
                     } else {
                         // Remove the old vote if this is a re-vote.
-                        prop.votes
-                            .remove_vote(current_ballot.vote, current_ballot.power)?;
+                        current_ballot.remove_from(&mut prop)?;
                         Ok(Ballot {
                             power: vote_power,
                             vote,
+                            ranked_vote: ranked_vote.clone(),
+                            approval_vote: approval_vote.clone(),
+                            rationale: rationale.clone(),
                         })
                     }
                 } else {
@@ -333,13 +507,16 @@ pub fn execute_vote(
             None => Ok(Ballot {
                 vote,
                 power: vote_power,
+                ranked_vote: ranked_vote.clone(),
+                approval_vote: approval_vote.clone(),
+                rationale: rationale.clone(),
             }),
         },
     )?;
 
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power)?;
+    ballot.add_to(&mut prop)?;
     prop.update_status(&env.block)?;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
     let new_status = prop.status;
@@ -355,7 +532,8 @@ pub fn execute_vote(
         deps.storage,
         proposal_id,
         info.sender.to_string(),
-        vote.to_string(),
+        ballot.position(),
+        rationale,
     )?;
     Ok(Response::default()
         .add_submessages(change_hooks)
@@ -363,7 +541,7 @@ pub fn execute_vote(
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("position", vote.to_string())
+        .add_attribute("position", ballot.position())
         .add_attribute("status", prop.status.to_string()))
 }
 
@@ -386,8 +564,7 @@ pub fn execute_execute(
         }
     }
 
-    let mut prop = PROPOSALS
-        .may_load(deps.storage, proposal_id)?
+    let mut prop = may_load_proposal_mut(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     // Check here that the proposal is passed. Allow it to be
@@ -399,80 +576,101 @@ pub fn execute_execute(
         return Err(ContractError::NotPassed {});
     }
 
+    let winning_choices = prop.winning_choices()?;
+    if winning_choices.is_empty() {
+        // We don't anticipate this case as the proposal would not be
+        // in passed state, checked above.
+        return Err(ContractError::Tie {});
+    }
+
+    // Options of proposals executing more than the winning option
+    // reply on success too, so that the status of each option can be
+    // tracked. Replies arrive in the order the options are executed.
+    let executes_multiple_options = prop.executes_multiple_options();
+    let mut response = Response::default();
+    let mut option_executions = vec![];
+    for option_id in winning_choices {
+        let msgs = PROPOSAL_OPTIONS
+            .load(deps.storage, (proposal_id, option_id))?
+            .msgs
+            .unwrap_or_default();
+        let status = if msgs.is_empty() {
+            OptionExecutionStatus::Executed
+        } else {
+            let execute_message = WasmMsg::Execute {
+                contract_addr: config.dao.to_string(),
+                msg: to_binary(&cwd_core::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+                funds: vec![],
+            };
+            let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+            match (
+                config.close_proposal_on_execution_failure,
+                executes_multiple_options,
+            ) {
+                (true, true) => {
+                    response = response
+                        .add_submessage(SubMsg::reply_always(execute_message, masked_proposal_id));
+                    OptionExecutionStatus::Pending
+                }
+                (true, false) => {
+                    response = response.add_submessage(SubMsg::reply_on_error(
+                        execute_message,
+                        masked_proposal_id,
+                    ));
+                    OptionExecutionStatus::Executed
+                }
+                (false, _) => {
+                    response = response.add_message(execute_message);
+                    OptionExecutionStatus::Executed
+                }
+            }
+        };
+        option_executions.push(OptionExecution { option_id, status });
+    }
+
     prop.status = Status::Executed;
+    if executes_multiple_options {
+        prop.option_executions = Some(option_executions);
+    }
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    let vote_result = prop.calculate_vote_result()?;
-    match vote_result {
-        VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
-        VoteResult::SingleWinner(winning_choice) => {
-            let response = match winning_choice.msgs {
-                Some(msgs) => {
-                    if !msgs.is_empty() {
-                        let execute_message = WasmMsg::Execute {
-                            contract_addr: config.dao.to_string(),
-                            msg: to_binary(&cwd_core::msg::ExecuteMsg::ExecuteProposalHook {
-                                msgs,
-                            })?,
-                            funds: vec![],
-                        };
-                        match config.close_proposal_on_execution_failure {
-                            true => {
-                                let masked_proposal_id =
-                                    mask_proposal_execution_proposal_id(proposal_id);
-                                Response::default().add_submessage(SubMsg::reply_on_error(
-                                    execute_message,
-                                    masked_proposal_id,
-                                ))
-                            }
-                            false => Response::default().add_message(execute_message),
-                        }
-                    } else {
-                        Response::default()
-                    }
-                }
-                None => Response::default(),
-            };
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
 
-            let hooks = proposal_status_changed_hooks(
-                PROPOSAL_HOOKS,
-                deps.storage,
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
                 proposal_id,
-                old_status.to_string(),
-                prop.status.to_string(),
-            )?;
-
-            // Add prepropose / deposit module hook which will handle deposit refunds.
-            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-            let hooks = match proposal_creation_policy {
-                ProposalCreationPolicy::Anyone {} => hooks,
-                ProposalCreationPolicy::Module { addr } => {
-                    let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
-                        proposal_id,
-                        new_status: prop.status,
-                    })?;
-                    let mut hooks = hooks;
-                    hooks.push(SubMsg::reply_on_error(
-                        WasmMsg::Execute {
-                            contract_addr: addr.into_string(),
-                            msg,
-                            funds: vec![],
-                        },
-                        failed_pre_propose_module_hook_id(),
-                    ));
-                    hooks
-                }
-            };
-
-            Ok(response
-                .add_submessages(hooks)
-                .add_attribute("action", "execute")
-                .add_attribute("sender", info.sender)
-                .add_attribute("proposal_id", proposal_id.to_string())
-                .add_attribute("dao", config.dao))
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
         }
-    }
+    };
+
+    Ok(response
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
 }
 
 pub fn execute_close(
@@ -481,7 +679,8 @@ pub fn execute_close(
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response<Empty>, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut prop = may_load_proposal_mut(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     prop.update_status(&env.block)?;
     if prop.status != Status::Rejected {
@@ -541,6 +740,9 @@ pub fn execute_update_config(
     allow_revoting: bool,
     dao: String,
     close_proposal_on_execution_failure: bool,
+    max_rationale_length: Option<u64>,
+    execution_mode: Option<ExecutionMode>,
+    max_num_choices: Option<u32>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -550,6 +752,8 @@ pub fn execute_update_config(
     }
 
     voting_strategy.validate()?;
+    validate_execution_mode(&voting_strategy, execution_mode)?;
+    validate_max_num_choices(&voting_strategy, max_num_choices)?;
 
     let dao = deps.api.addr_validate(&dao)?;
 
@@ -566,6 +770,9 @@ pub fn execute_update_config(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            max_rationale_length,
+            execution_mode,
+            max_num_choices,
         },
     )?;
 
@@ -700,6 +907,44 @@ pub fn remove_hook(
     Ok(())
 }
 
+/// Checks that proposals may have at least two and at most
+/// `MAX_NUM_CHOICES_LIMIT` options, if the maximum number of options
+/// is set. Ranked choice proposals may have at most `MAX_NUM_CHOICES`
+/// options so that the number of distinct rankings stays small.
+fn validate_max_num_choices(
+    voting_strategy: &VotingStrategy,
+    max_num_choices: Option<u32>,
+) -> Result<(), ContractError> {
+    let max = match voting_strategy {
+        VotingStrategy::RankedChoice { .. } => MAX_NUM_CHOICES,
+        _ => MAX_NUM_CHOICES_LIMIT,
+    };
+    match max_num_choices {
+        Some(max_num_choices) if !(2..=max).contains(&max_num_choices) => {
+            Err(ContractError::InvalidMaxNumChoices { max })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that proposals with `voting_strategy` can execute the
+/// options selected by `execution_mode`. Instant-runoff only finds a
+/// single winner, so ranked choice proposals execute only that option.
+fn validate_execution_mode(
+    voting_strategy: &VotingStrategy,
+    execution_mode: Option<ExecutionMode>,
+) -> Result<(), ContractError> {
+    if let Some(execution_mode) = execution_mode {
+        execution_mode.validate()?;
+        if matches!(voting_strategy, VotingStrategy::RankedChoice { .. })
+            && execution_mode != (ExecutionMode::Winner {})
+        {
+            return Err(ContractError::UnsupportedExecutionMode {});
+        }
+    }
+    Ok(())
+}
+
 pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
     let id: u64 = PROPOSAL_COUNT.load(store)? + 1;
     PROPOSAL_COUNT.save(store, &id)?;
@@ -721,6 +966,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ListProposalOptions {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_proposal_options(deps, proposal_id, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
@@ -744,7 +994,8 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let (proposal, _) = may_load_proposal(deps.storage, id)?
+        .ok_or_else(|| StdError::not_found(std::any::type_name::<MultipleChoiceProposal>()))?;
     to_binary(&proposal.into_response(&env.block, id)?)
 }
 
@@ -761,12 +1012,12 @@ pub fn query_list_proposals(
 ) -> StdResult<Binary> {
     let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = LEGACY_PROPOSALS
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
-        .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
+        .collect::<Result<Vec<(u64, LegacyMultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, proposal)| proposal.into_proposal().0.into_response(&env.block, id))
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
@@ -780,12 +1031,12 @@ pub fn query_reverse_proposals(
 ) -> StdResult<Binary> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let max = start_before.map(Bound::exclusive);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = LEGACY_PROPOSALS
         .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
         .take(limit as usize)
-        .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
+        .collect::<Result<Vec<(u64, LegacyMultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, proposal)| proposal.into_proposal().0.into_response(&env.block, id))
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
@@ -802,7 +1053,10 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
     let vote = ballot.map(|ballot| VoteInfo {
         voter,
         vote: ballot.vote,
+        ranked_vote: ballot.ranked_vote,
+        approval_vote: ballot.approval_vote,
         power: ballot.power,
+        rationale: ballot.rationale,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -828,7 +1082,10 @@ pub fn query_list_votes(
             Ok(VoteInfo {
                 voter,
                 vote: ballot.vote,
+                ranked_vote: ballot.ranked_vote,
+                approval_vote: ballot.approval_vote,
                 power: ballot.power,
+                rationale: ballot.rationale,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -836,6 +1093,35 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_list_proposal_options(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u32>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    // Proposals created by earlier versions hold their options inline
+    // until they are updated.
+    if let Some((_, Some(options))) = may_load_proposal(deps.storage, proposal_id)? {
+        let options = options
+            .into_iter()
+            .filter(|option| start_after.map_or(true, |start_after| option.index > start_after))
+            .take(limit as usize)
+            .collect();
+        return to_binary(&ProposalOptionsResponse { options });
+    }
+    let min = start_after.map(Bound::<u32>::exclusive);
+
+    let options = PROPOSAL_OPTIONS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(_, option)| option))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalOptionsResponse { options })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cwd_interface::voting::InfoResponse { info })
@@ -846,14 +1132,41 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            let failed = msg.result.is_err();
             PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
                 Some(mut prop) => {
-                    prop.status = Status::ExecutionFailed;
+                    match prop.option_executions {
+                        // Options reply in the order they were executed,
+                        // so the reply is for the first pending option.
+                        Some(ref mut option_executions) => {
+                            if let Some(option_execution) =
+                                option_executions.iter_mut().find(|option_execution| {
+                                    option_execution.status == OptionExecutionStatus::Pending
+                                })
+                            {
+                                option_execution.status = if failed {
+                                    OptionExecutionStatus::ExecutionFailed
+                                } else {
+                                    OptionExecutionStatus::Executed
+                                };
+                            }
+                            if failed {
+                                prop.status = Status::ExecutionFailed;
+                            }
+                        }
+                        None => prop.status = Status::ExecutionFailed,
+                    }
                     Ok(prop)
                 }
                 None => Err(ContractError::NoSuchProposal { id: proposal_id }),
             })?;
-            Ok(Response::new().add_attribute("proposal execution failed", proposal_id.to_string()))
+            if failed {
+                Ok(Response::new()
+                    .add_attribute("proposal execution failed", proposal_id.to_string()))
+            } else {
+                Ok(Response::new()
+                    .add_attribute("proposal option executed", proposal_id.to_string()))
+            }
         }
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
@@ -899,6 +1212,10 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // The options of proposals created by earlier versions are moved
+    // to `PROPOSAL_OPTIONS` when the proposals are next updated, see
+    // `may_load_proposal_mut`, rather than all at once here.
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
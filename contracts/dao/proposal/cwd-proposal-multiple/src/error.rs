@@ -77,6 +77,18 @@ pub enum ContractError {
     #[error("Invalid vote selected.")]
     InvalidVote {},
 
+    #[error("Only ranked choice proposals accept ranked votes.")]
+    RankedVoteNotAllowed {},
+
+    #[error("Only approval proposals accept approval votes.")]
+    ApprovalVoteNotAllowed {},
+
+    #[error("Ranked choice proposals may only execute the winning option.")]
+    UnsupportedExecutionMode {},
+
+    #[error("Proposals must allow between two and ({max}) options.")]
+    InvalidMaxNumChoices { max: u32 },
+
     #[error("Must have voting power to propose.")]
     MustHaveVotingPower {},
 
@@ -5,7 +5,10 @@ use cwd_hooks::HooksResponse;
 use cwd_interface::voting::InfoResponse;
 use cwd_macros::{info_query, proposal_module_query};
 use cwd_voting::{
-    multiple_choice::{MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy},
+    multiple_choice::{
+        ApprovalVote, ExecutionMode, MultipleChoiceOptions, MultipleChoiceVote, RankedChoiceVote,
+        VotingStrategy,
+    },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
 };
 
@@ -40,6 +43,16 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's reserve was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The maximum length of vote rationales in bytes. Defaults to
+    /// `DEFAULT_MAX_RATIONALE_LENGTH` if not set.
+    pub max_rationale_length: Option<u64>,
+    /// Which options of passed proposals are executed. Only the
+    /// winning option is executed if not set. Ranked choice proposals
+    /// may only execute the winning option.
+    pub execution_mode: Option<ExecutionMode>,
+    /// The maximum number of options of proposals, not counting
+    /// "None of the above". Defaults to `MAX_NUM_CHOICES` if not set.
+    pub max_num_choices: Option<u32>,
 }
 
 #[cw_serde]
@@ -66,6 +79,35 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         /// The senders position on the proposal.
         vote: MultipleChoiceVote,
+        /// An optional explanation of the vote, stored with the
+        /// ballot and included in the vote hook.
+        rationale: Option<String>,
+    },
+    /// Votes on a ranked choice proposal, ranking the options from
+    /// the most to the least preferred. Voting with `Vote` on a ranked
+    /// choice proposal ranks only the selected option.
+    RankedVote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The senders ranking of the options.
+        vote: RankedChoiceVote,
+        /// An optional explanation of the vote, stored with the
+        /// ballot and included in the vote hook.
+        rationale: Option<String>,
+    },
+    /// Votes on an approval proposal, approving at most the
+    /// `max_selections` of the voting strategy options. Each approved
+    /// option receives the full voting power of the sender. Voting
+    /// with `Vote` on an approval proposal approves only the selected
+    /// option.
+    ApprovalVote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The options the sender approves of.
+        vote: ApprovalVote,
+        /// An optional explanation of the vote, stored with the
+        /// ballot and included in the vote hook.
+        rationale: Option<String>,
     },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
@@ -115,6 +157,16 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's reserve was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// The maximum length of vote rationales in bytes. Defaults
+        /// to `DEFAULT_MAX_RATIONALE_LENGTH` if not set.
+        max_rationale_length: Option<u64>,
+        /// Which options of passed proposals are executed. This will
+        /// only apply to proposals created after the config update.
+        execution_mode: Option<ExecutionMode>,
+        /// The maximum number of options of proposals, not counting
+        /// "None of the above". Defaults to `MAX_NUM_CHOICES` if not
+        /// set.
+        max_num_choices: Option<u32>,
     },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
@@ -175,6 +227,17 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u64>,
     },
+    /// Lists the options of a proposal in index order, "None of the
+    /// above" last.
+    #[returns(crate::query::ProposalOptionsResponse)]
+    ListProposalOptions {
+        proposal_id: u64,
+        /// The index of the option to start listing options after.
+        start_after: Option<u32>,
+        /// The maximum number of options to return. If no limit is
+        /// set a max of 30 options will be returned.
+        limit: Option<u64>,
+    },
     /// Gets the current proposal creation policy for this
     /// module.
     #[returns(ProposalCreationPolicy)]
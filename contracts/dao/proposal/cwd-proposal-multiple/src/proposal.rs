@@ -3,7 +3,8 @@ use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Uint128};
 use cw_utils::Expiration;
 use cwd_voting::{
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes, VotingStrategy,
+        CheckedMultipleChoiceOption, ExecutionMode, MultipleChoiceOptionType, MultipleChoiceVotes,
+        RankedChoiceVotes, VotingStrategy,
     },
     proposal::Proposal,
     status::Status,
@@ -29,25 +30,115 @@ pub struct MultipleChoiceProposal {
     /// The the time at which this proposal will expire and close for
     /// additional votes.
     pub expiration: Expiration,
-    /// The options to be chosen from in the vote.
-    pub choices: Vec<CheckedMultipleChoiceOption>,
     /// Proposal status (Open, rejected, executed, execution failed, closed, passed)
     pub status: Status,
     /// Voting settings (threshold, quorum, etc.)
     pub voting_strategy: VotingStrategy,
     /// The total power when the proposal started (used to calculate percentages)
     pub total_power: Uint128,
-    /// The vote tally.
+    /// The vote tally. For ranked choice proposals this holds the
+    /// power behind the first choice of each vote. For approval
+    /// proposals every approved option receives the full power of a
+    /// vote.
     pub votes: MultipleChoiceVotes,
+    /// The power behind each ranking cast on a ranked choice
+    /// proposal. None until the first vote is cast.
+    pub ranked_votes: Option<RankedChoiceVotes>,
+    /// The voting power behind the votes cast on an approval
+    /// proposal. None until the first vote is cast.
+    pub approval_power: Option<Uint128>,
     /// Whether DAO members are allowed to change their votes.
     /// When disabled, proposals can be executed as soon as they pass.
     /// When enabled, proposals can only be executed after the voting
     /// period has ended and the proposal passed.
     pub allow_revoting: bool,
+    /// Which options are executed once the proposal passes. Only the
+    /// winning option is executed if not set.
+    pub execution_mode: Option<ExecutionMode>,
+    /// The execution status of each executed option in rank order,
+    /// if the proposal executes more than the winning option. None
+    /// until the proposal is executed.
+    pub option_executions: Option<Vec<OptionExecution>>,
+}
+
+/// The execution status of one of the options of a proposal.
+#[cw_serde]
+pub struct OptionExecution {
+    /// The index of the option in the choices of the proposal.
+    pub option_id: u32,
+    pub status: OptionExecutionStatus,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum OptionExecutionStatus {
+    /// The messages of the option were dispatched and their result is
+    /// not known yet.
+    Pending,
+    /// The messages of the option were executed.
+    Executed,
+    /// The messages of the option failed to execute.
+    ExecutionFailed,
+}
+
+/// A proposal stored with its options inline, as proposals were
+/// before their options were moved to `PROPOSAL_OPTIONS`. Proposals
+/// stored since deserialize to it without options.
+#[cw_serde]
+pub struct LegacyMultipleChoiceProposal {
+    pub title: String,
+    pub description: String,
+    pub proposer: Addr,
+    pub start_height: u64,
+    pub min_voting_period: Option<Expiration>,
+    pub expiration: Expiration,
+    /// The options of the proposal. None if they are already stored
+    /// separately.
+    pub choices: Option<Vec<CheckedMultipleChoiceOption>>,
+    pub status: Status,
+    pub voting_strategy: VotingStrategy,
+    pub total_power: Uint128,
+    pub votes: MultipleChoiceVotes,
+    pub ranked_votes: Option<RankedChoiceVotes>,
+    pub approval_power: Option<Uint128>,
+    pub allow_revoting: bool,
+    pub execution_mode: Option<ExecutionMode>,
+    pub option_executions: Option<Vec<OptionExecution>>,
+}
+
+impl LegacyMultipleChoiceProposal {
+    /// Splits the legacy proposal into a proposal and its inline
+    /// options.
+    pub fn into_proposal(
+        self,
+    ) -> (
+        MultipleChoiceProposal,
+        Option<Vec<CheckedMultipleChoiceOption>>,
+    ) {
+        let proposal = MultipleChoiceProposal {
+            title: self.title,
+            description: self.description,
+            proposer: self.proposer,
+            start_height: self.start_height,
+            min_voting_period: self.min_voting_period,
+            expiration: self.expiration,
+            status: self.status,
+            voting_strategy: self.voting_strategy,
+            total_power: self.total_power,
+            votes: self.votes,
+            ranked_votes: self.ranked_votes,
+            approval_power: self.approval_power,
+            allow_revoting: self.allow_revoting,
+            execution_mode: self.execution_mode,
+            option_executions: self.option_executions,
+        };
+        (proposal, self.choices)
+    }
 }
 
 pub enum VoteResult {
-    SingleWinner(CheckedMultipleChoiceOption),
+    /// The index of the winning option.
+    SingleWinner(u32),
     Tie,
 }
 
@@ -86,6 +177,76 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// The number of options of the proposal, including "None of the
+    /// above".
+    pub fn num_choices(&self) -> usize {
+        self.votes.vote_weights.len()
+    }
+
+    /// The type of the option at `index`. "None of the above" is
+    /// always the last option.
+    pub fn option_type(&self, index: u32) -> MultipleChoiceOptionType {
+        if index as usize + 1 == self.num_choices() {
+            MultipleChoiceOptionType::None
+        } else {
+            MultipleChoiceOptionType::Standard
+        }
+    }
+
+    /// The voting power behind the votes cast on the proposal.
+    pub fn voted_power(&self) -> Uint128 {
+        match self.voting_strategy {
+            VotingStrategy::Approval { .. } => self.approval_power.unwrap_or_default(),
+            _ => self.votes.total(),
+        }
+    }
+
+    /// Whether the proposal executes more than the winning option.
+    pub fn executes_multiple_options(&self) -> bool {
+        !matches!(self.execution_mode, None | Some(ExecutionMode::Winner {}))
+    }
+
+    /// The indexes of the options executed once the proposal passes,
+    /// in rank order. In the `top_n` and `threshold` execution modes
+    /// only options with more votes than "None of the above" are
+    /// executed, and equally voted options are ranked by their index.
+    pub fn winning_choices(&self) -> StdResult<Vec<u32>> {
+        let execution_mode = match self.execution_mode {
+            None | Some(ExecutionMode::Winner {}) => {
+                return Ok(match self.calculate_vote_result()? {
+                    VoteResult::SingleWinner(index)
+                        if self.option_type(index) == MultipleChoiceOptionType::Standard =>
+                    {
+                        vec![index]
+                    }
+                    _ => vec![],
+                });
+            }
+            Some(execution_mode) => execution_mode,
+        };
+
+        let power = |index: &u32| self.votes.vote_weights[*index as usize];
+        let none_power = self.votes.vote_weights.last().copied().unwrap_or_default();
+        let mut winners: Vec<u32> = (0..self.num_choices() as u32)
+            .filter(|index| {
+                self.option_type(*index) == MultipleChoiceOptionType::Standard
+                    && power(index) > none_power
+            })
+            .collect();
+        // The sort is stable, so equally voted options stay in index
+        // order.
+        winners.sort_by(|a, b| power(b).cmp(&power(a)));
+        match execution_mode {
+            ExecutionMode::Winner {} => (),
+            ExecutionMode::TopN { n } => winners.truncate(n as usize),
+            ExecutionMode::Threshold { percent } => {
+                let bar = self.voted_power() * percent;
+                winners.retain(|index| power(index) >= bar);
+            }
+        }
+        Ok(winners)
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
         let new_status = self.current_status(block)?;
@@ -116,9 +277,21 @@ impl MultipleChoiceProposal {
             }
         }
 
+        // The options executed by proposals executing more than the
+        // winning option are only known once voting has ended.
+        if self.executes_multiple_options() {
+            return Ok(self.expiration.is_expired(block)
+                && does_vote_count_pass(
+                    self.voted_power(),
+                    self.total_power,
+                    self.voting_strategy.get_quorum(),
+                )
+                && !self.winning_choices()?.is_empty());
+        }
+
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
-            self.votes.total(),
+            self.voted_power(),
             self.total_power,
             self.voting_strategy.get_quorum(),
         ) {
@@ -128,14 +301,14 @@ impl MultipleChoiceProposal {
                 VoteResult::Tie => return Ok(false),
                 VoteResult::SingleWinner(winning_choice) => {
                     // Proposal is not passed if winning choice is None.
-                    if winning_choice.option_type != MultipleChoiceOptionType::None {
+                    if self.option_type(winning_choice) != MultipleChoiceOptionType::None {
                         // If proposal is expired, quorum has been reached, and winning choice is neither tied nor None, then proposal is passed.
                         if self.expiration.is_expired(block) {
                             return Ok(true);
                         } else {
                             // If the proposal is not expired but the leading choice cannot
                             // possibly be outwon by any other choices, the proposal has passed.
-                            return self.is_choice_unbeatable(&winning_choice);
+                            return self.is_choice_unbeatable(winning_choice);
                         }
                     }
                 }
@@ -150,6 +323,11 @@ impl MultipleChoiceProposal {
         if self.allow_revoting && !self.expiration.is_expired(block) {
             return Ok(false);
         }
+        // Proposals executing more than the winning option are only
+        // rejected once they expire without passing.
+        if self.executes_multiple_options() {
+            return Ok(false);
+        }
 
         let vote_result = self.calculate_vote_result()?;
         match vote_result {
@@ -157,13 +335,13 @@ impl MultipleChoiceProposal {
             // there is no voting power left.
             VoteResult::Tie => {
                 let rejected =
-                    self.expiration.is_expired(block) || self.total_power == self.votes.total();
+                    self.expiration.is_expired(block) || self.total_power == self.voted_power();
                 Ok(rejected)
             }
             VoteResult::SingleWinner(winning_choice) => {
                 match (
                     does_vote_count_pass(
-                        self.votes.total(),
+                        self.voted_power(),
                         self.total_power,
                         self.voting_strategy.get_quorum(),
                     ),
@@ -172,7 +350,7 @@ impl MultipleChoiceProposal {
                     // Quorum is met and proposal is expired.
                     (true, true) => {
                         // Proposal is rejected if "None" is the winning option.
-                        if winning_choice.option_type == MultipleChoiceOptionType::None {
+                        if self.option_type(winning_choice) == MultipleChoiceOptionType::None {
                             return Ok(true);
                         }
                         Ok(false)
@@ -181,8 +359,8 @@ impl MultipleChoiceProposal {
                     (true, false) | (false, false) => {
                         // If the proposal is not expired and the leading choice is None and it cannot
                         // possibly be outwon by any other choices, the proposal is rejected.
-                        if winning_choice.option_type == MultipleChoiceOptionType::None {
-                            return self.is_choice_unbeatable(&winning_choice);
+                        if self.option_type(winning_choice) == MultipleChoiceOptionType::None {
+                            return self.is_choice_unbeatable(winning_choice);
                         }
                         Ok(false)
                     }
@@ -196,7 +374,7 @@ impl MultipleChoiceProposal {
     /// Find the option with the highest vote weight, and note if there is a tie.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
-            VotingStrategy::SingleChoice { quorum: _ } => {
+            VotingStrategy::SingleChoice { .. } | VotingStrategy::Approval { .. } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
                     let top_choices: Vec<(usize, &Uint128)> = self
@@ -214,9 +392,7 @@ impl MultipleChoiceProposal {
 
                     match top_choices.first() {
                         Some(winning_choice) => {
-                            return Ok(VoteResult::SingleWinner(
-                                self.choices[winning_choice.0].clone(),
-                            ));
+                            return Ok(VoteResult::SingleWinner(winning_choice.0 as u32));
                         }
                         None => {
                             return Err(StdError::generic_err("no votes found"));
@@ -225,16 +401,28 @@ impl MultipleChoiceProposal {
                 }
                 Err(StdError::not_found("max vote weight"))
             }
+            VotingStrategy::RankedChoice { quorum: _ } => Ok(self
+                .ranked_votes
+                .as_ref()
+                .and_then(|ranked_votes| ranked_votes.instant_runoff(self.num_choices()))
+                .map_or(VoteResult::Tie, VoteResult::SingleWinner)),
         }
     }
 
     /// Ensure that with the remaining vote power, the choice with the second highest votes
     /// cannot overtake the first choice.
-    fn is_choice_unbeatable(
-        &self,
-        winning_choice: &CheckedMultipleChoiceOption,
-    ) -> StdResult<bool> {
-        let winning_choice_power = self.votes.vote_weights[winning_choice.index as usize];
+    fn is_choice_unbeatable(&self, winning_choice: u32) -> StdResult<bool> {
+        let winning_choice_power = self.votes.vote_weights[winning_choice as usize];
+        if let VotingStrategy::RankedChoice { .. } = self.voting_strategy {
+            // Votes transfer between options as they are eliminated, so
+            // only a majority of first choices over all the power left
+            // is sure to win.
+            let other_power = self.total_power - winning_choice_power;
+            return Ok(match self.option_type(winning_choice) {
+                MultipleChoiceOptionType::Standard => winning_choice_power > other_power,
+                MultipleChoiceOptionType::None => winning_choice_power >= other_power,
+            });
+        }
         if let Some(second_choice_power) = self
             .votes
             .vote_weights
@@ -243,8 +431,8 @@ impl MultipleChoiceProposal {
             .max_by(|&a, &b| a.cmp(b))
         {
             // Check if the remaining vote power can be used to overtake the current winning choice.
-            let remaining_vote_power = self.total_power - self.votes.total();
-            match winning_choice.option_type {
+            let remaining_vote_power = self.total_power - self.voted_power();
+            match self.option_type(winning_choice) {
                 MultipleChoiceOptionType::Standard => {
                     if winning_choice_power > *second_choice_power + remaining_vote_power {
                         return Ok(true);
@@ -271,7 +459,6 @@ mod tests {
     use super::*;
 
     use cosmwasm_std::testing::mock_env;
-    use cwd_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
 
     fn create_proposal(
         block: &BlockInfo,
@@ -281,38 +468,27 @@ mod tests {
         is_expired: bool,
         allow_revoting: bool,
     ) -> MultipleChoiceProposal {
-        // The last option that gets added in into_checked is always the none of the above option
-        let options = vec![
-            MultipleChoiceOption {
-                description: "multiple choice option 1".to_string(),
-                msgs: None,
-            },
-            MultipleChoiceOption {
-                description: "multiple choice option 2".to_string(),
-                msgs: None,
-            },
-        ];
-
         let expiration: Expiration = if is_expired {
             Expiration::AtHeight(block.height - 5)
         } else {
             Expiration::AtHeight(block.height + 5)
         };
 
-        let mc_options = MultipleChoiceOptions { options };
         MultipleChoiceProposal {
             title: "A simple text proposal".to_string(),
             description: "A simple text proposal".to_string(),
             proposer: Addr::unchecked("CREATOR"),
             start_height: mock_env().block.height,
             expiration,
-            // The last option that gets added in into_checked is always the none of the above option
-            choices: mc_options.into_checked().unwrap().options,
             status: Status::Open,
             voting_strategy,
             total_power,
             votes,
+            ranked_votes: None,
+            approval_power: None,
             allow_revoting,
+            execution_mode: None,
+            option_executions: None,
             min_voting_period: None,
         }
     }
@@ -2,7 +2,9 @@ use crate::{proposal::MultipleChoiceProposal, state::Config};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
-use cwd_voting::multiple_choice::MultipleChoiceVote;
+use cwd_voting::multiple_choice::{
+    ApprovalVote, CheckedMultipleChoiceOption, MultipleChoiceVote, RankedChoiceVote,
+};
 
 #[cw_serde]
 pub struct ProposalListResponse {
@@ -16,6 +18,11 @@ pub struct ProposalResponse {
     pub proposal: MultipleChoiceProposal,
 }
 
+#[cw_serde]
+pub struct ProposalOptionsResponse {
+    pub options: Vec<CheckedMultipleChoiceOption>,
+}
+
 /// Information about a vote that was cast.
 #[cw_serde]
 pub struct VoteInfo {
@@ -23,8 +30,14 @@ pub struct VoteInfo {
     pub voter: Addr,
     /// Position on the vote.
     pub vote: MultipleChoiceVote,
+    /// The ranking of the options, if this is a ranked choice vote.
+    pub ranked_vote: Option<RankedChoiceVote>,
+    /// The approved options, if this is an approval vote.
+    pub approval_vote: Option<ApprovalVote>,
     /// The voting power behind the vote.
     pub power: Uint128,
+    /// The reasoning the voter published with the vote, if any.
+    pub rationale: Option<String>,
 }
 
 #[cw_serde]
@@ -1,11 +1,14 @@
-use crate::proposal::MultipleChoiceProposal;
+use crate::proposal::{LegacyMultipleChoiceProposal, MultipleChoiceProposal};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use cwd_hooks::Hooks;
 use cwd_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    multiple_choice::{
+        ApprovalVote, CheckedMultipleChoiceOption, ExecutionMode, MultipleChoiceVote,
+        RankedChoiceVote, VotingStrategy,
+    },
     pre_propose::ProposalCreationPolicy,
 };
 
@@ -43,6 +46,15 @@ pub struct Config {
     /// remain open until the DAO's reserve was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The maximum length of vote rationales in bytes. Defaults to
+    /// `DEFAULT_MAX_RATIONALE_LENGTH` if not set.
+    pub max_rationale_length: Option<u64>,
+    /// Which options of passed proposals are executed. Only the
+    /// winning option is executed if not set.
+    pub execution_mode: Option<ExecutionMode>,
+    /// The maximum number of options of proposals, not counting
+    /// "None of the above". Defaults to `MAX_NUM_CHOICES` if not set.
+    pub max_num_choices: Option<u32>,
 }
 
 // we cast a ballot with our chosen vote and a given weight
@@ -51,14 +63,89 @@ pub struct Config {
 pub struct Ballot {
     /// The amount of voting power behind the vote.
     pub power: Uint128,
-    /// The position.
+    /// The position. For ranked choice votes, the most preferred
+    /// option. For approval votes, the first approved option.
     pub vote: MultipleChoiceVote,
+    /// The ranking of the options, if this is a ranked choice vote.
+    pub ranked_vote: Option<RankedChoiceVote>,
+    /// The approved options, if this is an approval vote.
+    pub approval_vote: Option<ApprovalVote>,
+    /// The reasoning the voter published with the vote, if any.
+    pub rationale: Option<String>,
+}
+
+impl Ballot {
+    /// Adds the voting power behind the ballot to the tally of `prop`.
+    pub fn add_to(&self, prop: &mut MultipleChoiceProposal) -> StdResult<()> {
+        match self.approval_vote {
+            Some(ref approval_vote) => {
+                for option_id in approval_vote.option_ids.iter() {
+                    let vote = MultipleChoiceVote {
+                        option_id: *option_id,
+                    };
+                    prop.votes.add_vote(vote, self.power)?;
+                }
+                let approval_power = prop.approval_power.unwrap_or_default();
+                prop.approval_power = Some(approval_power.checked_add(self.power)?);
+            }
+            None => prop.votes.add_vote(self.vote, self.power)?,
+        }
+        if let Some(ref ranked_vote) = self.ranked_vote {
+            prop.ranked_votes
+                .get_or_insert_with(Default::default)
+                .add_vote(ranked_vote, self.power)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the voting power behind the ballot from the tally of
+    /// `prop`.
+    pub fn remove_from(&self, prop: &mut MultipleChoiceProposal) -> StdResult<()> {
+        match self.approval_vote {
+            Some(ref approval_vote) => {
+                for option_id in approval_vote.option_ids.iter() {
+                    let vote = MultipleChoiceVote {
+                        option_id: *option_id,
+                    };
+                    prop.votes.remove_vote(vote, self.power)?;
+                }
+                let approval_power = prop.approval_power.unwrap_or_default();
+                prop.approval_power = Some(approval_power.checked_sub(self.power)?);
+            }
+            None => prop.votes.remove_vote(self.vote, self.power)?,
+        }
+        if let (Some(ranked_votes), Some(ranked_vote)) =
+            (prop.ranked_votes.as_mut(), &self.ranked_vote)
+        {
+            ranked_votes.remove_vote(ranked_vote, self.power)?;
+        }
+        Ok(())
+    }
+
+    /// The position of the ballot as reported by vote hooks.
+    pub fn position(&self) -> String {
+        match (&self.ranked_vote, &self.approval_vote) {
+            (Some(ranked_vote), _) => ranked_vote.to_string(),
+            (_, Some(approval_vote)) => approval_vote.to_string(),
+            (None, None) => self.vote.to_string(),
+        }
+    }
 }
 
 /// The current top level config for the module.
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+/// Proposals as they are saved. Proposals are loaded with
+/// `may_load_proposal` and `may_load_proposal_mut`, as proposals
+/// created by earlier versions may still hold their options inline.
 pub const PROPOSALS: Map<u64, MultipleChoiceProposal> = Map::new("proposals");
+/// Proposals as stored before their options were moved to
+/// `PROPOSAL_OPTIONS`, which also reads the proposals stored since.
+pub const LEGACY_PROPOSALS: Map<u64, LegacyMultipleChoiceProposal> = Map::new("proposals");
+/// The options of each proposal, keyed by proposal ID and option
+/// index.
+pub const PROPOSAL_OPTIONS: Map<(u64, u32), CheckedMultipleChoiceOption> =
+    Map::new("proposal_options");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
@@ -67,3 +154,39 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+
+/// Loads proposal `id` along with its inline options, if it was
+/// created by an earlier version and has not been updated since.
+pub fn may_load_proposal(
+    storage: &dyn Storage,
+    id: u64,
+) -> StdResult<
+    Option<(
+        MultipleChoiceProposal,
+        Option<Vec<CheckedMultipleChoiceOption>>,
+    )>,
+> {
+    Ok(LEGACY_PROPOSALS
+        .may_load(storage, id)?
+        .map(LegacyMultipleChoiceProposal::into_proposal))
+}
+
+/// Loads proposal `id` to update it. The inline options of proposals
+/// created by earlier versions are moved to `PROPOSAL_OPTIONS` first,
+/// so that the proposal may be saved to `PROPOSALS`.
+pub fn may_load_proposal_mut(
+    storage: &mut dyn Storage,
+    id: u64,
+) -> StdResult<Option<MultipleChoiceProposal>> {
+    match may_load_proposal(storage, id)? {
+        Some((proposal, Some(options))) => {
+            for option in options {
+                PROPOSAL_OPTIONS.save(storage, (id, option.index), &option)?;
+            }
+            PROPOSALS.save(storage, id, &proposal)?;
+            Ok(Some(proposal))
+        }
+        Some((proposal, None)) => Ok(Some(proposal)),
+        None => Ok(None),
+    }
+}
@@ -3,12 +3,14 @@ use cw20::Cw20Coin;
 use cw_denom::CheckedDenom;
 use cw_multi_test::{BankSudo, BasicApp, Executor};
 use cwd_core::state::ProposalModule;
-use cwd_testing::ShouldExecute;
+use cwd_testing::{ShouldExecute, TestRankedChoiceVote, RANKED_CHOICE_OPTIONS};
 use cwd_voting::{
     deposit::{CheckedDepositInfo, UncheckedDepositInfo},
     multiple_choice::{
-        MultipleChoiceOption, MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy,
+        MultipleChoiceOption, MultipleChoiceOptions, MultipleChoiceVote, RankedChoiceVote,
+        VotingStrategy,
     },
+    pre_propose::PreProposeInfo,
     status::Status,
     threshold::PercentageThreshold,
 };
@@ -20,8 +22,9 @@ use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     query::{ProposalResponse, VoteInfo, VoteResponse},
     testing::{
+        execute::make_proposal,
         instantiate::instantiate_with_native_bonded_balances_governance,
-        queries::query_deposit_config_and_pre_propose_module,
+        queries::{query_deposit_config_and_pre_propose_module, query_multiple_proposal_module},
         tests::{
             get_pre_propose_info, no_init, proposal_multiple_contract, TestMultipleChoiceVote,
         },
@@ -47,6 +50,127 @@ pub fn do_votes_bonded(
     );
 }
 
+// Creates a ranked choice proposal with three options and "None of
+// the above", casts the provided ranked votes on it and checks the
+// status of the proposal once it has expired.
+pub fn do_ranked_votes_bonded(
+    votes: Vec<TestRankedChoiceVote>,
+    quorum: PercentageThreshold,
+    expected_status: Status,
+    total_supply: Option<Uint128>,
+) {
+    let mut app = cw_multi_test::custom_app::<NeutronMsg, Empty, _>(no_init);
+
+    let mut initial_balances = votes
+        .iter()
+        .map(|TestRankedChoiceVote { voter, weight, .. }| Cw20Coin {
+            address: voter.to_string(),
+            amount: *weight,
+        })
+        .collect::<Vec<Cw20Coin>>();
+    let initial_balances_supply = votes.iter().fold(Uint128::zero(), |p, n| p + n.weight);
+    if let Some(total_supply) = total_supply {
+        initial_balances.push(Cw20Coin {
+            address: "filler".to_string(),
+            amount: total_supply - initial_balances_supply,
+        })
+    }
+
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: cw_utils::Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::RankedChoice { quorum },
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(initial_balances),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = (0..RANKED_CHOICE_OPTIONS - 1)
+        .map(|idx| MultipleChoiceOption {
+            description: format!("multiple choice option {}", idx + 1),
+            msgs: None,
+        })
+        .collect();
+    let proposer = match votes.first() {
+        Some(vote) => vote.voter.clone(),
+        None => panic!("do_ranked_votes_bonded must have at least one vote."),
+    };
+    let proposal_id = make_proposal(
+        &mut app,
+        &govmod,
+        &proposer,
+        MultipleChoiceOptions { options },
+    );
+
+    for TestRankedChoiceVote {
+        voter,
+        ranking,
+        weight,
+        should_execute,
+    } in votes
+    {
+        let vote = RankedChoiceVote { ranking };
+        let res = app.execute_contract(
+            Addr::unchecked(&voter),
+            govmod.clone(),
+            &ExecuteMsg::RankedVote {
+                proposal_id,
+                vote: vote.clone(),
+                rationale: None,
+            },
+            &[],
+        );
+        match should_execute {
+            ShouldExecute::Yes => {
+                res.unwrap();
+                let resp: VoteResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        govmod.clone(),
+                        &QueryMsg::GetVote {
+                            proposal_id,
+                            voter: voter.clone(),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(
+                    resp.vote,
+                    Some(VoteInfo {
+                        voter: Addr::unchecked(&voter),
+                        vote: vote.first_choice().unwrap(),
+                        ranked_vote: Some(vote),
+                        approval_vote: None,
+                        power: weight,
+                        rationale: None,
+                    })
+                );
+            }
+            ShouldExecute::No => {
+                res.unwrap_err();
+            }
+            ShouldExecute::Meh => (),
+        }
+    }
+
+    app.update_block(|block| block.height += 100);
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod, &QueryMsg::Proposal { proposal_id })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, expected_status);
+}
+
 // Creates multiple choice proposal with provided config and executes provided votes against it.
 fn do_test_votes<F>(
     votes: Vec<TestMultipleChoiceVote>,
@@ -94,6 +218,9 @@ where
         allow_revoting: false,
         voting_strategy,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info,
     };
 
@@ -196,6 +323,7 @@ where
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: position,
+                rationale: None,
             },
             &[],
         );
@@ -220,6 +348,8 @@ where
                     vote: Some(VoteInfo {
                         voter: Addr::unchecked(&voter),
                         vote: position,
+                        ranked_vote: None,
+                        approval_vote: None,
                         power: match deposit_config.deposit_info {
                             Some(CheckedDepositInfo {
                                 amount,
@@ -236,6 +366,7 @@ where
                             // expected voting power.
                             _ => weight,
                         },
+                        rationale: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -747,3 +878,13 @@ fn test_pass_exact_quorum() {
 fn fuzz_votes_cw4_weights() {
     fuzz_voting(do_votes_bonded)
 }
+
+#[test]
+fn test_ranked_choice_runoff() {
+    cwd_testing::test_ranked_choice_runoff(do_ranked_votes_bonded)
+}
+
+#[test]
+fn fuzz_ranked_choice_votes() {
+    cwd_testing::fuzz_ranked_choice_voting(do_ranked_votes_bonded)
+}
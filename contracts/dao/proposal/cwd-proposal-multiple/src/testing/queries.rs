@@ -8,7 +8,7 @@ use neutron_sdk::bindings::msg::NeutronMsg;
 
 use crate::{
     msg::QueryMsg,
-    query::{ProposalListResponse, ProposalResponse},
+    query::{ProposalListResponse, ProposalOptionsResponse, ProposalResponse},
     state::Config,
 };
 
@@ -93,6 +93,25 @@ pub fn query_list_proposals(
         .unwrap()
 }
 
+pub fn query_list_proposal_options(
+    app: &BasicApp<NeutronMsg>,
+    proposal_multiple: &Addr,
+    proposal_id: u64,
+    start_after: Option<u32>,
+    limit: Option<u64>,
+) -> ProposalOptionsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_multiple,
+            &QueryMsg::ListProposalOptions {
+                proposal_id,
+                start_after,
+                limit,
+            },
+        )
+        .unwrap()
+}
+
 pub fn query_proposal_hooks(app: &BasicApp<NeutronMsg>, proposal_multiple: &Addr) -> HooksResponse {
     app.wrap()
         .query_wasm_smart(proposal_multiple, &QueryMsg::ProposalHooks {})
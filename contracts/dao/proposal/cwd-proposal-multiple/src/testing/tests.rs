@@ -1,6 +1,8 @@
 use cosmwasm_std::{
-    coins, to_binary, Addr, Api, Coin, CosmosMsg, Decimal, Empty, Storage, Timestamp, Uint128,
-    WasmMsg,
+    coins, from_binary,
+    testing::{mock_dependencies, mock_env},
+    to_binary, Addr, Api, Coin, CosmosMsg, Decimal, Deps, Empty, Order, StdResult, Storage,
+    Timestamp, Uint128, WasmMsg,
 };
 use cw20::Cw20Coin;
 use cw_denom::{CheckedDenom, UncheckedDenom};
@@ -15,9 +17,9 @@ use cwd_interface::{Admin, ModuleInstantiateInfo};
 use cwd_voting::{
     deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
-        MAX_NUM_CHOICES,
+        ApprovalVote, CheckedMultipleChoiceOption, ExecutionMode, MultipleChoiceOption,
+        MultipleChoiceOptionType, MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes,
+        RankedChoiceVote, VotingStrategy, MAX_NUM_CHOICES, MAX_NUM_CHOICES_LIMIT,
     },
     pre_propose::PreProposeInfo,
     status::Status,
@@ -27,17 +29,25 @@ use neutron_sdk::bindings::msg::NeutronMsg;
 use std::panic;
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::MultipleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse},
-    state::Config,
+    contract::{migrate, query},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    proposal::{
+        LegacyMultipleChoiceProposal, MultipleChoiceProposal, OptionExecution,
+        OptionExecutionStatus,
+    },
+    query::{ProposalListResponse, ProposalOptionsResponse, ProposalResponse},
+    state::{
+        may_load_proposal, may_load_proposal_mut, Config, LEGACY_PROPOSALS, PROPOSALS,
+        PROPOSAL_OPTIONS,
+    },
     testing::{
         execute::make_proposal,
         instantiate::instantiate_with_native_bonded_balances_governance,
         queries::{
             query_balance_native, query_deposit_config_and_pre_propose_module,
-            query_list_proposals, query_list_proposals_reverse, query_multiple_proposal_module,
-            query_proposal, query_proposal_config, query_proposal_hooks, query_vote_hooks,
+            query_list_proposal_options, query_list_proposals, query_list_proposals_reverse,
+            query_multiple_proposal_module, query_proposal, query_proposal_config,
+            query_proposal_hooks, query_vote_hooks,
         },
     },
     ContractError,
@@ -125,6 +135,9 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -141,6 +154,9 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
     };
     assert_eq!(config, expected);
 
@@ -163,26 +179,35 @@ fn test_propose() {
     let created: ProposalResponse = query_proposal(&app, &govmod, 1);
 
     let current_block = app.block_info();
-    let checked_options = mc_options.into_checked().unwrap();
     let expected = MultipleChoiceProposal {
         title: "title".to_string(),
         description: "description".to_string(),
         proposer: Addr::unchecked(CREATOR_ADDR),
         start_height: current_block.height,
         expiration: max_voting_period.after(&current_block),
-        choices: checked_options.options,
         status: Status::Open,
         voting_strategy,
         total_power: Uint128::new(100_000_000),
         votes: MultipleChoiceVotes {
             vote_weights: vec![Uint128::zero(); 3],
         },
+        ranked_votes: None,
+        approval_power: None,
+        execution_mode: None,
+        option_executions: None,
         allow_revoting: false,
         min_voting_period: None,
     };
 
     assert_eq!(created.proposal, expected);
     assert_eq!(created.id, 1u64);
+
+    // The options are stored apart from the proposal.
+    let options = query_list_proposal_options(&app, &govmod, 1, None, None).options;
+    assert_eq!(
+        options,
+        mc_options.into_checked(MAX_NUM_CHOICES).unwrap().options
+    );
 }
 
 #[test]
@@ -198,6 +223,9 @@ fn test_propose_wrong_num_choices() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -213,6 +241,9 @@ fn test_propose_wrong_num_choices() {
     let expected = Config {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -275,6 +306,9 @@ fn test_proposal_count_initialized_to_zero() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         only_members_execute: true,
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
@@ -311,6 +345,9 @@ fn test_no_early_pass_with_min_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -372,6 +409,7 @@ fn test_no_early_pass_with_min_duration() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -400,6 +438,9 @@ fn test_propose_with_messages() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         only_members_execute: true,
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
@@ -435,6 +476,9 @@ fn test_propose_with_messages() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period: cw_utils::Duration::Height(20),
         only_members_execute: false,
         allow_revoting: false,
@@ -479,6 +523,7 @@ fn test_propose_with_messages() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -518,6 +563,9 @@ fn test_min_duration_units_missmatch() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
     instantiate_with_native_bonded_balances_governance(
@@ -550,6 +598,9 @@ fn test_min_duration_larger_than_proposal_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
     instantiate_with_native_bonded_balances_governance(
@@ -581,6 +632,9 @@ fn test_min_duration_same_as_proposal_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -642,6 +696,7 @@ fn test_min_duration_same_as_proposal_duration() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -658,6 +713,7 @@ fn test_min_duration_same_as_proposal_duration() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 2 },
+            rationale: None,
         },
         &[],
     )
@@ -685,6 +741,9 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -739,6 +798,9 @@ fn test_native_proposal_deposit() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: get_pre_propose_info(
             &mut app,
             Some(UncheckedDepositInfo {
@@ -836,6 +898,7 @@ fn test_native_proposal_deposit() {
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: MultipleChoiceVote { option_id: 1 },
+                rationale: None,
             },
             &[],
         );
@@ -873,6 +936,9 @@ fn test_cant_propose_zero_power() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1001,6 +1067,9 @@ fn test_open_proposal_submission() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: get_pre_propose_info(&mut app, None, true),
     };
     let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
@@ -1039,7 +1108,22 @@ fn test_open_proposal_submission() {
         voting_strategy: VotingStrategy::SingleChoice {
             quorum: PercentageThreshold::Percent(Decimal::percent(100)),
         },
-        choices: vec![
+        votes: MultipleChoiceVotes {
+            vote_weights: vec![Uint128::zero(); 3],
+        },
+        ranked_votes: None,
+        approval_power: None,
+        execution_mode: None,
+        option_executions: None,
+    };
+
+    assert_eq!(created.proposal, expected);
+    assert_eq!(created.id, 1u64);
+
+    let options = query_list_proposal_options(&app, &govmod, 1, None, None).options;
+    assert_eq!(
+        options,
+        vec![
             CheckedMultipleChoiceOption {
                 description: "multiple choice option 1".to_string(),
                 msgs: None,
@@ -1061,14 +1145,8 @@ fn test_open_proposal_submission() {
                 vote_count: Uint128::zero(),
                 index: 2,
             },
-        ],
-        votes: MultipleChoiceVotes {
-            vote_weights: vec![Uint128::zero(); 3],
-        },
-    };
-
-    assert_eq!(created.proposal, expected);
-    assert_eq!(created.id, 1u64);
+        ]
+    );
 }
 
 #[test]
@@ -1081,6 +1159,9 @@ fn test_execute_expired_proposal() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1144,6 +1225,7 @@ fn test_execute_expired_proposal() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1202,6 +1284,9 @@ fn test_query_list_proposals() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1266,7 +1351,6 @@ fn test_query_list_proposals() {
     proposals_backward.proposals.reverse();
 
     assert_eq!(proposals_forward.proposals, proposals_backward.proposals);
-    let checked_options = mc_options.into_checked().unwrap();
     let current_block = app.block_info();
     let expected = ProposalResponse {
         id: 1,
@@ -1276,13 +1360,16 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: current_block.height,
             expiration: max_voting_period.after(&current_block),
-            choices: checked_options.options.clone(),
             status: Status::Open,
             voting_strategy: voting_strategy.clone(),
             total_power: Uint128::new(100),
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
+            ranked_votes: None,
+            approval_power: None,
+            execution_mode: None,
+            option_executions: None,
             allow_revoting: false,
             min_voting_period: None,
         },
@@ -1304,13 +1391,16 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: current_block.height,
             expiration: max_voting_period.after(&current_block),
-            choices: checked_options.options,
             status: Status::Open,
             voting_strategy,
             total_power: Uint128::new(100),
             votes: MultipleChoiceVotes {
                 vote_weights: vec![Uint128::zero(); 3],
             },
+            ranked_votes: None,
+            approval_power: None,
+            execution_mode: None,
+            option_executions: None,
             allow_revoting: false,
             min_voting_period: None,
         },
@@ -1333,6 +1423,9 @@ fn test_hooks() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1464,6 +1557,9 @@ fn test_revoting() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            max_rationale_length: None,
+            execution_mode: None,
+            max_num_choices: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -1513,6 +1609,7 @@ fn test_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1525,6 +1622,7 @@ fn test_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 1 },
+            rationale: None,
         },
         &[],
     )
@@ -1556,6 +1654,7 @@ fn test_revoting() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1591,6 +1690,9 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            max_rationale_length: None,
+            execution_mode: None,
+            max_num_choices: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -1647,6 +1749,9 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            max_rationale_length: None,
+            execution_mode: None,
+            max_num_choices: None,
         },
         &[],
     )
@@ -1662,6 +1767,7 @@ fn test_allow_revoting_config_changes() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1672,6 +1778,7 @@ fn test_allow_revoting_config_changes() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 1 },
+            rationale: None,
         },
         &[],
     )
@@ -1697,6 +1804,7 @@ fn test_allow_revoting_config_changes() {
         &ExecuteMsg::Vote {
             proposal_id: 2,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1709,6 +1817,7 @@ fn test_allow_revoting_config_changes() {
             &ExecuteMsg::Vote {
                 proposal_id: 2,
                 vote: MultipleChoiceVote { option_id: 1 },
+                rationale: None,
             },
             &[],
         )
@@ -1736,6 +1845,9 @@ fn test_revoting_same_vote_twice() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            max_rationale_length: None,
+            execution_mode: None,
+            max_num_choices: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -1785,6 +1897,7 @@ fn test_revoting_same_vote_twice() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1798,6 +1911,7 @@ fn test_revoting_same_vote_twice() {
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: MultipleChoiceVote { option_id: 0 },
+                rationale: None,
             },
             &[],
         )
@@ -1826,6 +1940,9 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            max_rationale_length: None,
+            execution_mode: None,
+            max_num_choices: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         },
         Some(vec![
@@ -1875,6 +1992,7 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -1887,6 +2005,7 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 1 },
+            rationale: None,
         },
         &[],
     )
@@ -1918,6 +2037,7 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: MultipleChoiceVote { option_id: 99 },
+                rationale: None,
             },
             &[],
         )
@@ -1951,6 +2071,9 @@ fn test_close_failed_proposal() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
     };
 
@@ -2001,6 +2124,7 @@ fn test_close_failed_proposal() {
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -2047,6 +2171,9 @@ fn test_close_failed_proposal() {
                                     allow_revoting: false,
                                     dao: original.dao.to_string(),
                                     close_proposal_on_execution_failure: false,
+                                    max_rationale_length: None,
+                                    execution_mode: None,
+                                    max_num_choices: None,
                                 })
                                 .unwrap(),
                                 funds: vec![],
@@ -2072,6 +2199,7 @@ fn test_close_failed_proposal() {
             &ExecuteMsg::Vote {
                 proposal_id: 2,
                 vote: MultipleChoiceVote { option_id: 0 },
+                rationale: None,
             },
             &[],
         )
@@ -2108,6 +2236,7 @@ fn test_close_failed_proposal() {
         &ExecuteMsg::Vote {
             proposal_id: 3,
             vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
         },
         &[],
     )
@@ -2128,3 +2257,831 @@ fn test_close_failed_proposal() {
     // not reverted
     assert_eq!(updated.proposal.status, Status::Passed);
 }
+
+#[test]
+fn test_ranked_choice_vote() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: true,
+        voting_strategy: VotingStrategy::RankedChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "green".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "red".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+
+    let send = |amount: u128| {
+        Some(vec![CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(amount, "ujuno"),
+        })])
+    };
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: send(10),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: send(20),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 3".to_string(),
+            msgs: send(30),
+        },
+    ];
+    let proposal_id = make_proposal(&mut app, &govmod, "blue", MultipleChoiceOptions { options });
+
+    let ranked_vote = |app: &mut BasicApp<NeutronMsg>, voter: &str, ranking: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::RankedVote {
+                proposal_id,
+                vote: RankedChoiceVote { ranking },
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    // Empty rankings, unknown options, options ranked twice and
+    // rankings longer than `MAX_RANKING_LENGTH` are not allowed.
+    for ranking in [vec![], vec![0, 4], vec![1, 1], vec![1, 0, 2]] {
+        let err: ContractError = ranked_vote(&mut app, "blue", ranking)
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(err, ContractError::InvalidVote {}));
+    }
+
+    // A single choice vote ranks only the selected option.
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: MultipleChoiceVote { option_id: 0 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    ranked_vote(&mut app, "green", vec![2, 0]).unwrap();
+    ranked_vote(&mut app, "red", vec![2, 1]).unwrap();
+    // Revoting replaces the previous ranking.
+    ranked_vote(&mut app, "green", vec![1, 2]).unwrap();
+    let err: ContractError = ranked_vote(&mut app, "green", vec![1, 2])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(
+        proposal.votes.vote_weights,
+        vec![
+            Uint128::new(40),
+            Uint128::new(35),
+            Uint128::new(25),
+            Uint128::zero()
+        ]
+    );
+
+    // Option 3 is eliminated and red's vote moves to option 2, which
+    // wins over option 1 with 60 to 40.
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod,
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance_native(&app, "recipient", "ujuno"),
+        Uint128::new(20)
+    );
+}
+
+#[test]
+fn test_ranked_vote_on_single_choice_proposal() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let proposal_id = make_proposal(
+        &mut app,
+        &govmod,
+        CREATOR_ADDR,
+        MultipleChoiceOptions { options },
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod,
+            &ExecuteMsg::RankedVote {
+                proposal_id,
+                vote: RankedChoiceVote {
+                    ranking: vec![0, 1],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RankedVoteNotAllowed {}));
+}
+
+#[test]
+fn test_approval_vote() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: true,
+        voting_strategy: VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 2,
+        },
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "green".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "red".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+
+    let send = |amount: u128| {
+        Some(vec![CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: coins(amount, "ujuno"),
+        })])
+    };
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: send(10),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: send(20),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 3".to_string(),
+            msgs: send(30),
+        },
+    ];
+    let proposal_id = make_proposal(&mut app, &govmod, "blue", MultipleChoiceOptions { options });
+
+    let approval_vote = |app: &mut BasicApp<NeutronMsg>, voter: &str, option_ids: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::ApprovalVote {
+                proposal_id,
+                vote: ApprovalVote { option_ids },
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    // Empty approvals, unknown options, options approved twice, more
+    // than `max_selections` options and "None of the above" with other
+    // options are not allowed.
+    for option_ids in [vec![], vec![0, 4], vec![1, 1], vec![0, 1, 2], vec![0, 3]] {
+        let err: ContractError = approval_vote(&mut app, "blue", option_ids)
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(err, ContractError::InvalidVote {}));
+    }
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::RankedVote {
+                proposal_id,
+                vote: RankedChoiceVote {
+                    ranking: vec![0, 1],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RankedVoteNotAllowed {}));
+
+    approval_vote(&mut app, "blue", vec![0, 1]).unwrap();
+    approval_vote(&mut app, "green", vec![1, 2]).unwrap();
+    // A single choice vote approves only the selected option.
+    app.execute_contract(
+        Addr::unchecked("red"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: MultipleChoiceVote { option_id: 2 },
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+    // Revoting replaces the previous approvals.
+    approval_vote(&mut app, "red", vec![0, 2]).unwrap();
+
+    // Every approved option receives the full power of the voter.
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(
+        proposal.votes.vote_weights,
+        vec![
+            Uint128::new(65),
+            Uint128::new(75),
+            Uint128::new(60),
+            Uint128::zero()
+        ]
+    );
+    assert_eq!(proposal.approval_power, Some(Uint128::new(100)));
+
+    // Option 2 has the most approvals.
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod,
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_balance_native(&app, "recipient", "ujuno"),
+        Uint128::new(20)
+    );
+}
+
+#[test]
+fn test_approval_vote_on_single_choice_proposal() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: None,
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: None,
+        },
+    ];
+    let proposal_id = make_proposal(
+        &mut app,
+        &govmod,
+        CREATOR_ADDR,
+        MultipleChoiceOptions { options },
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod,
+            &ExecuteMsg::ApprovalVote {
+                proposal_id,
+                vote: ApprovalVote {
+                    option_ids: vec![0, 1],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ApprovalVoteNotAllowed {}));
+}
+
+/// Instantiates a DAO where "blue", "green" and "red" hold 40, 35 and
+/// 25 voting power and a treasury of 100 ujuno, and creates a proposal
+/// whose options send `amounts` to "recipient".
+fn make_budget_proposal(
+    voting_strategy: VotingStrategy,
+    execution_mode: ExecutionMode,
+    max_num_choices: None,
+    amounts: [u128; 3],
+) -> (BasicApp<NeutronMsg>, Addr, u64) {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy,
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: Some(execution_mode),
+        max_num_choices: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "green".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "red".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+
+    let options = amounts
+        .iter()
+        .enumerate()
+        .map(|(idx, amount)| MultipleChoiceOption {
+            description: format!("multiple choice option {}", idx + 1),
+            msgs: Some(vec![CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: coins(*amount, "ujuno"),
+            })]),
+        })
+        .collect();
+    let proposal_id = make_proposal(&mut app, &govmod, "blue", MultipleChoiceOptions { options });
+    (app, govmod, proposal_id)
+}
+
+#[test]
+fn test_execute_top_n_options() {
+    let (mut app, govmod, proposal_id) = make_budget_proposal(
+        VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 2,
+        },
+        ExecutionMode::TopN { n: 2 },
+        [10, 20, 30],
+    );
+    for (voter, option_ids) in [
+        ("blue", vec![0, 1]),
+        ("green", vec![1, 2]),
+        ("red", vec![2]),
+    ] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::ApprovalVote {
+                proposal_id,
+                vote: ApprovalVote { option_ids },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // The winning options are only known once voting has ended.
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Open);
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+
+    // Option 2 with 75 approvals and option 3 with 60 approvals are
+    // executed, in that order.
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Executed);
+    assert_eq!(
+        proposal.option_executions,
+        Some(vec![
+            OptionExecution {
+                option_id: 1,
+                status: OptionExecutionStatus::Executed,
+            },
+            OptionExecution {
+                option_id: 2,
+                status: OptionExecutionStatus::Executed,
+            },
+        ])
+    );
+    assert_eq!(
+        query_balance_native(&app, "recipient", "ujuno"),
+        Uint128::new(50)
+    );
+}
+
+#[test]
+fn test_execute_options_above_threshold() {
+    let (mut app, govmod, proposal_id) = make_budget_proposal(
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        ExecutionMode::Threshold {
+            percent: Decimal::percent(30),
+        },
+        [10, 1000, 30],
+    );
+    for (voter, option_id) in [("blue", 1), ("green", 0), ("red", 2)] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote { option_id },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.update_block(|b| b.height += 10);
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+
+    // Option 3 with 25% of the votes is not executed. Option 2 can not
+    // be paid for by the treasury and fails without reverting option 1.
+    let proposal = query_proposal(&app, &govmod, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::ExecutionFailed);
+    assert_eq!(
+        proposal.option_executions,
+        Some(vec![
+            OptionExecution {
+                option_id: 1,
+                status: OptionExecutionStatus::ExecutionFailed,
+            },
+            OptionExecution {
+                option_id: 0,
+                status: OptionExecutionStatus::Executed,
+            },
+        ])
+    );
+    assert_eq!(
+        query_balance_native(&app, "recipient", "ujuno"),
+        Uint128::new(10)
+    );
+}
+
+#[test]
+fn test_ranked_choice_executes_winner_only() {
+    let (mut app, govmod, _) = make_budget_proposal(
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        ExecutionMode::TopN { n: 2 },
+        [10, 20, 30],
+    );
+    let dao = query_proposal_config(&app, &govmod).dao;
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod,
+            &ExecuteMsg::UpdateConfig {
+                min_voting_period: None,
+                max_voting_period: Duration::Height(6),
+                only_members_execute: false,
+                allow_revoting: false,
+                dao: dao.to_string(),
+                voting_strategy: VotingStrategy::RankedChoice {
+                    quorum: PercentageThreshold::Majority {},
+                },
+                close_proposal_on_execution_failure: true,
+                max_rationale_length: None,
+                execution_mode: Some(ExecutionMode::TopN { n: 2 }),
+                max_num_choices: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::UnsupportedExecutionMode {}));
+}
+
+#[test]
+fn test_max_num_choices() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let voting_strategy = VotingStrategy::SingleChoice {
+        quorum: PercentageThreshold::Majority {},
+    };
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(6),
+        only_members_execute: false,
+        allow_revoting: false,
+        voting_strategy: voting_strategy.clone(),
+        close_proposal_on_execution_failure: true,
+        max_rationale_length: None,
+        execution_mode: None,
+        max_num_choices: Some(MAX_NUM_CHOICES + 2),
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+    };
+    let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = |num_choices: u32| MultipleChoiceOptions {
+        options: (0..num_choices)
+            .map(|idx| MultipleChoiceOption {
+                description: format!("multiple choice option {}", idx + 1),
+                msgs: None,
+            })
+            .collect(),
+    };
+    let propose = |app: &mut BasicApp<NeutronMsg>, num_choices: u32| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "A simple text proposal".to_string(),
+                choices: options(num_choices),
+                proposer: None,
+            },
+            &[],
+        )
+    };
+    propose(&mut app, MAX_NUM_CHOICES + 3).unwrap_err();
+    propose(&mut app, MAX_NUM_CHOICES + 2).unwrap();
+
+    // Options are paged through in index order, "None of the above"
+    // last.
+    let page = query_list_proposal_options(&app, &govmod, 1, Some(4), Some(5)).options;
+    assert_eq!(
+        page.iter().map(|option| option.index).collect::<Vec<_>>(),
+        vec![5, 6, 7, 8, 9]
+    );
+    let page = query_list_proposal_options(&app, &govmod, 1, Some(9), None).options;
+    assert_eq!(
+        page.iter().map(|option| option.index).collect::<Vec<_>>(),
+        vec![10, 11, 12]
+    );
+    assert_eq!(page[2].option_type, MultipleChoiceOptionType::None);
+
+    for max_num_choices in [1, MAX_NUM_CHOICES_LIMIT + 1] {
+        let err: ContractError = app
+            .execute_contract(
+                core_addr.clone(),
+                govmod.clone(),
+                &ExecuteMsg::UpdateConfig {
+                    min_voting_period: None,
+                    max_voting_period: Duration::Height(6),
+                    only_members_execute: false,
+                    allow_revoting: false,
+                    dao: core_addr.to_string(),
+                    voting_strategy: voting_strategy.clone(),
+                    close_proposal_on_execution_failure: true,
+                    max_rationale_length: None,
+                    execution_mode: None,
+                    max_num_choices: Some(max_num_choices),
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(
+            err,
+            ContractError::InvalidMaxNumChoices {
+                max: MAX_NUM_CHOICES_LIMIT
+            }
+        ));
+    }
+
+    // Ranked choice proposals are held to `MAX_NUM_CHOICES` options.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            govmod,
+            &ExecuteMsg::UpdateConfig {
+                min_voting_period: None,
+                max_voting_period: Duration::Height(6),
+                only_members_execute: false,
+                allow_revoting: false,
+                dao: core_addr.to_string(),
+                voting_strategy: VotingStrategy::RankedChoice {
+                    quorum: PercentageThreshold::Majority {},
+                },
+                close_proposal_on_execution_failure: true,
+                max_rationale_length: None,
+                execution_mode: None,
+                max_num_choices: Some(MAX_NUM_CHOICES + 1),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::InvalidMaxNumChoices {
+            max: MAX_NUM_CHOICES
+        }
+    ));
+}
+
+#[test]
+fn test_migrate_inline_options() {
+    let mut deps = mock_dependencies();
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: None,
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: None,
+            },
+        ],
+    }
+    .into_checked(MAX_NUM_CHOICES)
+    .unwrap()
+    .options;
+    let legacy_proposal = LegacyMultipleChoiceProposal {
+        title: "title".to_string(),
+        description: "description".to_string(),
+        proposer: Addr::unchecked(CREATOR_ADDR),
+        start_height: 1,
+        min_voting_period: None,
+        expiration: cw_utils::Expiration::AtHeight(10),
+        choices: Some(options.clone()),
+        status: Status::Open,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        total_power: Uint128::new(100),
+        votes: MultipleChoiceVotes::zero(3),
+        ranked_votes: None,
+        approval_power: None,
+        allow_revoting: false,
+        execution_mode: None,
+        option_executions: None,
+    };
+    LEGACY_PROPOSALS
+        .save(deps.as_mut().storage, 1, &legacy_proposal)
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+
+    // The migration leaves the proposal as it is, and queries read
+    // the inline options.
+    let (expected, _) = legacy_proposal.into_proposal();
+    let query_options =
+        |deps: Deps, start_after: Option<u32>| -> Vec<CheckedMultipleChoiceOption> {
+            let resp: ProposalOptionsResponse = from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::ListProposalOptions {
+                        proposal_id: 1,
+                        start_after,
+                        limit: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            resp.options
+        };
+    PROPOSALS.load(deps.as_ref().storage, 1).unwrap_err();
+    assert_eq!(
+        may_load_proposal(deps.as_ref().storage, 1).unwrap(),
+        Some((expected.clone(), Some(options.clone())))
+    );
+    assert_eq!(query_options(deps.as_ref(), None), options);
+    assert_eq!(query_options(deps.as_ref(), Some(1)), options[2..]);
+
+    // Loading the proposal to update it moves its options.
+    assert_eq!(
+        may_load_proposal_mut(deps.as_mut().storage, 1).unwrap(),
+        Some(expected.clone())
+    );
+    assert_eq!(PROPOSALS.load(deps.as_ref().storage, 1).unwrap(), expected);
+    let migrated_options = PROPOSAL_OPTIONS
+        .prefix(1)
+        .range(deps.as_ref().storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, option)| option))
+        .collect::<StdResult<Vec<_>>>()
+        .unwrap();
+    assert_eq!(migrated_options, options);
+    assert_eq!(query_options(deps.as_ref(), None), options);
+    assert_eq!(
+        may_load_proposal(deps.as_ref().storage, 1).unwrap(),
+        Some((expected, None))
+    );
+}
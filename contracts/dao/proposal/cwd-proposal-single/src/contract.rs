@@ -2,36 +2,46 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdResult, Storage, SubMsg, WasmMsg,
+    StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
 use cwd_hooks::Hooks;
 use cwd_pre_propose_single::contract::ExecuteMsg as PreProposeMsg;
 use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use cwd_vote_hooks::new_vote_hooks;
 use cwd_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
-use cwd_voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use cwd_voting::proposal::{validate_rationale, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
 use cwd_voting::reply::{
     failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
 };
 use cwd_voting::status::Status;
 use cwd_voting::threshold::Threshold;
-use cwd_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
+use cwd_voting::vault::get_delegated_power;
+use cwd_voting::voting::{
+    get_total_power, get_voting_power, validate_voting_period, Vote, Votes, WeightedVote,
+};
 use neutron_sdk::bindings::msg::NeutronMsg;
 
 use crate::msg::MigrateMsg;
 use crate::proposal::SingleChoiceProposal;
-use crate::state::{Config, CREATION_POLICY};
+use crate::state::{Config, VetoConfig, CREATION_POLICY};
 
 use crate::{
     error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::advance_proposal_id,
     query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    query::{
+        DelegationOverrideInfo, DelegationOverridesResponse, ProposalAmendmentsResponse,
+        ProposalResponse, VoteInfo, VoteListResponse, VoteResponse,
+    },
+    state::{
+        Ballot, DelegationOverride, ProposalAmendment, BALLOTS, CONFIG, DELEGATION_OVERRIDES,
+        OVERRIDDEN_POWER, PROPOSALS, PROPOSAL_AMENDMENTS, PROPOSAL_COUNT, PROPOSAL_HOOKS,
+        VOTE_HOOKS,
+    },
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-subdao-proposal-single";
@@ -57,6 +67,13 @@ pub fn instantiate(
         .pre_propose_info
         .into_initial_policy_and_messages(dao.clone())?;
 
+    let execution_delay = validate_execution_delay(msg.execution_delay, max_voting_period)?;
+    let veto = validate_veto_config(deps.as_ref(), msg.veto, max_voting_period)?;
+    let delegation_vault = msg
+        .delegation_vault
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
     let config = Config {
         threshold: msg.threshold,
         max_voting_period,
@@ -64,6 +81,10 @@ pub fn instantiate(
         dao: dao.clone(),
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        execution_delay,
+        veto,
+        max_rationale_length: msg.max_rationale_length,
+        delegation_vault,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -92,9 +113,25 @@ pub fn execute(
             msgs,
             proposer,
         } => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Amend {
+            proposal_id,
+            title,
+            description,
+            msgs,
+        } => execute_amend(deps, env, info, proposal_id, title, description, msgs),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, None, rationale),
+        ExecuteMsg::WeightedVote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_weighted_vote(deps, env, info, proposal_id, vote, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -102,6 +139,10 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            execution_delay,
+            veto,
+            max_rationale_length,
+            delegation_vault,
         } => execute_update_config(
             deps,
             info,
@@ -111,6 +152,10 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            execution_delay,
+            veto,
+            max_rationale_length,
+            delegation_vault,
         ),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
@@ -159,6 +204,11 @@ pub fn execute_propose(
     };
 
     let expiration = config.max_voting_period.after(&env.block);
+    let timelock_expiration = get_timelock_expiration(&config, expiration)?;
+    let execution_delay_expiration = config
+        .execution_delay
+        .map(|delay| expiration + delay)
+        .transpose()?;
 
     let total_power = get_total_power(deps.as_ref(), config.dao, Some(env.block.height))?;
 
@@ -177,6 +227,9 @@ pub fn execute_propose(
             status: Status::Open,
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
+            timelock_expiration,
+            execution_delay_expiration,
+            veto: config.veto,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -244,6 +297,75 @@ pub fn execute_propose(
         .add_attribute("status", proposal.status.to_string()))
 }
 
+pub fn execute_amend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<NeutronMsg>>,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    // A pre-propose module may restrict what proposals contain, so
+    // while one is in use only it may amend proposals.
+    let authorized = match CREATION_POLICY.load(deps.storage)? {
+        ProposalCreationPolicy::Anyone {} => info.sender == prop.proposer,
+        ProposalCreationPolicy::Module { addr } => info.sender == addr,
+    };
+    if !authorized {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.current_status(&env.block) != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    // Votes are cast on the content of the proposal, so it may no
+    // longer be amended once the first one is cast.
+    if BALLOTS
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(ContractError::AmendAfterVote { id: proposal_id });
+    }
+
+    let amendment = ProposalAmendment {
+        height: env.block.height,
+        title: std::mem::replace(&mut prop.title, title),
+        description: std::mem::replace(&mut prop.description, description),
+        msgs: std::mem::replace(&mut prop.msgs, msgs),
+    };
+
+    // Amended proposals are subject to the same size limit as new
+    // ones, see `execute_propose`.
+    let proposal_size = cosmwasm_std::to_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    let index = PROPOSAL_AMENDMENTS
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(0, |last| last + 1);
+    PROPOSAL_AMENDMENTS.save(deps.storage, (proposal_id, index), &amendment)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "amend")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amendment", index.to_string()))
+}
+
 pub fn execute_execute(
     deps: DepsMut,
     env: Env,
@@ -261,6 +383,19 @@ pub fn execute_execute(
     // period.
     let old_status = prop.status;
     prop.update_status(&env.block);
+    if prop.status == Status::PassedTimelocked {
+        // The vetoer may be allowed to skip the veto timelock, but not
+        // the execution delay.
+        let early_execute = prop.veto.as_ref().map_or(false, |veto| {
+            veto.early_execute && info.sender == veto.vetoer
+        }) && prop
+            .execution_delay_expiration
+            .map_or(true, |delay| delay.is_expired(&env.block));
+        if !early_execute {
+            return Err(ContractError::Timelocked { id: proposal_id });
+        }
+        prop.status = Status::Passed;
+    }
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
@@ -335,8 +470,11 @@ pub fn execute_vote(
     info: MessageInfo,
     proposal_id: u64,
     vote: Vote,
+    weighted_vote: Option<WeightedVote>,
+    rationale: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    validate_rationale(rationale.as_deref(), config.max_rationale_length)?;
     let mut prop = PROPOSALS
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
@@ -344,34 +482,68 @@ pub fn execute_vote(
         return Err(ContractError::NotOpen { id: proposal_id });
     }
 
-    let vote_power = get_voting_power(
+    let mut vote_power = get_voting_power(
         deps.as_ref(),
         info.sender.clone(),
-        config.dao,
+        config.dao.clone(),
         Some(prop.start_height),
     )?;
+    // Delegators voting directly override the vote of their delegate
+    // with the power they delegated, as counted by the voting registry
+    // of the DAO.
+    if let Some(ref vault) = config.delegation_vault {
+        let registry: Addr = deps
+            .querier
+            .query_wasm_smart(&config.dao, &cwd_core::msg::QueryMsg::VotingModule {})?;
+        let delegation = get_delegated_power(
+            deps.as_ref(),
+            &registry,
+            vault,
+            &info.sender,
+            prop.start_height,
+        )?;
+        if let Some(delegate) = delegation.delegate {
+            vote_power = vote_power.checked_add(delegation.power)?;
+            override_delegate(
+                deps.storage,
+                &mut prop,
+                proposal_id,
+                &info.sender,
+                delegate,
+                delegation.power,
+            )?;
+        }
+    }
+    // Delegates vote without the power of the delegators who
+    // overrode them.
+    let overridden_power = OVERRIDDEN_POWER
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .unwrap_or_default();
+    let vote_power = vote_power.saturating_sub(overridden_power);
     if vote_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(
+    let ballot = BALLOTS.update(
         deps.storage,
         (proposal_id, info.sender.clone()),
         |bal| match bal {
             Some(current_ballot) => {
                 if prop.allow_revoting {
-                    if current_ballot.vote == vote {
+                    if current_ballot.vote == vote && current_ballot.weighted_vote == weighted_vote
+                    {
                         // Don't allow casting the same vote more than
                         // once. This seems liable to be confusing
                         // behavior.
                         Err(ContractError::AlreadyCast {})
                     } else {
                         // Remove the old vote if this is a re-vote.
-                        prop.votes
-                            .remove_vote(current_ballot.vote, current_ballot.power);
+                        current_ballot.remove_from(&mut prop.votes);
                         Ok(Ballot {
                             power: vote_power,
                             vote,
+                            weighted_vote,
+                            rationale: rationale.clone(),
                         })
                     }
                 } else {
@@ -381,13 +553,15 @@ pub fn execute_vote(
             None => Ok(Ballot {
                 power: vote_power,
                 vote,
+                weighted_vote,
+                rationale: rationale.clone(),
             }),
         },
     )?;
 
     let old_status = prop.status;
 
-    prop.votes.add_vote(vote, vote_power);
+    ballot.add_to(&mut prop.votes);
     prop.update_status(&env.block);
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
@@ -401,12 +575,18 @@ pub fn execute_vote(
         new_status.to_string(),
     )?;
 
+    // Weighted votes are reported with their full breakdown.
+    let position = ballot.weighted_vote.map_or_else(
+        || vote.to_string(),
+        |weighted_vote| weighted_vote.to_string(),
+    );
     let vote_hooks = new_vote_hooks(
         VOTE_HOOKS,
         deps.storage,
         proposal_id,
         info.sender.to_string(),
-        vote.to_string(),
+        position.clone(),
+        rationale,
     )?;
 
     Ok(Response::default()
@@ -415,10 +595,64 @@ pub fn execute_vote(
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("position", vote.to_string())
+        .add_attribute("position", position)
         .add_attribute("status", prop.status.to_string()))
 }
 
+/// Records the override of `delegate` by `delegator` on a proposal
+/// and withdraws the delegated power from the ballot of the delegate,
+/// if they have voted. Overrides are recorded once per delegator.
+fn override_delegate(
+    storage: &mut dyn Storage,
+    prop: &mut SingleChoiceProposal,
+    proposal_id: u64,
+    delegator: &Addr,
+    delegate: Addr,
+    power: Uint128,
+) -> Result<(), ContractError> {
+    if power.is_zero() || DELEGATION_OVERRIDES.has(storage, (proposal_id, delegator.clone())) {
+        return Ok(());
+    }
+
+    OVERRIDDEN_POWER.update(
+        storage,
+        (proposal_id, delegate.clone()),
+        |overridden| -> StdResult<Uint128> {
+            Ok(overridden.unwrap_or_default().checked_add(power)?)
+        },
+    )?;
+    if let Some(mut ballot) = BALLOTS.may_load(storage, (proposal_id, delegate.clone()))? {
+        ballot.withdraw_power(&mut prop.votes, power);
+        BALLOTS.save(storage, (proposal_id, delegate.clone()), &ballot)?;
+    }
+    DELEGATION_OVERRIDES.save(
+        storage,
+        (proposal_id, delegator.clone()),
+        &DelegationOverride { delegate, power },
+    )?;
+    Ok(())
+}
+
+pub fn execute_weighted_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: WeightedVote,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    vote.validate()?;
+    execute_vote(
+        deps,
+        env,
+        info,
+        proposal_id,
+        vote.predominant(),
+        Some(vote),
+        rationale,
+    )
+}
+
 pub fn execute_close(
     deps: DepsMut,
     env: Env,
@@ -430,8 +664,16 @@ pub fn execute_close(
     // Update status to ensure that proposals which were open and have
     // expired are moved to "rejected."
     prop.update_status(&env.block);
-    if prop.status != Status::Rejected {
-        return Err(ContractError::WrongCloseStatus {});
+    match prop.status {
+        Status::Rejected => {}
+        // Only the vetoer of the proposal may close a passed proposal,
+        // and only until its timelock expires.
+        Status::PassedTimelocked => {
+            if prop.veto.as_ref().map(|veto| veto.vetoer.as_str()) != Some(info.sender.as_str()) {
+                return Err(ContractError::Unauthorized {});
+            }
+        }
+        _ => return Err(ContractError::WrongCloseStatus {}),
     }
 
     let old_status = prop.status;
@@ -476,6 +718,72 @@ pub fn execute_close(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+pub fn execute_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let veto = prop
+        .veto
+        .clone()
+        .ok_or(ContractError::NotVetoable { id: proposal_id })?;
+    if info.sender != veto.vetoer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let old_status = prop.status;
+    prop.update_status(&env.block);
+    match prop.status {
+        Status::PassedTimelocked => {}
+        Status::Open if veto.veto_before_passed => {}
+        _ => return Err(ContractError::NotVetoable { id: proposal_id }),
+    }
+
+    prop.status = Status::Vetoed;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
+                proposal_id,
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "veto")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
@@ -486,6 +794,10 @@ pub fn execute_update_config(
     allow_revoting: bool,
     dao: String,
     close_proposal_on_execution_failure: bool,
+    execution_delay: Option<Duration>,
+    veto: Option<VetoConfig>,
+    max_rationale_length: Option<u64>,
+    delegation_vault: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -499,6 +811,11 @@ pub fn execute_update_config(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
+    let execution_delay = validate_execution_delay(execution_delay, max_voting_period)?;
+    let veto = validate_veto_config(deps.as_ref(), veto, max_voting_period)?;
+    let delegation_vault = delegation_vault
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
 
     CONFIG.save(
         deps.storage,
@@ -509,6 +826,10 @@ pub fn execute_update_config(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            execution_delay,
+            veto,
+            max_rationale_length,
+            delegation_vault,
         },
     )?;
 
@@ -517,6 +838,66 @@ pub fn execute_update_config(
         .add_attribute("sender", info.sender))
 }
 
+/// Timelocks are added to the expiration of proposals, so they must be
+/// non-zero and have the same units as the maximum voting period.
+fn is_valid_timelock(timelock: Duration, max_voting_period: Duration) -> bool {
+    match (timelock, max_voting_period) {
+        (Duration::Height(timelock), Duration::Height(_)) => timelock != 0,
+        (Duration::Time(timelock), Duration::Time(_)) => timelock != 0,
+        _ => false,
+    }
+}
+
+fn validate_execution_delay(
+    execution_delay: Option<Duration>,
+    max_voting_period: Duration,
+) -> Result<Option<Duration>, ContractError> {
+    match execution_delay {
+        Some(delay) if !is_valid_timelock(delay, max_voting_period) => {
+            Err(ContractError::InvalidExecutionDelay {})
+        }
+        _ => Ok(execution_delay),
+    }
+}
+
+fn validate_veto_config(
+    deps: Deps,
+    veto: Option<VetoConfig>,
+    max_voting_period: Duration,
+) -> Result<Option<VetoConfig>, ContractError> {
+    veto.map(|veto| {
+        if !is_valid_timelock(veto.timelock_duration, max_voting_period) {
+            return Err(ContractError::InvalidVetoTimelock {});
+        }
+        Ok(VetoConfig {
+            vetoer: deps.api.addr_validate(&veto.vetoer)?.into_string(),
+            ..veto
+        })
+    })
+    .transpose()
+}
+
+/// Passed proposals are timelocked until both the execution delay and
+/// the veto timelock have elapsed after the end of their voting
+/// period.
+fn get_timelock_expiration(
+    config: &Config,
+    expiration: Expiration,
+) -> StdResult<Option<Expiration>> {
+    let timelocks = [
+        config.execution_delay,
+        config.veto.as_ref().map(|veto| veto.timelock_duration),
+    ];
+    let mut timelock_expiration: Option<Expiration> = None;
+    for timelock in timelocks.into_iter().flatten() {
+        let end = (expiration + timelock)?;
+        if timelock_expiration.map_or(true, |current| end > current) {
+            timelock_expiration = Some(end);
+        }
+    }
+    Ok(timelock_expiration)
+}
+
 pub fn execute_update_proposal_creation_policy(
     deps: DepsMut,
     info: MessageInfo,
@@ -652,6 +1033,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
+        QueryMsg::ProposalAmendments {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_proposal_amendments(deps, proposal_id, start_after, limit),
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
         QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
         QueryMsg::ListVotes {
@@ -659,6 +1045,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ListDelegationOverrides {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_delegation_overrides(deps, proposal_id, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
@@ -728,6 +1119,52 @@ pub fn query_reverse_proposals(
     to_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_proposal_amendments(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let amendments = PROPOSAL_AMENDMENTS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(_, amendment)| amendment))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&ProposalAmendmentsResponse { amendments })
+}
+
+pub fn query_list_delegation_overrides(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let overrides = DELEGATION_OVERRIDES
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (delegator, delegation_override) = item?;
+            Ok(DelegationOverrideInfo {
+                delegator,
+                delegate: delegation_override.delegate,
+                power: delegation_override.power,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&DelegationOverridesResponse { overrides })
+}
+
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
     to_binary(&proposal_count)
@@ -740,6 +1177,8 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         voter,
         vote: ballot.vote,
         power: ballot.power,
+        weighted_vote: ballot.weighted_vote,
+        rationale: ballot.rationale,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -766,6 +1205,8 @@ pub fn query_list_votes(
                 voter,
                 vote: ballot.vote,
                 power: ballot.power,
+                weighted_vote: ballot.weighted_vote,
+                rationale: ballot.rationale,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -1,6 +1,6 @@
 use std::u64;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{OverflowError, StdError};
 use cw_utils::ParseReplyError;
 use cwd_hooks::HookError;
 use cwd_voting::reply::error::TagError;
@@ -11,6 +11,9 @@ pub enum ContractError {
     #[error(transparent)]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
     #[error(transparent)]
     ParseReplyError(#[from] ParseReplyError),
 
@@ -35,6 +38,9 @@ pub enum ContractError {
     #[error("proposal is not open ({id})")]
     NotOpen { id: u64 },
 
+    #[error("proposal has been voted on and may no longer be amended ({id})")]
+    AmendAfterVote { id: u64 },
+
     #[error("not registered to vote (no voting power) at time of proposal creation")]
     NotRegistered {},
 
@@ -50,6 +56,18 @@ pub enum ContractError {
     #[error("only rejected proposals may be closed")]
     WrongCloseStatus {},
 
+    #[error("proposal is timelocked ({id})")]
+    Timelocked { id: u64 },
+
+    #[error("execution delay must be non-zero and have the same units (height or time) as max_voting_period")]
+    InvalidExecutionDelay {},
+
+    #[error("veto timelock duration must be non-zero and have the same units (height or time) as max_voting_period")]
+    InvalidVetoTimelock {},
+
+    #[error("proposal can not be vetoed ({id})")]
+    NotVetoable { id: u64 },
+
     #[error(
         "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
     )]
@@ -7,7 +7,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cwd_macros::{info_query, proposal_module_query};
-use cwd_voting::{pre_propose::PreProposeInfo, threshold::Threshold, voting::Vote};
+use cwd_voting::{
+    pre_propose::PreProposeInfo,
+    threshold::Threshold,
+    voting::{Vote, WeightedVote},
+};
+
+use crate::state::VetoConfig;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -37,6 +43,22 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's reserve was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The amount of time passed proposals are timelocked for before
+    /// they may be executed, counted from the end of their voting
+    /// period. Must have the same units as `max_voting_period`.
+    pub execution_delay: Option<Duration>,
+    /// The vetoer of the module and what it may do, if any. The
+    /// vetoer may also close passed proposals while they are
+    /// timelocked.
+    pub veto: Option<VetoConfig>,
+    /// The maximum length of vote rationales in bytes. Defaults to
+    /// `DEFAULT_MAX_RATIONALE_LENGTH` if not set.
+    pub max_rationale_length: Option<u64>,
+    /// The voting vault whose delegators may override the votes of
+    /// their delegates by voting directly, if any. The vault must
+    /// support the `Delegation` query and the voting registry of the
+    /// DAO the `DelegatedPowerAtHeight` query.
+    pub delegation_vault: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -58,6 +80,22 @@ pub enum ExecuteMsg {
         /// set the proposer of the proposal it creates.
         proposer: Option<String>,
     },
+    /// Replaces the title, description and messages of an open
+    /// proposal no votes have been cast on yet. The replaced content
+    /// is recorded in the amendment history of the proposal. Only the
+    /// proposer may call this method, or the pre-propose module if
+    /// proposals are created through one.
+    Amend {
+        /// The ID of the proposal to amend.
+        proposal_id: u64,
+        /// The new title of the proposal.
+        title: String,
+        /// The new description of the proposal.
+        description: String,
+        /// The new messages that should be executed in response to
+        /// this proposal passing.
+        msgs: Vec<CosmosMsg<NeutronMsg>>,
+    },
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
     Vote {
@@ -65,6 +103,21 @@ pub enum ExecuteMsg {
         proposal_id: u64,
         /// The senders position on the proposal.
         vote: Vote,
+        /// An optional explanation of the vote, stored with the
+        /// ballot and included in the vote hook.
+        rationale: Option<String>,
+    },
+    /// Votes on a proposal, splitting the voting power of the sender
+    /// between the positions. The weights must sum to one.
+    WeightedVote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The fractions of the senders voting power behind each
+        /// position.
+        vote: WeightedVote,
+        /// An optional explanation of the vote, stored with the
+        /// ballot and included in the vote hook.
+        rationale: Option<String>,
     },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
@@ -74,11 +127,19 @@ pub enum ExecuteMsg {
     },
     /// Closes a proposal that has failed (either not passed or timed
     /// out). If applicable this will cause the proposal deposit
-    /// associated wth said proposal to be returned.
+    /// associated wth said proposal to be returned. The vetoer of the
+    /// proposal may also close it while it is timelocked.
     Close {
         /// The ID of the proposal to close.
         proposal_id: u64,
     },
+    /// Vetoes a proposal that is timelocked, or still open if the
+    /// vetoer is allowed to veto proposals before they pass. Only the
+    /// vetoer of the proposal may call this method.
+    Veto {
+        /// The ID of the proposal to veto.
+        proposal_id: u64,
+    },
     /// Updates the governance module's config.
     UpdateConfig {
         /// The new proposal passing threshold. This will only apply
@@ -110,6 +171,21 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's reserve was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// The amount of time passed proposals are timelocked for
+        /// before they may be executed, counted from the end of their
+        /// voting period. This will only apply to proposals created
+        /// after the config update.
+        execution_delay: Option<Duration>,
+        /// The vetoer of the module and what it may do, if any. This
+        /// will only apply to proposals created after the config
+        /// update.
+        veto: Option<VetoConfig>,
+        /// The maximum length of vote rationales in bytes. Defaults
+        /// to `DEFAULT_MAX_RATIONALE_LENGTH` if not set.
+        max_rationale_length: Option<u64>,
+        /// The voting vault whose delegators may override the votes
+        /// of their delegates by voting directly, if any.
+        delegation_vault: Option<String>,
     },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
@@ -170,6 +246,20 @@ pub enum QueryMsg {
         /// returned.
         limit: Option<u64>,
     },
+    /// Lists the amendments of a proposal, oldest first. Returns
+    /// `query::ProposalAmendmentsResponse`.
+    #[returns(crate::query::ProposalAmendmentsResponse)]
+    ProposalAmendments {
+        proposal_id: u64,
+        /// The index of the amendment to start listing amendments
+        /// after. Amendments are indexed from zero, so if this is set
+        /// to 1 the third and later amendments will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of amendments to return as part of
+        /// this query. If no limit is set a max of 30 amendments will
+        /// be returned.
+        limit: Option<u64>,
+    },
     /// Returns the number of proposals that have been created in this
     /// module.
     #[returns(u64)]
@@ -191,6 +281,21 @@ pub enum QueryMsg {
         /// query. If no limit is specified a max of 30 are returned.
         limit: Option<u64>,
     },
+    /// Lists the delegators who overrode the vote of their delegate
+    /// on a proposal by voting directly. Returns
+    /// `query::DelegationOverridesResponse`.
+    #[returns(crate::query::DelegationOverridesResponse)]
+    ListDelegationOverrides {
+        /// The proposal to list the overrides of.
+        proposal_id: u64,
+        /// The delegator to start listing overrides after. Ordering
+        /// is done alphabetically.
+        start_after: Option<String>,
+        /// The maximum number of overrides to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
     /// Gets the current proposal creation policy for this
     /// module. Returns `voting::pre_propose::ProposalCreationPolicy`.
     #[returns(cwd_voting::pre_propose::ProposalCreationPolicy)]
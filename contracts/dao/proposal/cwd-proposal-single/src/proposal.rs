@@ -1,5 +1,5 @@
 use crate::query::ProposalResponse;
-use crate::state::PROPOSAL_COUNT;
+use crate::state::{VetoConfig, PROPOSAL_COUNT};
 use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, StdResult, Storage, Uint128};
 use cw_utils::Expiration;
 use cwd_voting::status::Status;
@@ -36,6 +36,18 @@ pub struct SingleChoiceProposal {
     pub status: Status,
     pub votes: Votes,
     pub allow_revoting: bool,
+    /// The time at which the timelock of this proposal expires should
+    /// it pass. Passed proposals may not be executed before then.
+    /// None if the proposal was created without an execution delay or
+    /// vetoer.
+    pub timelock_expiration: Option<Expiration>,
+    /// The time at which the execution delay of this proposal expires
+    /// should it pass. The vetoer may not execute it early before
+    /// then. None if the proposal was created without an execution
+    /// delay.
+    pub execution_delay_expiration: Option<Expiration>,
+    /// The vetoer of this proposal, if any.
+    pub veto: Option<VetoConfig>,
 }
 
 pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
@@ -60,16 +72,28 @@ impl SingleChoiceProposal {
     /// Gets the current status of the proposal.
     pub fn current_status(&self, block: &BlockInfo) -> Status {
         if self.status == Status::Open && self.is_passed(block) {
-            Status::Passed
+            if self.is_timelocked(block) {
+                Status::PassedTimelocked
+            } else {
+                Status::Passed
+            }
         } else if self.status == Status::Open
             && (self.expiration.is_expired(block) || self.is_rejected(block))
         {
             Status::Rejected
+        } else if self.status == Status::PassedTimelocked && !self.is_timelocked(block) {
+            Status::Passed
         } else {
             self.status
         }
     }
 
+    /// Returns true if the proposal's timelock has not yet expired.
+    pub fn is_timelocked(&self, block: &BlockInfo) -> bool {
+        self.timelock_expiration
+            .map_or(false, |timelock| !timelock.is_expired(block))
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) {
         let new_status = self.current_status(block);
@@ -272,6 +296,9 @@ mod test {
             threshold,
             total_power,
             votes,
+            timelock_expiration: None,
+            execution_delay_expiration: None,
+            veto: None,
         };
         (prop, block)
     }
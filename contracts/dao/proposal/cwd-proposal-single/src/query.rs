@@ -2,9 +2,10 @@ use cosmwasm_std::{Addr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cwd_voting::voting::Vote;
+use cwd_voting::voting::{Vote, WeightedVote};
 
 use crate::proposal::SingleChoiceProposal;
+use crate::state::ProposalAmendment;
 
 /// Information about a proposal returned by proposal queries.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -23,6 +24,11 @@ pub struct VoteInfo {
     pub vote: Vote,
     /// The voting power behind the vote.
     pub power: Uint128,
+    /// The weights the voting power is split between the positions
+    /// by, if this is a weighted vote.
+    pub weighted_vote: Option<WeightedVote>,
+    /// The reasoning the voter published with the vote, if any.
+    pub rationale: Option<String>,
 }
 
 /// Information about a vote.
@@ -38,6 +44,29 @@ pub struct VoteListResponse {
     pub votes: Vec<VoteInfo>,
 }
 
+/// The vote of a delegator overriding the vote of their delegate.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegationOverrideInfo {
+    /// The delegator who voted directly.
+    pub delegator: Addr,
+    /// The delegate whose vote was overridden.
+    pub delegate: Addr,
+    /// The delegated voting power withdrawn from the delegate.
+    pub power: Uint128,
+}
+
+/// The delegation overrides of a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DelegationOverridesResponse {
+    pub overrides: Vec<DelegationOverrideInfo>,
+}
+
+/// The amendments of a proposal, oldest first.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalAmendmentsResponse {
+    pub amendments: Vec<ProposalAmendment>,
+}
+
 /// A list of proposals returned by `ListProposals` and
 /// `ReverseProposals`.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -1,12 +1,17 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, Uint128};
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 
 use cwd_hooks::Hooks;
+use neutron_sdk::bindings::msg::NeutronMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cwd_voting::{pre_propose::ProposalCreationPolicy, threshold::Threshold, voting::Vote};
+use cwd_voting::{
+    pre_propose::ProposalCreationPolicy,
+    threshold::Threshold,
+    voting::{Vote, Votes, WeightedVote},
+};
 
 use crate::proposal::SingleChoiceProposal;
 
@@ -15,9 +20,63 @@ use crate::proposal::SingleChoiceProposal;
 pub struct Ballot {
     /// The amount of voting power behind the vote.
     pub power: Uint128,
-    /// The position.
+    /// The position. For weighted votes, the position with the most
+    /// weight.
     pub vote: Vote,
+    /// The weights the voting power is split between the positions
+    /// by, if this is a weighted vote.
+    pub weighted_vote: Option<WeightedVote>,
+    /// The reasoning the voter published with the vote, if any.
+    pub rationale: Option<String>,
 }
+
+impl Ballot {
+    /// Adds the voting power behind the ballot to `votes`.
+    pub fn add_to(&self, votes: &mut Votes) {
+        match self.weighted_vote {
+            Some(ref weighted_vote) => votes.add_weighted_vote(weighted_vote, self.power),
+            None => votes.add_vote(self.vote, self.power),
+        }
+    }
+
+    /// Removes the voting power behind the ballot from `votes`.
+    pub fn remove_from(&self, votes: &mut Votes) {
+        match self.weighted_vote {
+            Some(ref weighted_vote) => votes.remove_weighted_vote(weighted_vote, self.power),
+            None => votes.remove_vote(self.vote, self.power),
+        }
+    }
+
+    /// Withdraws `power` from the ballot, updating `votes`
+    /// accordingly.
+    pub fn withdraw_power(&mut self, votes: &mut Votes, power: Uint128) {
+        self.remove_from(votes);
+        self.power = self.power.saturating_sub(power);
+        self.add_to(votes);
+    }
+}
+
+/// The vote of a delegator overriding the vote of their delegate.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DelegationOverride {
+    /// The delegate whose vote was overridden.
+    pub delegate: Addr,
+    /// The delegated voting power withdrawn from the delegate.
+    pub power: Uint128,
+}
+/// The content of a proposal replaced by an amendment.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalAmendment {
+    /// The block height at which the proposal was amended.
+    pub height: u64,
+    /// The title of the proposal before the amendment.
+    pub title: String,
+    /// The description of the proposal before the amendment.
+    pub description: String,
+    /// The messages of the proposal before the amendment.
+    pub msgs: Vec<CosmosMsg<NeutronMsg>>,
+}
+
 /// The governance module's configuration.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -48,6 +107,40 @@ pub struct Config {
     /// remain open until the DAO's reserve was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// The amount of time passed proposals are timelocked for before
+    /// they may be executed, counted from the end of their voting
+    /// period. Must have the same units as `max_voting_period`.
+    pub execution_delay: Option<Duration>,
+    /// The vetoer of the module and what it may do, if any. The
+    /// vetoer may also close passed proposals while they are
+    /// timelocked.
+    pub veto: Option<VetoConfig>,
+    /// The maximum length of vote rationales in bytes. Defaults to
+    /// `DEFAULT_MAX_RATIONALE_LENGTH` if not set.
+    pub max_rationale_length: Option<u64>,
+    /// The voting vault whose delegators may override the votes of
+    /// their delegates by voting directly, if any. The vault must
+    /// support the `Delegation` query and the voting registry of the
+    /// DAO the `DelegatedPowerAtHeight` query.
+    pub delegation_vault: Option<Addr>,
+}
+
+/// Allows an address, for example a security subDAO, to veto
+/// proposals of the module.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VetoConfig {
+    /// The address which may veto proposals.
+    pub vetoer: String,
+    /// The amount of time after the end of their voting period during
+    /// which passed proposals are timelocked and may be vetoed. Must
+    /// have the same units as `max_voting_period`.
+    pub timelock_duration: Duration,
+    /// Whether the vetoer may execute proposals before their timelock
+    /// expires.
+    pub early_execute: bool,
+    /// Whether the vetoer may veto proposals while they are still open
+    /// for voting.
+    pub veto_before_passed: bool,
 }
 
 /// The current top level config for the module.  The "config" key was
@@ -57,6 +150,16 @@ pub const CONFIG: Item<Config> = Item::new("config_v2");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+/// The amendments of each proposal, keyed by proposal ID and the index
+/// of the amendment. Indexes start at zero, oldest first.
+pub const PROPOSAL_AMENDMENTS: Map<(u64, u64), ProposalAmendment> = Map::new("proposal_amendments");
+/// The overrides of delegators who voted directly, keyed by proposal
+/// ID and delegator.
+pub const DELEGATION_OVERRIDES: Map<(u64, Addr), DelegationOverride> =
+    Map::new("delegation_overrides");
+/// The voting power withdrawn from delegates by the overrides of
+/// their delegators, keyed by proposal ID and delegate.
+pub const OVERRIDDEN_POWER: Map<(u64, Addr), Uint128> = Map::new("overridden_power");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
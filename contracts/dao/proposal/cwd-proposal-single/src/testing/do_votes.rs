@@ -82,6 +82,10 @@ where
         min_voting_period: None,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        execution_delay: None,
+        veto: None,
+        max_rationale_length: None,
+        delegation_vault: None,
         pre_propose_info,
     };
 
@@ -169,6 +173,7 @@ where
             &ExecuteMsg::Vote {
                 proposal_id: 1,
                 vote: position,
+                rationale: None,
             },
             &[],
         );
@@ -206,6 +211,8 @@ where
                             // expected voting power.
                             _ => weight,
                         },
+                        weighted_vote: None,
+                        rationale: None,
                     }),
                 };
                 assert_eq!(vote, expected)
@@ -131,7 +131,11 @@ pub(crate) fn vote_on_proposal(
     app.execute_contract(
         Addr::unchecked(sender),
         proposal_single.clone(),
-        &ExecuteMsg::Vote { proposal_id, vote },
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale: None,
+        },
         &[],
     )
     .unwrap();
@@ -147,7 +151,11 @@ pub(crate) fn vote_on_proposal_should_fail(
     app.execute_contract(
         Addr::unchecked(sender),
         proposal_single.clone(),
-        &ExecuteMsg::Vote { proposal_id, vote },
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale: None,
+        },
         &[],
     )
     .unwrap_err()
@@ -219,6 +227,38 @@ pub(crate) fn close_proposal(
     .unwrap();
 }
 
+pub(crate) fn veto_proposal_should_fail(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+) -> ContractError {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::Veto { proposal_id },
+        &[],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap()
+}
+
+pub(crate) fn veto_proposal(
+    app: &mut BasicApp<NeutronMsg>,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::Veto { proposal_id },
+        &[],
+    )
+    .unwrap();
+}
+
 pub(crate) fn mint_natives(app: &mut BasicApp<NeutronMsg>, receiver: &str, amount: Vec<Coin>) {
     app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
         to_address: receiver.to_string(),
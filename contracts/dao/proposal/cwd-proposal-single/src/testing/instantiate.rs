@@ -22,7 +22,7 @@ use super::{
     CREATOR_ADDR,
 };
 
-const DAO_ADDR: &str = "dao";
+pub(crate) const DAO_ADDR: &str = "dao";
 
 pub(crate) fn get_pre_propose_info(
     app: &mut BasicApp<NeutronMsg>,
@@ -66,6 +66,10 @@ pub(crate) fn get_proposal_module_instantiate(app: &mut BasicApp<NeutronMsg>) ->
             false,
         ),
         close_proposal_on_execution_failure: true,
+        execution_delay: None,
+        veto: None,
+        max_rationale_length: None,
+        delegation_vault: None,
     }
 }
 
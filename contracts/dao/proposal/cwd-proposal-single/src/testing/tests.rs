@@ -7,13 +7,14 @@ use cosmwasm_std::{
 use cosmwasm_std::{Api, Storage};
 use cw2::ContractVersion;
 use cw20::Cw20Coin;
-use cw_multi_test::{custom_app, BasicApp, Executor, Router};
+use cw_multi_test::{custom_app, next_block, BasicApp, Executor, Router};
 use cw_utils::Duration;
 use cwd_core::msg::{ExecuteMsg as DaoExecuteMsg, QueryMsg as DaoQueryMsg};
 use cwd_core::query::SubDao;
 use cwd_hooks::{HookError, HooksResponse};
 use cwd_interface::voting::InfoResponse;
 use cwd_voting::{
+    error::VotingError,
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::MAX_PROPOSAL_SIZE,
     reply::{
@@ -22,7 +23,7 @@ use cwd_voting::{
     },
     status::Status,
     threshold::{PercentageThreshold, Threshold},
-    voting::{Vote, Votes},
+    voting::{Vote, Votes, WeightedVote},
 };
 use neutron_sdk::bindings::msg::NeutronMsg;
 
@@ -31,17 +32,22 @@ use crate::{
     contract::{CONTRACT_NAME, CONTRACT_VERSION},
     msg::{ExecuteMsg, QueryMsg},
     proposal::SingleChoiceProposal,
-    query::{ProposalResponse, VoteInfo},
-    state::Config,
+    query::{
+        DelegationOverrideInfo, DelegationOverridesResponse, ProposalAmendmentsResponse,
+        ProposalResponse, VoteInfo,
+    },
+    state::{Config, ProposalAmendment, VetoConfig},
     testing::{
         execute::{
             add_proposal_hook, add_proposal_hook_should_fail, add_vote_hook,
             add_vote_hook_should_fail, close_proposal, close_proposal_should_fail, make_proposal,
             mint_natives, remove_proposal_hook, remove_proposal_hook_should_fail, remove_vote_hook,
-            remove_vote_hook_should_fail, vote_on_proposal, vote_on_proposal_should_fail,
+            remove_vote_hook_should_fail, veto_proposal, veto_proposal_should_fail,
+            vote_on_proposal, vote_on_proposal_should_fail,
         },
         instantiate::{
             get_proposal_module_instantiate, instantiate_with_native_bonded_balances_governance,
+            DAO_ADDR,
         },
         queries::{
             query_balance_native, query_creation_policy, query_list_proposals,
@@ -225,6 +231,699 @@ fn test_proposal_cant_close_after_expiry_is_passed() {
     assert!(matches!(err, ContractError::WrongCloseStatus {}));
 }
 
+#[test]
+fn test_execution_delay() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let mut instantiate = get_proposal_module_instantiate(&mut app);
+    instantiate.execution_delay = Some(Duration::Time(86400));
+    instantiate.veto = Some(VetoConfig {
+        vetoer: "vetoer".to_string(),
+        timelock_duration: Duration::Time(3600),
+        early_execute: false,
+        veto_before_passed: false,
+    });
+    let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+    mint_natives(&mut app, CREATOR_ADDR, coins(20_000_000, "ujuno"));
+    let msgs: Vec<CosmosMsg<NeutronMsg>> = vec![BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()];
+    let executed_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, msgs.clone());
+    let closed_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, msgs);
+    for proposal_id in [executed_id, closed_id] {
+        vote_on_proposal(
+            &mut app,
+            &proposal_module,
+            CREATOR_ADDR,
+            proposal_id,
+            Vote::Yes,
+        );
+        let proposal = query_proposal(&app, &proposal_module, proposal_id);
+        assert_eq!(proposal.proposal.status, Status::PassedTimelocked);
+    }
+
+    // Timelocked proposals may not be executed, and only the vetoer
+    // of the proposal may close them.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, executed_id);
+    assert!(matches!(err, ContractError::Timelocked { id } if id == executed_id));
+    let err = close_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, closed_id);
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Changing the vetoer does not change who may close proposals
+    // that were already created.
+    let config = query_proposal_config(&app, &proposal_module);
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.into_string(),
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            execution_delay: config.execution_delay,
+            veto: config.veto.map(|veto| VetoConfig {
+                vetoer: "new_vetoer".to_string(),
+                ..veto
+            }),
+            max_rationale_length: config.max_rationale_length,
+            delegation_vault: config.delegation_vault.map(Addr::into_string),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = close_proposal_should_fail(&mut app, &proposal_module, "new_vetoer", closed_id);
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    close_proposal(&mut app, &proposal_module, "vetoer", closed_id);
+    let proposal = query_proposal(&app, &proposal_module, closed_id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    // The timelock expires a day after the voting period.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let proposal = query_proposal(&app, &proposal_module, executed_id);
+    assert_eq!(proposal.proposal.status, Status::PassedTimelocked);
+    app.update_block(|b| b.time = b.time.plus_seconds(86400));
+    let proposal = query_proposal(&app, &proposal_module, executed_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // Once passed the proposal may no longer be closed.
+    let err = close_proposal_should_fail(&mut app, &proposal_module, "vetoer", executed_id);
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, executed_id);
+    let proposal = query_proposal(&app, &proposal_module, executed_id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_weighted_vote() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let instantiate = get_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(50),
+            },
+            Cw20Coin {
+                address: "custodian".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "multisig".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, CREATOR_ADDR, coins(10_000_000, "ujuno"));
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("custodian"),
+            proposal_module.clone(),
+            &ExecuteMsg::WeightedVote {
+                proposal_id,
+                vote: WeightedVote {
+                    yes: Decimal::percent(50),
+                    no: Decimal::percent(60),
+                    abstain: Decimal::zero(),
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidWeightedVote {})
+    ));
+
+    let custodian_vote = WeightedVote {
+        yes: Decimal::percent(50),
+        no: Decimal::percent(50),
+        abstain: Decimal::zero(),
+    };
+    let multisig_vote = WeightedVote {
+        yes: Decimal::percent(30),
+        no: Decimal::percent(30),
+        abstain: Decimal::percent(40),
+    };
+    for (voter, vote) in [("custodian", custodian_vote), ("multisig", multisig_vote)] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::WeightedVote {
+                proposal_id,
+                vote,
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(15 + 6),
+            no: Uint128::new(15 + 6),
+            abstain: Uint128::new(8),
+        }
+    );
+
+    let votes = query_list_votes(&app, &proposal_module, proposal_id, None, None);
+    assert_eq!(
+        votes.votes,
+        vec![
+            VoteInfo {
+                voter: Addr::unchecked("custodian"),
+                vote: Vote::Yes,
+                power: Uint128::new(30),
+                weighted_vote: Some(custodian_vote),
+                rationale: None,
+            },
+            VoteInfo {
+                voter: Addr::unchecked("multisig"),
+                vote: Vote::Abstain,
+                power: Uint128::new(20),
+                weighted_vote: Some(multisig_vote),
+                rationale: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_vote_rationale() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let mut instantiate = get_proposal_module_instantiate(&mut app);
+    instantiate.max_rationale_length = Some(16);
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            amount: Uint128::new(100),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, CREATOR_ADDR, coins(10_000_000, "ujuno"));
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+                rationale: Some("a rationale that is too long".to_string()),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::RationaleTooLong {
+            length: 28,
+            max: 16
+        })
+    ));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id,
+            vote: Vote::Yes,
+            rationale: Some("good for the dao".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let votes = query_list_votes(&app, &proposal_module, proposal_id, None, None);
+    assert_eq!(
+        votes.votes,
+        vec![VoteInfo {
+            voter: Addr::unchecked(CREATOR_ADDR),
+            vote: Vote::Yes,
+            power: Uint128::new(100),
+            weighted_vote: None,
+            rationale: Some("good for the dao".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_delegation_override() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let instantiate = get_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "delegate".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "delegator".to_string(),
+                amount: Uint128::new(30),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let registry: Addr = app
+        .wrap()
+        .query_wasm_smart(&core_addr, &DaoQueryMsg::VotingModule {})
+        .unwrap();
+    let vaults: Vec<neutron_voting_registry::msg::VotingVault> = app
+        .wrap()
+        .query_wasm_smart(
+            registry.clone(),
+            &neutron_voting_registry::msg::QueryMsg::VotingVaults {},
+        )
+        .unwrap();
+    let vault = Addr::unchecked(&vaults[0].address);
+    app.execute_contract(
+        Addr::unchecked("delegator"),
+        vault.clone(),
+        &neutron_vault::msg::ExecuteMsg::Delegate {
+            to: "delegate".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let config = query_proposal_config(&app, &proposal_module);
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.into_string(),
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            execution_delay: config.execution_delay,
+            veto: config.veto,
+            max_rationale_length: config.max_rationale_length,
+            delegation_vault: Some(vault.into_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    mint_natives(&mut app, CREATOR_ADDR, coins(20_000_000, "ujuno"));
+
+    // The delegator overrides the vote their delegate already cast.
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegate",
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(40));
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegator",
+        proposal_id,
+        Vote::No,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(10),
+            no: Uint128::new(30),
+            abstain: Uint128::zero(),
+        }
+    );
+    let votes = query_list_votes(&app, &proposal_module, proposal_id, None, None);
+    assert_eq!(
+        votes
+            .votes
+            .iter()
+            .map(|vote| (vote.voter.as_str(), vote.power))
+            .collect::<Vec<_>>(),
+        vec![
+            ("delegate", Uint128::new(10)),
+            ("delegator", Uint128::new(30)),
+        ]
+    );
+    let overrides: DelegationOverridesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListDelegationOverrides {
+                proposal_id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        overrides.overrides,
+        vec![DelegationOverrideInfo {
+            delegator: Addr::unchecked("delegator"),
+            delegate: Addr::unchecked("delegate"),
+            power: Uint128::new(30),
+        }]
+    );
+
+    // The delegate votes without the power of the delegator who
+    // already voted.
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegator",
+        proposal_id,
+        Vote::No,
+    );
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegate",
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(10),
+            no: Uint128::new(30),
+            abstain: Uint128::zero(),
+        }
+    );
+
+    // The overridden power is the delegated power as counted by the
+    // registry, after the power transform of the vault.
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        registry,
+        &neutron_voting_registry::msg::ExecuteMsg::UpdateVaultWeights {
+            weights: vec![neutron_voting_registry::msg::VaultWeight {
+                vault: vault.to_string(),
+                transform: neutron_voting_registry::state::PowerTransform::Multiplier {
+                    factor: Decimal::percent(50),
+                },
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegate",
+        proposal_id,
+        Vote::Yes,
+    );
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        "delegator",
+        proposal_id,
+        Vote::No,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::new(5),
+            no: Uint128::new(15),
+            abstain: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn test_amend() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let mut instantiate = get_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_native_bonded_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "voter".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, CREATOR_ADDR, coins(10_000_000, "ujuno"));
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    let msgs: Vec<CosmosMsg<NeutronMsg>> = vec![BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()];
+    let amend = |title: &str| ExecuteMsg::Amend {
+        proposal_id,
+        title: title.to_string(),
+        description: "amended description".to_string(),
+        msgs: msgs.clone(),
+    };
+
+    // Only the proposer may amend.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("voter"),
+            proposal_module.clone(),
+            &amend("amended title"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    for title in ["amended title", "second title"] {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &amend(title),
+            &[],
+        )
+        .unwrap();
+    }
+
+    // The replaced content is recorded.
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.title, "second title");
+    assert_eq!(proposal.proposal.description, "amended description");
+    assert_eq!(proposal.proposal.msgs, msgs);
+    let query_amendments = |start_after, limit| -> Vec<ProposalAmendment> {
+        let res: ProposalAmendmentsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ProposalAmendments {
+                    proposal_id,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        res.amendments
+    };
+    let first = ProposalAmendment {
+        height: app.block_info().height,
+        title: "title".to_string(),
+        description: "description".to_string(),
+        msgs: vec![],
+    };
+    let second = ProposalAmendment {
+        height: app.block_info().height,
+        title: "amended title".to_string(),
+        description: "amended description".to_string(),
+        msgs: msgs.clone(),
+    };
+    assert_eq!(
+        query_amendments(None, None),
+        vec![first.clone(), second.clone()]
+    );
+    assert_eq!(query_amendments(None, Some(1)), vec![first]);
+    assert_eq!(query_amendments(Some(0), None), vec![second]);
+
+    // Once voted on the proposal may no longer be amended.
+    vote_on_proposal(&mut app, &proposal_module, "voter", proposal_id, Vote::No);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &amend("third title"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AmendAfterVote { id } if id == proposal_id));
+
+    // Nor may it once it is no longer open.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &amend("third title"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotOpen { id } if id == proposal_id));
+}
+
+#[test]
+fn test_amend_pre_propose_module() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let instantiate = get_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, CREATOR_ADDR, coins(10_000_000, "ujuno"));
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // The proposer may not amend the proposal past the pre-propose
+    // module, which would otherwise allow any messages.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id,
+                title: "amended title".to_string(),
+                description: "amended description".to_string(),
+                msgs: vec![BankMsg::Send {
+                    to_address: CREATOR_ADDR.to_string(),
+                    amount: coins(10, "ujuno"),
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.title, "title");
+    assert!(proposal.proposal.msgs.is_empty());
+}
+#[test]
+fn test_veto() {
+    let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
+    let mut instantiate = get_proposal_module_instantiate(&mut app);
+    instantiate.veto = Some(VetoConfig {
+        vetoer: "vetoer".to_string(),
+        timelock_duration: Duration::Time(86400),
+        early_execute: true,
+        veto_before_passed: false,
+    });
+    instantiate.execution_delay = Some(Duration::Time(3600));
+    let core_addr = instantiate_with_native_bonded_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_natives(&mut app, core_addr.as_str(), coins(10, "ujuno"));
+    mint_natives(&mut app, CREATOR_ADDR, coins(30_000_000, "ujuno"));
+    let msgs: Vec<CosmosMsg<NeutronMsg>> = vec![BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(10, "ujuno"),
+    }
+    .into()];
+    let executed_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, msgs.clone());
+    let vetoed_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, msgs.clone());
+    let open_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, msgs);
+
+    // Open proposals may not be vetoed unless `veto_before_passed` is
+    // set.
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, "vetoer", open_id);
+    assert!(matches!(err, ContractError::NotVetoable { id } if id == open_id));
+
+    for proposal_id in [executed_id, vetoed_id] {
+        vote_on_proposal(
+            &mut app,
+            &proposal_module,
+            CREATOR_ADDR,
+            proposal_id,
+            Vote::Yes,
+        );
+        let proposal = query_proposal(&app, &proposal_module, proposal_id);
+        assert_eq!(proposal.proposal.status, Status::PassedTimelocked);
+    }
+
+    // Only the vetoer may veto.
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, vetoed_id);
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    veto_proposal(&mut app, &proposal_module, "vetoer", vetoed_id);
+    let proposal = query_proposal(&app, &proposal_module, vetoed_id);
+    assert_eq!(proposal.proposal.status, Status::Vetoed);
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, vetoed_id);
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    // The vetoer may skip the veto timelock, but not the execution
+    // delay.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, "vetoer", executed_id);
+    assert!(matches!(err, ContractError::Timelocked { id } if id == executed_id));
+    app.update_block(|b| b.time = b.time.plus_seconds(604800 + 3600));
+    let proposal = query_proposal(&app, &proposal_module, executed_id);
+    assert_eq!(proposal.proposal.status, Status::PassedTimelocked);
+
+    // Only the vetoer may execute timelocked proposals early.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, CREATOR_ADDR, executed_id);
+    assert!(matches!(err, ContractError::Timelocked { id } if id == executed_id));
+    execute_proposal(&mut app, &proposal_module, "vetoer", executed_id);
+    let proposal = query_proposal(&app, &proposal_module, executed_id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    let err = veto_proposal_should_fail(&mut app, &proposal_module, "vetoer", executed_id);
+    assert!(matches!(err, ContractError::NotVetoable { id } if id == executed_id));
+}
+
 #[test]
 fn test_execute_no_non_passed_execution() {
     let CommonTest {
@@ -294,6 +993,10 @@ fn test_update_config() {
                 allow_revoting: false,
                 dao: core_addr.to_string(),
                 close_proposal_on_execution_failure: false,
+                execution_delay: None,
+                veto: None,
+                max_rationale_length: None,
+                delegation_vault: None,
             })
             .unwrap(),
             funds: vec![],
@@ -321,6 +1024,10 @@ fn test_update_config() {
             allow_revoting: false,
             dao: core_addr.clone(),
             close_proposal_on_execution_failure: false,
+            execution_delay: None,
+            veto: None,
+            max_rationale_length: None,
+            delegation_vault: None,
         }
     );
 
@@ -338,6 +1045,10 @@ fn test_update_config() {
                 allow_revoting: false,
                 dao: core_addr.to_string(),
                 close_proposal_on_execution_failure: false,
+                execution_delay: None,
+                veto: None,
+                max_rationale_length: None,
+                delegation_vault: None,
             },
             &[],
         )
@@ -420,6 +1131,9 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     no: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
+                timelock_expiration: None,
+                execution_delay_expiration: None,
+                veto: None,
             }
         }
     )
@@ -893,6 +1607,10 @@ fn test_execution_failed() {
             dao: config.dao.into_string(),
             // Disable.
             close_proposal_on_execution_failure: false,
+            execution_delay: config.execution_delay,
+            veto: config.veto,
+            max_rationale_length: config.max_rationale_length,
+            delegation_vault: config.delegation_vault.map(|addr| addr.into_string()),
         },
         &[],
     )
@@ -958,6 +1676,9 @@ fn test_reply_proposal_mock() {
                 msgs: vec![],
                 status: Status::Open,
                 votes: Votes::zero(),
+                timelock_expiration: None,
+                execution_delay_expiration: None,
+                veto: None,
             },
         )
         .unwrap();
@@ -1283,27 +2004,37 @@ fn test_query_list_votes() {
             VoteInfo {
                 voter: Addr::unchecked("five"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("four"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("two"),
                 vote: Vote::No,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             }
         ]
     );
@@ -1321,12 +2052,16 @@ fn test_query_list_votes() {
             VoteInfo {
                 voter: Addr::unchecked("one"),
                 vote: Vote::Yes,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             },
             VoteInfo {
                 voter: Addr::unchecked("three"),
                 vote: Vote::No,
-                power: Uint128::new(1)
+                power: Uint128::new(1),
+                weighted_vote: None,
+                rationale: None,
             },
         ]
     );
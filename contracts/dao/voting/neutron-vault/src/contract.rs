@@ -1,19 +1,23 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Storage, Uint128,
 };
 use cw2::set_contract_version;
 use cw_utils::must_pay;
 use cwd_interface::voting::{
     BondingStatusResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
-use cwd_voting::vault::{BonderBalanceResponse, ListBondersResponse};
+use cwd_voting::vault::{BonderBalanceResponse, DelegationResponse, ListBondersResponse};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, BONDED_BALANCES, BONDED_TOTAL, CONFIG, DAO};
+use crate::msg::{
+    DelegatePowerResponse, ExecuteMsg, InstantiateMsg, ListDelegatesResponse, MigrateMsg, QueryMsg,
+};
+use crate::state::{
+    Config, BONDED_BALANCES, BONDED_TOTAL, CONFIG, DAO, DELEGATED_POWER, DELEGATIONS,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-voting-vault";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -61,6 +65,8 @@ pub fn execute(
             name,
             description,
         } => execute_update_config(deps, info, owner, name, description),
+        ExecuteMsg::Delegate { to } => execute_delegate(deps, env, info, to),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
     }
 }
 
@@ -79,6 +85,9 @@ pub fn execute_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Respon
         env.block.height,
         |total| -> StdResult<Uint128> { Ok(total.unwrap_or_default().checked_add(amount)?) },
     )?;
+    if let Some(delegate) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        add_delegated_power(deps.storage, &delegate, amount, env.block.height)?;
+    }
 
     Ok(Response::new()
         .add_attribute("action", "bond")
@@ -115,6 +124,9 @@ pub fn execute_unbond(
                 .map_err(|_e| ContractError::InvalidUnbondAmount {})
         },
     )?;
+    if let Some(delegate) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        remove_delegated_power(deps.storage, &delegate, amount, env.block.height)?;
+    }
 
     let msg = CosmosMsg::Bank(BankMsg::Send {
         to_address: info.sender.to_string(),
@@ -154,6 +166,77 @@ pub fn execute_update_config(
         .add_attribute("owner", config.owner))
 }
 
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&to)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+
+    let balance = BONDED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        remove_delegated_power(deps.storage, &previous, balance, env.block.height)?;
+    }
+    DELEGATIONS.save(deps.storage, &info.sender, &delegate, env.block.height)?;
+    add_delegated_power(deps.storage, &delegate, balance, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", delegate))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegate = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotDelegated {})?;
+
+    let balance = BONDED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    remove_delegated_power(deps.storage, &delegate, balance, env.block.height)?;
+    DELEGATIONS.remove(deps.storage, &info.sender, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", delegate))
+}
+
+fn add_delegated_power(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    DELEGATED_POWER.update(storage, delegate, height, |power| -> StdResult<Uint128> {
+        Ok(power.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+fn remove_delegated_power(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    DELEGATED_POWER.update(storage, delegate, height, |power| -> StdResult<Uint128> {
+        Ok(power.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -174,6 +257,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::BondingStatus { height, address } => {
             to_binary(&query_bonding_status(deps, env, height, address)?)
         }
+        QueryMsg::Delegation { address, height } => {
+            to_binary(&query_delegation(deps, env, address, height)?)
+        }
+        QueryMsg::ListDelegates { start_after, limit } => {
+            query_list_delegates(deps, start_after, limit)
+        }
     }
 }
 
@@ -185,9 +274,22 @@ pub fn query_voting_power_at_height(
 ) -> StdResult<VotingPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
     let address = deps.api.addr_validate(&address)?;
-    let power = BONDED_BALANCES
+    // The bonded balance of a delegator counts towards the voting
+    // power of their delegate instead.
+    let own = if DELEGATIONS
+        .may_load_at_height(deps.storage, &address, height)?
+        .is_some()
+    {
+        Uint128::zero()
+    } else {
+        BONDED_BALANCES
+            .may_load_at_height(deps.storage, &address, height)?
+            .unwrap_or_default()
+    };
+    let delegated = DELEGATED_POWER
         .may_load_at_height(deps.storage, &address, height)?
         .unwrap_or_default();
+    let power = own.checked_add(delegated)?;
     Ok(VotingPowerAtHeightResponse { power, height })
 }
 
@@ -251,6 +353,34 @@ pub fn query_list_bonders(
     to_binary(&ListBondersResponse { bonders })
 }
 
+pub fn query_list_delegates(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_at = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let delegates = cw_paginate::paginate_snapshot_map(
+        deps,
+        &DELEGATED_POWER,
+        start_at.as_ref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+
+    let delegates = delegates
+        .into_iter()
+        .map(|(address, power)| DelegatePowerResponse {
+            address: address.into_string(),
+            power,
+        })
+        .collect();
+
+    to_binary(&ListDelegatesResponse { delegates })
+}
+
 pub fn query_bonding_status(
     deps: Deps,
     env: Env,
@@ -269,6 +399,21 @@ pub fn query_bonding_status(
     })
 }
 
+pub fn query_delegation(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<DelegationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let delegate = DELEGATIONS.may_load_at_height(deps.storage, &address, height)?;
+    let power = BONDED_BALANCES
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    Ok(DelegationResponse { delegate, power })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
@@ -27,4 +27,10 @@ pub enum ContractError {
 
     #[error("Can only unbond less than or equal to the amount you have bonded")]
     InvalidUnbondAmount {},
+
+    #[error("Can not delegate voting power to yourself")]
+    SelfDelegation {},
+
+    #[error("Voting power is not delegated")]
+    NotDelegated {},
 }
@@ -28,6 +28,12 @@ pub enum ExecuteMsg {
         description: String,
         owner: String,
     },
+    /// Delegates the voting power of the sender's bonded balance to
+    /// `to`, replacing any previous delegation. Delegations are not
+    /// transitive: only the sender's own bonded balance is delegated.
+    Delegate { to: String },
+    /// Takes back the voting power delegated by the sender.
+    Undelegate {},
 }
 
 #[voting_query]
@@ -38,6 +44,32 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(crate::state::Config)]
     Config {},
+    #[returns(cwd_voting::vault::DelegationResponse)]
+    Delegation {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Lists the addresses bonders have delegated their voting power
+    /// to, with the bonded balance currently delegated to each, which
+    /// may be zero. Delegates are not listed by `ListBonders` unless
+    /// they have bonded themselves.
+    #[returns(ListDelegatesResponse)]
+    ListDelegates {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ListDelegatesResponse {
+    pub delegates: Vec<DelegatePowerResponse>,
+}
+
+/// The sum of the bonded balances delegated to a delegate.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DelegatePowerResponse {
+    pub address: String,
+    pub power: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -1,6 +1,8 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::Config;
+use crate::msg::{
+    DelegatePowerResponse, ExecuteMsg, InstantiateMsg, ListDelegatesResponse, MigrateMsg, QueryMsg,
+};
+use crate::state::{Config, BONDED_BALANCES};
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
 use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
 use cw_multi_test::{
@@ -9,7 +11,7 @@ use cw_multi_test::{
 use cwd_interface::voting::{
     BondingStatusResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
-use cwd_voting::vault::{BonderBalanceResponse, ListBondersResponse};
+use cwd_voting::vault::{BonderBalanceResponse, DelegationResponse, ListBondersResponse};
 
 const DAO_ADDR: &str = "dao";
 const NAME: &str = "name";
@@ -164,6 +166,46 @@ fn get_total_power_at_height(
         .unwrap()
 }
 
+fn delegate(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    to: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Delegate { to: to.to_string() },
+        &[],
+    )
+}
+
+fn undelegate(app: &mut App, contract_addr: Addr, sender: &str) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Undelegate {},
+        &[],
+    )
+}
+
+fn get_delegation(
+    app: &App,
+    contract_addr: &Addr,
+    address: &str,
+    height: Option<u64>,
+) -> DelegationResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::Delegation {
+                address: address.to_string(),
+                height,
+            },
+        )
+        .unwrap()
+}
+
 fn get_config(app: &mut App, contract_addr: Addr) -> Config {
     app.wrap()
         .query_wasm_smart(contract_addr, &QueryMsg::Config {})
@@ -723,6 +765,148 @@ fn test_query_list_bonders() {
     assert_eq!(bonders, ListBondersResponse { bonders: vec![] });
 }
 
+#[test]
+fn test_delegation() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+        },
+    );
+
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+    app.update_block(next_block);
+
+    // Can't delegate to yourself or undelegate without delegating.
+    let err = delegate(&mut app, addr.clone(), ADDR1, ADDR1).unwrap_err();
+    assert_eq!(
+        err.downcast::<crate::ContractError>().unwrap(),
+        crate::ContractError::SelfDelegation {}
+    );
+    let err = undelegate(&mut app, addr.clone(), ADDR1).unwrap_err();
+    assert_eq!(
+        err.downcast::<crate::ContractError>().unwrap(),
+        crate::ContractError::NotDelegated {}
+    );
+
+    // ADDR1 delegates to ADDR2, bonds some more and unbonds part of it.
+    delegate(&mut app, addr.clone(), ADDR1, ADDR2).unwrap();
+    app.update_block(next_block);
+    let delegated_height = app.block_info().height;
+    bond_tokens(&mut app, addr.clone(), ADDR1, 30, DENOM).unwrap();
+    unbond_tokens(&mut app, addr.clone(), ADDR1, 10).unwrap();
+    app.update_block(next_block);
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert!(resp.power.is_zero());
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(170));
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(resp.power, Uint128::new(170));
+    assert_eq!(
+        get_delegation(&app, &addr, ADDR1, None),
+        DelegationResponse {
+            delegate: Some(Addr::unchecked(ADDR2)),
+            power: Uint128::new(120),
+        }
+    );
+
+    // Past heights are not affected by later bonds.
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR2.to_string(),
+        Some(delegated_height),
+    );
+    assert_eq!(resp.power, Uint128::new(150));
+
+    // ADDR1 takes back the voting power.
+    undelegate(&mut app, addr.clone(), ADDR1).unwrap();
+    app.update_block(next_block);
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(120));
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(50));
+    assert_eq!(
+        get_delegation(&app, &addr, ADDR1, None),
+        DelegationResponse {
+            delegate: None,
+            power: Uint128::new(120),
+        }
+    );
+    assert_eq!(
+        get_delegation(&app, &addr, ADDR1, Some(delegated_height)).delegate,
+        Some(Addr::unchecked(ADDR2))
+    );
+
+    // Delegates without a bonded balance are not listed among the
+    // bonders, but among the delegates.
+    delegate(&mut app, addr.clone(), ADDR1, "delegate").unwrap();
+    app.update_block(next_block);
+    let resp: ListBondersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::ListBonders {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp.bonders
+            .into_iter()
+            .map(|bonder| (bonder.address, bonder.balance))
+            .collect::<Vec<_>>(),
+        vec![
+            (ADDR1.to_string(), Uint128::new(120)),
+            (ADDR2.to_string(), Uint128::new(50)),
+        ]
+    );
+    let list_delegates = |start_after: Option<&str>, limit| -> Vec<DelegatePowerResponse> {
+        let resp: ListDelegatesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::ListDelegates {
+                    start_after: start_after.map(str::to_string),
+                    limit,
+                },
+            )
+            .unwrap();
+        resp.delegates
+    };
+    // ADDR2 is still listed after ADDR1 took back the delegation.
+    let addr2 = DelegatePowerResponse {
+        address: ADDR2.to_string(),
+        power: Uint128::zero(),
+    };
+    let delegate_power = DelegatePowerResponse {
+        address: "delegate".to_string(),
+        power: Uint128::new(120),
+    };
+    assert_eq!(
+        list_delegates(None, None),
+        vec![addr2.clone(), delegate_power.clone()]
+    );
+    assert_eq!(list_delegates(None, Some(1)), vec![addr2]);
+    assert_eq!(list_delegates(Some(ADDR2), None), vec![delegate_power]);
+    assert!(!BONDED_BALANCES.has(
+        app.contract_storage(&addr).as_ref(),
+        &Addr::unchecked("delegate")
+    ));
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), "delegate".to_string(), None);
+    assert_eq!(resp.power, Uint128::new(120));
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -1,15 +1,23 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Uint128,
 };
 use cw2::set_contract_version;
 // use cw_controllers::ClaimsResponse;
 use cwd_interface::voting::{self, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cwd_voting::vault::{get_delegation, DelegatedPowerResponse};
 use neutron_vault::msg::QueryMsg as VaultQueryMsg;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VotingVault};
-use crate::state::{Config, CONFIG, DAO};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PowerBreakdownResponse, QueryMsg, VaultPower,
+    VaultWeight, VotingVault,
+};
+use crate::state::{
+    Config, LegacyConfig, LegacyVaults, PowerTransform, VotingVaultState, CONFIG, DAO,
+    LEGACY_CONFIG, LEGACY_VAULTS, POWER_TRANSFORMS, VOTING_VAULTS,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-voting-registry";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -17,7 +25,7 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -25,15 +33,17 @@ pub fn instantiate(
 
     let owner = deps.api.addr_validate(&msg.owner)?;
 
-    let mut voting_vaults: Vec<Addr> = vec![];
     for vault in msg.voting_vaults.iter() {
-        voting_vaults.push(deps.api.addr_validate(vault)?);
+        let vault = deps.api.addr_validate(vault)?;
+        VOTING_VAULTS.save(
+            deps.storage,
+            &vault,
+            &VotingVaultState::Active,
+            env.block.height,
+        )?;
     }
 
-    let config = Config {
-        owner,
-        voting_vaults,
-    };
+    let config = Config { owner };
 
     CONFIG.save(deps.storage, &config)?;
     DAO.save(deps.storage, &info.sender)?;
@@ -54,61 +64,110 @@ pub fn execute(
         ExecuteMsg::AddVotingVault {
             new_voting_vault_contract,
         } => execute_add_voting_vault(deps, env, info, new_voting_vault_contract),
-        ExecuteMsg::RemoveVotingVault {
-            old_voting_vault_contract,
-        } => execute_remove_voting_vault(deps, env, info, old_voting_vault_contract),
+        ExecuteMsg::ActivateVotingVault {
+            voting_vault_contract,
+        } => execute_activate_voting_vault(deps, env, info, voting_vault_contract),
+        ExecuteMsg::DeactivateVotingVault {
+            voting_vault_contract,
+        } => execute_deactivate_voting_vault(deps, env, info, voting_vault_contract),
         ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::UpdateVaultWeights { weights } => {
+            execute_update_vault_weights(deps, env, info, weights)
+        }
     }
 }
 
 pub fn execute_add_voting_vault(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_voting_vault_contact: String,
 ) -> Result<Response, ContractError> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
+    let config: Config = CONFIG.load(deps.storage)?;
 
     if info.sender != config.owner {
         return Err(ContractError::Unauthorized {});
     }
 
     let new_voting_vault = deps.api.addr_validate(&new_voting_vault_contact)?;
-    if !config.voting_vaults.contains(&new_voting_vault) {
-        config.voting_vaults.push(new_voting_vault);
-        CONFIG.save(deps.storage, &config)?;
-    } else {
+    if VOTING_VAULTS.has(deps.storage, &new_voting_vault) {
         return Err(ContractError::VotingVaultAlreadyExists {});
     }
+    VOTING_VAULTS.save(
+        deps.storage,
+        &new_voting_vault,
+        &VotingVaultState::Active,
+        env.block.height,
+    )?;
 
-    Ok(Response::new())
+    Ok(Response::new()
+        .add_attribute("action", "add_voting_vault")
+        .add_attribute("vault", new_voting_vault))
 }
 
-pub fn execute_remove_voting_vault(
+pub fn execute_activate_voting_vault(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    old_voting_vault_contact: String,
+    voting_vault_contract: String,
 ) -> Result<Response, ContractError> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
+    let config: Config = CONFIG.load(deps.storage)?;
 
     if info.sender != config.owner {
         return Err(ContractError::Unauthorized {});
     }
 
-    if config.voting_vaults.len() == 1 {
-        return Err(ContractError::RemoveLastVault {});
+    let voting_vault = deps.api.addr_validate(&voting_vault_contract)?;
+    match VOTING_VAULTS.may_load(deps.storage, &voting_vault)? {
+        None => return Err(ContractError::VotingVaultNotFound {}),
+        Some(VotingVaultState::Active) => return Err(ContractError::VotingVaultAlreadyActive {}),
+        Some(VotingVaultState::Inactive) => (),
+    }
+    VOTING_VAULTS.save(
+        deps.storage,
+        &voting_vault,
+        &VotingVaultState::Active,
+        env.block.height,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "activate_voting_vault")
+        .add_attribute("vault", voting_vault))
+}
+
+pub fn execute_deactivate_voting_vault(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    voting_vault_contract: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
     }
 
-    let new_voting_vault = deps.api.addr_validate(&old_voting_vault_contact)?;
-    if config.voting_vaults.contains(&new_voting_vault) {
-        config
-            .voting_vaults
-            .retain(|value| *value != old_voting_vault_contact);
-        CONFIG.save(deps.storage, &config)?;
+    let voting_vault = deps.api.addr_validate(&voting_vault_contract)?;
+    match VOTING_VAULTS.may_load(deps.storage, &voting_vault)? {
+        None => return Err(ContractError::VotingVaultNotFound {}),
+        Some(VotingVaultState::Inactive) => {
+            return Err(ContractError::VotingVaultAlreadyInactive {})
+        }
+        Some(VotingVaultState::Active) => (),
+    }
+    if load_active_vaults(deps.as_ref(), env.block.height + 1)?.len() == 1 {
+        return Err(ContractError::DeactivateLastVault {});
     }
+    VOTING_VAULTS.save(
+        deps.storage,
+        &voting_vault,
+        &VotingVaultState::Inactive,
+        env.block.height,
+    )?;
 
-    Ok(Response::new())
+    Ok(Response::new()
+        .add_attribute("action", "deactivate_voting_vault")
+        .add_attribute("vault", voting_vault))
 }
 
 pub fn execute_update_config(
@@ -131,6 +190,29 @@ pub fn execute_update_config(
         .add_attribute("owner", config.owner))
 }
 
+pub fn execute_update_vault_weights(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    weights: Vec<VaultWeight>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for VaultWeight { vault, transform } in weights {
+        let vault = deps.api.addr_validate(&vault)?;
+        if !VOTING_VAULTS.has(deps.storage, &vault) {
+            return Err(ContractError::VotingVaultNotFound {});
+        }
+        transform.validate()?;
+        POWER_TRANSFORMS.save(deps.storage, &vault, &transform, env.block.height)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_vault_weights"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -140,16 +222,30 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::TotalPowerAtHeight { height } => {
             to_binary(&query_total_power_at_height(deps, env, height)?)
         }
+        QueryMsg::VotingPowerBreakdownAtHeight { address, height } => to_binary(
+            &query_voting_power_breakdown_at_height(deps, env, address, height)?,
+        ),
+        QueryMsg::TotalPowerBreakdownAtHeight { height } => {
+            to_binary(&query_total_power_breakdown_at_height(deps, env, height)?)
+        }
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::VotingVaults {} => to_binary(&query_voting_vaults(deps)?),
+        QueryMsg::VaultWeights {} => to_binary(&query_vault_weights(deps)?),
+        QueryMsg::DelegatedPowerAtHeight {
+            vault,
+            address,
+            height,
+        } => to_binary(&query_delegated_power_at_height(
+            deps, env, vault, address, height,
+        )?),
     }
 }
 
 pub fn query_voting_vaults(deps: Deps) -> StdResult<Vec<VotingVault>> {
     let mut voting_vaults: Vec<VotingVault> = vec![];
-    for vault in CONFIG.load(deps.storage)?.voting_vaults.iter() {
+    for (vault, state) in load_vaults(deps)? {
         let vault_description: String = deps
             .querier
             .query_wasm_smart(vault, &VaultQueryMsg::Description {})?;
@@ -161,35 +257,79 @@ pub fn query_voting_vaults(deps: Deps) -> StdResult<Vec<VotingVault>> {
             address: vault.to_string(),
             name: vault_name,
             description: vault_description,
+            state,
         });
     }
 
     Ok(voting_vaults)
 }
 
+pub fn query_vault_weights(deps: Deps) -> StdResult<Vec<VaultWeight>> {
+    load_vaults(deps)?
+        .into_iter()
+        .map(|(vault, _)| {
+            Ok(VaultWeight {
+                transform: POWER_TRANSFORMS
+                    .may_load(deps.storage, &vault)?
+                    .unwrap_or(PowerTransform::Linear {}),
+                vault: vault.into_string(),
+            })
+        })
+        .collect()
+}
+
+/// Loads every vault ever added to the registry with its current
+/// state.
+fn load_vaults(deps: Deps) -> StdResult<Vec<(Addr, VotingVaultState)>> {
+    VOTING_VAULTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// Loads the vaults which were active at `height`.
+fn load_active_vaults(deps: Deps, height: u64) -> StdResult<Vec<Addr>> {
+    let mut active_vaults = vec![];
+    for vault in VOTING_VAULTS.keys(deps.storage, None, None, Order::Ascending) {
+        let vault = vault?;
+        if load_vault_state(deps, &vault, height)? == Some(VotingVaultState::Active) {
+            active_vaults.push(vault);
+        }
+    }
+    Ok(active_vaults)
+}
+
+/// Loads the state of `vault` at `height`. The vaults migrated from
+/// the config are active at the heights before the migration.
+fn load_vault_state(deps: Deps, vault: &Addr, height: u64) -> StdResult<Option<VotingVaultState>> {
+    if let Some(legacy) = LEGACY_VAULTS.may_load(deps.storage)? {
+        if height <= legacy.height && legacy.vaults.contains(vault) {
+            return Ok(Some(VotingVaultState::Active));
+        }
+    }
+    VOTING_VAULTS.may_load_at_height(deps.storage, vault, height)
+}
+
+/// Loads the power transform of `vault` at `height`.
+fn load_power_transform(deps: Deps, vault: &Addr, height: u64) -> StdResult<PowerTransform> {
+    Ok(POWER_TRANSFORMS
+        .may_load_at_height(deps.storage, vault, height)?
+        .unwrap_or(PowerTransform::Linear {}))
+}
+
 pub fn query_voting_power_at_height(
     deps: Deps,
-    _env: Env,
+    env: Env,
     address: String,
     height: Option<u64>,
 ) -> StdResult<VotingPowerAtHeightResponse> {
-    let voting_vaults = CONFIG.load(deps.storage)?.voting_vaults;
+    let height = height.unwrap_or(env.block.height);
     let mut total_power = VotingPowerAtHeightResponse {
         power: Default::default(),
-        height: Default::default(),
+        height,
     };
-    for vault in voting_vaults.iter() {
-        let total_power_single_vault: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
-            vault,
-            &voting::Query::VotingPowerAtHeight {
-                height,
-                address: address.clone(),
-            },
-        )?;
-        total_power.power = total_power
-            .power
-            .checked_add(total_power_single_vault.power)?;
-        total_power.height = total_power_single_vault.height;
+    for vault in load_active_vaults(deps, height)?.iter() {
+        let power = query_vault_voting_power(deps, vault, &address, height)?;
+        total_power.power = total_power.power.checked_add(power)?;
     }
 
     Ok(total_power)
@@ -197,28 +337,130 @@ pub fn query_voting_power_at_height(
 
 pub fn query_total_power_at_height(
     deps: Deps,
-    _env: Env,
+    env: Env,
     height: Option<u64>,
 ) -> StdResult<TotalPowerAtHeightResponse> {
-    let voting_vaults = CONFIG.load(deps.storage)?.voting_vaults;
-
+    let height = height.unwrap_or(env.block.height);
     let mut total_power: TotalPowerAtHeightResponse = TotalPowerAtHeightResponse {
         power: Default::default(),
-        height: Default::default(),
+        height,
     };
-    for vault in voting_vaults.iter() {
-        let total_power_single_vault: TotalPowerAtHeightResponse = deps
-            .querier
-            .query_wasm_smart(vault, &voting::Query::TotalPowerAtHeight { height })?;
-        total_power.power = total_power
-            .power
-            .checked_add(total_power_single_vault.power)?;
-        total_power.height = total_power_single_vault.height;
+    for vault in load_active_vaults(deps, height)?.iter() {
+        let power = query_vault_total_power(deps, vault, height)?;
+        total_power.power = total_power.power.checked_add(power)?;
     }
 
     Ok(total_power)
 }
 
+pub fn query_voting_power_breakdown_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<PowerBreakdownResponse> {
+    let height = height.unwrap_or(env.block.height);
+    query_power_breakdown(deps, height, |vault| {
+        query_vault_voting_power(deps, vault, &address, height)
+    })
+}
+
+pub fn query_total_power_breakdown_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<PowerBreakdownResponse> {
+    let height = height.unwrap_or(env.block.height);
+    query_power_breakdown(deps, height, |vault| {
+        query_vault_total_power(deps, vault, height)
+    })
+}
+
+pub fn query_delegated_power_at_height(
+    deps: Deps,
+    env: Env,
+    vault: String,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<DelegatedPowerResponse> {
+    let height = height.unwrap_or(env.block.height);
+    let vault = deps.api.addr_validate(&vault)?;
+    let address = deps.api.addr_validate(&address)?;
+    let mut response = DelegatedPowerResponse {
+        delegate: None,
+        power: Uint128::zero(),
+        height,
+    };
+    if load_vault_state(deps, &vault, height)? != Some(VotingVaultState::Active) {
+        return Ok(response);
+    }
+
+    let delegation = get_delegation(deps, &vault, &address, height)?;
+    if let Some(delegate) = delegation.delegate {
+        response.power = load_power_transform(deps, &vault, height)?.apply(delegation.power);
+        response.delegate = Some(delegate);
+    }
+
+    Ok(response)
+}
+
+/// Collects the power `vault_power` returns for each vault active at
+/// `height` along with the name of the vault.
+fn query_power_breakdown(
+    deps: Deps,
+    height: u64,
+    vault_power: impl Fn(&Addr) -> StdResult<Uint128>,
+) -> StdResult<PowerBreakdownResponse> {
+    let mut breakdown = PowerBreakdownResponse {
+        vaults: vec![],
+        power: Uint128::zero(),
+        height,
+    };
+    for vault in load_active_vaults(deps, height)? {
+        let power = vault_power(&vault)?;
+        let name: String = deps
+            .querier
+            .query_wasm_smart(&vault, &VaultQueryMsg::Name {})?;
+        breakdown.power = breakdown.power.checked_add(power)?;
+        breakdown.vaults.push(VaultPower {
+            address: vault.into_string(),
+            name,
+            power,
+        });
+    }
+
+    Ok(breakdown)
+}
+
+/// Queries the transformed voting power `address` has in `vault` at
+/// `height`.
+fn query_vault_voting_power(
+    deps: Deps,
+    vault: &Addr,
+    address: &str,
+    height: u64,
+) -> StdResult<Uint128> {
+    let vault_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        vault,
+        &voting::Query::VotingPowerAtHeight {
+            height: Some(height),
+            address: address.to_string(),
+        },
+    )?;
+    Ok(load_power_transform(deps, vault, height)?.apply(vault_power.power))
+}
+
+/// Queries the transformed total power in `vault` at `height`.
+fn query_vault_total_power(deps: Deps, vault: &Addr, height: u64) -> StdResult<Uint128> {
+    let vault_power: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        vault,
+        &voting::Query::TotalPowerAtHeight {
+            height: Some(height),
+        },
+    )?;
+    Ok(load_power_transform(deps, vault, height)?.apply(vault_power.power))
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&voting::InfoResponse { info })
@@ -230,7 +472,34 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Previous versions stored the list of vaults in the config. They
+    // are moved to the snapshotted vaults as active vaults.
+    if let Some(LegacyConfig {
+        owner,
+        voting_vaults: Some(voting_vaults),
+    }) = LEGACY_CONFIG.may_load(deps.storage)?
+    {
+        for vault in &voting_vaults {
+            VOTING_VAULTS.save(
+                deps.storage,
+                vault,
+                &VotingVaultState::Active,
+                env.block.height,
+            )?;
+        }
+        // The snapshots only record the vaults from the migration on,
+        // so the vaults are remembered to keep past heights queryable.
+        LEGACY_VAULTS.save(
+            deps.storage,
+            &LegacyVaults {
+                height: env.block.height,
+                vaults: voting_vaults,
+            },
+        )?;
+        CONFIG.save(deps.storage, &Config { owner })?;
+    }
+
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
@@ -16,6 +16,18 @@ pub enum ContractError {
     #[error("Voting vault already exists")]
     VotingVaultAlreadyExists {},
 
-    #[error("Removing last vault is forbidden")]
-    RemoveLastVault {},
+    #[error("Voting vault is already active")]
+    VotingVaultAlreadyActive {},
+
+    #[error("Voting vault is already inactive")]
+    VotingVaultAlreadyInactive {},
+
+    #[error("Deactivating last active vault is forbidden")]
+    DeactivateLastVault {},
+
+    #[error("Voting vault does not exist")]
+    VotingVaultNotFound {},
+
+    #[error("Power transform must not zero the voting power")]
+    InvalidPowerTransform {},
 }
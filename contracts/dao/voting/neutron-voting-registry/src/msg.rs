@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint128};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
@@ -7,6 +7,8 @@ use cwd_macros::{info_query, voting_query};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{PowerTransform, VotingVaultState};
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct InstantiateMsg {
     // Owner can update all configs including changing the owner. This will generally be a DAO.
@@ -18,9 +20,28 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    AddVotingVault { new_voting_vault_contract: String },
-    RemoveVotingVault { old_voting_vault_contract: String },
-    UpdateConfig { owner: String },
+    AddVotingVault {
+        new_voting_vault_contract: String,
+    },
+    /// Starts counting the voting power in a previously deactivated
+    /// vault. Only the owner may call this method.
+    ActivateVotingVault {
+        voting_vault_contract: String,
+    },
+    /// Stops counting the voting power in a vault from the next block
+    /// on. Queries at earlier heights still count it. Only the owner
+    /// may call this method.
+    DeactivateVotingVault {
+        voting_vault_contract: String,
+    },
+    UpdateConfig {
+        owner: String,
+    },
+    /// Sets the power transforms of voting vaults. Only the owner may
+    /// call this method.
+    UpdateVaultWeights {
+        weights: Vec<VaultWeight>,
+    },
 }
 
 #[voting_query]
@@ -34,6 +55,31 @@ pub enum QueryMsg {
     Config {},
     #[returns(Vec<VotingVault>)]
     VotingVaults {},
+    /// Returns the voting power of an address at a height along with
+    /// the power contributed by each vault active at the height.
+    #[returns(PowerBreakdownResponse)]
+    VotingPowerBreakdownAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Returns the total power at a height along with the power
+    /// contributed by each vault active at the height.
+    #[returns(PowerBreakdownResponse)]
+    TotalPowerBreakdownAtHeight { height: Option<u64> },
+    /// Lists the power transform of each voting vault.
+    #[returns(Vec<VaultWeight>)]
+    VaultWeights {},
+    /// Returns the delegate of an address in a vault supporting
+    /// delegation at a height, along with the voting power the
+    /// delegation adds to the delegate after the power transform of
+    /// the vault is applied. Nothing is delegated in vaults inactive
+    /// at the height.
+    #[returns(cwd_voting::vault::DelegatedPowerResponse)]
+    DelegatedPowerAtHeight {
+        vault: String,
+        address: String,
+        height: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -44,4 +90,30 @@ pub struct VotingVault {
     pub address: String,
     pub name: String,
     pub description: String,
+    pub state: VotingVaultState,
+}
+
+/// The power transform applied to the voting power in a vault.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VaultWeight {
+    pub vault: String,
+    pub transform: PowerTransform,
+}
+
+/// The voting power contributed by a vault, after its power transform
+/// is applied.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VaultPower {
+    pub address: String,
+    pub name: String,
+    pub power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PowerBreakdownResponse {
+    /// The power contributed by each vault active at the height.
+    pub vaults: Vec<VaultPower>,
+    /// The sum of the power contributed by the vaults.
+    pub power: Uint128,
+    pub height: u64,
 }
@@ -1,13 +1,112 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, SnapshotMap, Strategy};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::error::ContractError;
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct Config {
     pub owner: Addr,
-    pub voting_vaults: Vec<Addr>,
+}
+
+/// The config as stored by previous versions of the contract, which
+/// kept the list of voting vaults in it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct LegacyConfig {
+    pub owner: Addr,
+    pub voting_vaults: Option<Vec<Addr>>,
+}
+
+/// Whether the voting power in a vault is counted by the registry.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingVaultState {
+    Active,
+    Inactive,
+}
+
+/// A transformation applied to the voting power each address has in
+/// a vault before it is summed with the power from the other vaults.
+/// Only transforms for which the transformed power of a sum is the sum
+/// of the transformed powers, up to rounding, are supported, so that
+/// the total power of a vault may be transformed directly.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerTransform {
+    /// The power is counted as is.
+    Linear {},
+    /// The power is multiplied by `factor`, rounding down.
+    Multiplier { factor: Decimal },
+}
+
+impl PowerTransform {
+    /// Checks that the transform does not zero all voting power.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        match self {
+            PowerTransform::Multiplier { factor } if factor.is_zero() => {
+                Err(ContractError::InvalidPowerTransform {})
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Applies the transform to `power`.
+    pub fn apply(&self, power: Uint128) -> Uint128 {
+        match self {
+            PowerTransform::Linear {} => power,
+            PowerTransform::Multiplier { factor } => power * *factor,
+        }
+    }
+}
+
+/// The vaults listed in the config by previous versions of the
+/// contract, moved to `VOTING_VAULTS` by the migration at `height`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct LegacyVaults {
+    pub height: u64,
+    pub vaults: Vec<Addr>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+/// The state of every vault ever added to the registry, snapshotted so
+/// that queries at past heights only count the vaults active then.
+pub const VOTING_VAULTS: SnapshotMap<&Addr, VotingVaultState> = SnapshotMap::new(
+    "voting_vaults",
+    "voting_vaults__checkpoints",
+    "voting_vaults__changelog",
+    Strategy::EveryBlock,
+);
+/// The vaults active before the migration to `VOTING_VAULTS`, which
+/// has no history below the migration height.
+pub const LEGACY_VAULTS: Item<LegacyVaults> = Item::new("legacy_vaults");
 pub const DAO: Item<Addr> = Item::new("dao");
+/// The power transform of each vault, snapshotted so that queries at
+/// past heights apply the transforms in place then. Vaults without one
+/// count their power linearly.
+pub const POWER_TRANSFORMS: SnapshotMap<&Addr, PowerTransform> = SnapshotMap::new(
+    "power_transforms",
+    "power_transforms__checkpoints",
+    "power_transforms__changelog",
+    Strategy::EveryBlock,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::PowerTransform;
+    use cosmwasm_std::{Decimal, Uint128};
+
+    #[test]
+    fn test_power_transform_apply() {
+        let power = Uint128::new(1_000_000);
+        assert_eq!(PowerTransform::Linear {}.apply(power), power);
+        assert_eq!(
+            PowerTransform::Multiplier {
+                factor: Decimal::percent(150)
+            }
+            .apply(power),
+            Uint128::new(1_500_000)
+        );
+    }
+}
@@ -1,16 +1,30 @@
-use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::Config;
+use crate::contract::{migrate, query, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PowerBreakdownResponse, QueryMsg, VaultPower,
+    VaultWeight, VotingVault,
+};
+use crate::state::{
+    Config, LegacyConfig, PowerTransform, VotingVaultState, CONFIG, LEGACY_CONFIG, VOTING_VAULTS,
+};
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{Addr, Coin, Empty, Uint128};
-use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
-use cwd_interface::voting::InfoResponse;
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, Coin, ContractResult, Decimal, Empty, SystemResult,
+    Uint128, WasmQuery,
+};
+use cw_multi_test::{
+    custom_app, next_block, App, AppResponse, Contract, ContractWrapper, Executor,
+};
+use cwd_interface::voting::{
+    self, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cwd_voting::vault::DelegatedPowerResponse;
 
 const DAO_ADDR: &str = "dao";
 const VAULT_ADDR: &str = "vault";
 const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 const DENOM: &str = "ujuno";
+const DESCRIPTION: &str = "description";
 const INVALID_DENOM: &str = "uinvalid";
 const INIT_BALANCE: Uint128 = Uint128::new(10000);
 
@@ -23,6 +37,15 @@ fn vault_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+fn neutron_vault_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        neutron_vault::contract::execute,
+        neutron_vault::contract::instantiate,
+        neutron_vault::contract::query,
+    );
+    Box::new(contract)
+}
+
 fn mock_app() -> App {
     custom_app(|r, _a, s| {
         r.bank
@@ -111,22 +134,115 @@ fn add_vault(
     )
 }
 
-fn remove_vault(
+fn activate_vault(
     app: &mut App,
     contract_addr: Addr,
     sender: &str,
-    old_voting_vault_contract: String,
+    voting_vault_contract: String,
 ) -> anyhow::Result<AppResponse> {
     app.execute_contract(
         Addr::unchecked(sender),
         contract_addr,
-        &ExecuteMsg::RemoveVotingVault {
-            old_voting_vault_contract,
+        &ExecuteMsg::ActivateVotingVault {
+            voting_vault_contract,
         },
         &[],
     )
 }
 
+fn deactivate_vault(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    voting_vault_contract: String,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::DeactivateVotingVault {
+            voting_vault_contract,
+        },
+        &[],
+    )
+}
+
+fn instantiate_neutron_vault(app: &mut App, id: u64, name: &str) -> Addr {
+    app.instantiate_contract(
+        id,
+        Addr::unchecked(DAO_ADDR),
+        &neutron_vault::msg::InstantiateMsg {
+            name: name.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+        },
+        &[],
+        name,
+        None,
+    )
+    .unwrap()
+}
+
+fn bond_tokens(app: &mut App, vault: &Addr, sender: &str, amount: u128) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        vault.clone(),
+        &neutron_vault::msg::ExecuteMsg::Bond {},
+        &coins(amount, DENOM),
+    )
+    .unwrap();
+}
+
+fn update_vault_weights(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    weights: Vec<VaultWeight>,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::UpdateVaultWeights { weights },
+        &[],
+    )
+}
+
+fn get_voting_power(app: &App, contract_addr: &Addr, address: &str) -> Uint128 {
+    let resp: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::VotingPowerAtHeight {
+                address: address.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    resp.power
+}
+
+fn get_total_power(app: &App, contract_addr: &Addr) -> Uint128 {
+    get_total_power_at_height(app, contract_addr, None)
+}
+
+fn get_total_power_at_height(app: &App, contract_addr: &Addr, height: Option<u64>) -> Uint128 {
+    let resp: TotalPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap();
+    resp.power
+}
+
+fn get_vault_states(app: &App, contract_addr: &Addr) -> Vec<(String, VotingVaultState)> {
+    let resp: Vec<VotingVault> = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::VotingVaults {})
+        .unwrap();
+    resp.into_iter()
+        .map(|vault| (vault.address, vault.state))
+        .collect()
+}
+
 fn get_config(app: &mut App, contract_addr: Addr) -> Config {
     app.wrap()
         .query_wasm_smart(contract_addr, &QueryMsg::Config {})
@@ -150,20 +266,25 @@ fn test_instantiate() {
 #[test]
 fn test_instantiate_multiple_vaults() {
     let mut app = mock_app();
-    let vault_id = app.store_code(vault_contract());
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let first_vault = instantiate_neutron_vault(&mut app, vault_id, "first");
+    let second_vault = instantiate_neutron_vault(&mut app, vault_id, "second");
     // Populated fields
     let addr = instantiate_voting_registry(
         &mut app,
-        vault_id,
+        registry_id,
         InstantiateMsg {
             owner: DAO_ADDR.to_string(),
-            voting_vaults: vec![VAULT_ADDR.to_string(), String::from("another_vault")],
+            voting_vaults: vec![first_vault.to_string(), second_vault.to_string()],
         },
     );
-    let config = get_config(&mut app, addr);
     assert_eq!(
-        config.voting_vaults,
-        vec![VAULT_ADDR.to_string(), String::from("another_vault")]
+        get_vault_states(&app, &addr),
+        vec![
+            (first_vault.to_string(), VotingVaultState::Active),
+            (second_vault.to_string(), VotingVaultState::Active),
+        ]
     );
 }
 
@@ -205,7 +326,6 @@ fn test_update_config_as_owner() {
     assert_eq!(
         Config {
             owner: Addr::unchecked(ADDR1),
-            voting_vaults: vec![Addr::unchecked(VAULT_ADDR)]
         },
         config
     );
@@ -265,7 +385,6 @@ fn test_query_get_config() {
         config,
         Config {
             owner: Addr::unchecked(DAO_ADDR),
-            voting_vaults: vec![Addr::unchecked(VAULT_ADDR)]
         }
     )
 }
@@ -273,62 +392,89 @@ fn test_query_get_config() {
 #[test]
 fn test_add_vault_owner() {
     let mut app = mock_app();
-    let vault_id = app.store_code(vault_contract());
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let first_vault = instantiate_neutron_vault(&mut app, vault_id, "first");
+    let new_vault = instantiate_neutron_vault(&mut app, vault_id, "new");
     let addr = instantiate_voting_registry(
         &mut app,
-        vault_id,
+        registry_id,
         InstantiateMsg {
             owner: DAO_ADDR.to_string(),
-            voting_vaults: vec![VAULT_ADDR.to_string()],
+            voting_vaults: vec![first_vault.to_string()],
         },
     );
 
-    let new_vault: &str = "new_vault";
     add_vault(&mut app, addr.clone(), DAO_ADDR, new_vault.to_string()).unwrap();
-
-    let config = get_config(&mut app, addr);
     assert_eq!(
-        config,
-        Config {
-            owner: Addr::unchecked(DAO_ADDR),
-            voting_vaults: vec![Addr::unchecked(VAULT_ADDR), Addr::unchecked(new_vault)]
-        }
-    )
+        get_vault_states(&app, &addr),
+        vec![
+            (first_vault.to_string(), VotingVaultState::Active),
+            (new_vault.to_string(), VotingVaultState::Active),
+        ]
+    );
+
+    let err = add_vault(&mut app, addr, DAO_ADDR, new_vault.to_string()).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Voting vault already exists");
 }
 
 #[test]
-fn test_remove_vault_owner() {
+fn test_deactivate_vault_owner() {
     let mut app = mock_app();
-    let vault_id = app.store_code(vault_contract());
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let first_vault = instantiate_neutron_vault(&mut app, vault_id, "first");
+    let new_vault = instantiate_neutron_vault(&mut app, vault_id, "new");
     let addr = instantiate_voting_registry(
         &mut app,
-        vault_id,
+        registry_id,
         InstantiateMsg {
             owner: DAO_ADDR.to_string(),
-            voting_vaults: vec![VAULT_ADDR.to_string()],
+            voting_vaults: vec![first_vault.to_string()],
         },
     );
-
-    let new_vault: &str = "new_vault";
     add_vault(&mut app, addr.clone(), DAO_ADDR, new_vault.to_string()).unwrap();
 
-    let config = get_config(&mut app, addr.clone());
+    let err = deactivate_vault(&mut app, addr.clone(), ADDR1, first_vault.to_string()).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    deactivate_vault(&mut app, addr.clone(), DAO_ADDR, first_vault.to_string()).unwrap();
     assert_eq!(
-        config,
-        Config {
-            owner: Addr::unchecked(DAO_ADDR),
-            voting_vaults: vec![Addr::unchecked(VAULT_ADDR), Addr::unchecked(new_vault)]
-        }
+        get_vault_states(&app, &addr),
+        vec![
+            (first_vault.to_string(), VotingVaultState::Inactive),
+            (new_vault.to_string(), VotingVaultState::Active),
+        ]
     );
 
-    remove_vault(&mut app, addr.clone(), DAO_ADDR, VAULT_ADDR.to_string()).unwrap();
-    let config = get_config(&mut app, addr);
+    let err =
+        deactivate_vault(&mut app, addr.clone(), DAO_ADDR, first_vault.to_string()).unwrap_err();
     assert_eq!(
-        config,
-        Config {
-            owner: Addr::unchecked(DAO_ADDR),
-            voting_vaults: vec![Addr::unchecked(new_vault)]
-        }
+        err.root_cause().to_string(),
+        "Voting vault is already inactive"
+    );
+    let err = deactivate_vault(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        "unknown_vault".to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Voting vault does not exist");
+
+    activate_vault(&mut app, addr.clone(), DAO_ADDR, first_vault.to_string()).unwrap();
+    let err =
+        activate_vault(&mut app, addr.clone(), DAO_ADDR, first_vault.to_string()).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Voting vault is already active"
+    );
+    assert_eq!(
+        get_vault_states(&app, &addr),
+        vec![
+            (first_vault.to_string(), VotingVaultState::Active),
+            (new_vault.to_string(), VotingVaultState::Active),
+        ]
     );
 }
 
@@ -347,34 +493,463 @@ fn test_add_vault_manager() {
     );
 
     let new_vault: &str = "new_vault";
-    add_vault(&mut app, addr.clone(), ADDR1, new_vault.to_string()).unwrap();
+    add_vault(&mut app, addr, ADDR1, new_vault.to_string()).unwrap();
+}
 
-    let config = get_config(&mut app, addr);
+#[test]
+#[should_panic(expected = "Deactivating last active vault is forbidden")]
+fn test_deactivate_last_vault_owner() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_voting_registry(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            owner: DAO_ADDR.to_string(),
+            voting_vaults: vec![VAULT_ADDR.to_string()],
+        },
+    );
+
+    deactivate_vault(&mut app, addr, DAO_ADDR, VAULT_ADDR.to_string()).unwrap();
+}
+
+#[test]
+fn test_vault_activation_history() {
+    let mut app = mock_app();
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let bonded_vault = instantiate_neutron_vault(&mut app, vault_id, "bonded");
+    let lockdrop_vault = instantiate_neutron_vault(&mut app, vault_id, "lockdrop");
+    let addr = instantiate_voting_registry(
+        &mut app,
+        registry_id,
+        InstantiateMsg {
+            owner: DAO_ADDR.to_string(),
+            voting_vaults: vec![bonded_vault.to_string(), lockdrop_vault.to_string()],
+        },
+    );
+
+    bond_tokens(&mut app, &bonded_vault, ADDR1, 100);
+    bond_tokens(&mut app, &lockdrop_vault, ADDR1, 40);
+    app.update_block(next_block);
+    let both_active_height = app.block_info().height;
+    assert_eq!(get_voting_power(&app, &addr, ADDR1), Uint128::new(140));
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(140));
+
+    // The vault is still counted in the block it is deactivated in.
+    deactivate_vault(&mut app, addr.clone(), DAO_ADDR, lockdrop_vault.to_string()).unwrap();
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(140));
+    app.update_block(next_block);
+    let deactivated_height = app.block_info().height;
+    assert_eq!(get_voting_power(&app, &addr, ADDR1), Uint128::new(100));
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(100));
+
+    // Past heights keep counting the vault as it was active then.
     assert_eq!(
-        config,
-        Config {
-            owner: Addr::unchecked(DAO_ADDR),
-            voting_vaults: vec![Addr::unchecked(VAULT_ADDR)]
+        get_total_power_at_height(&app, &addr, Some(both_active_height)),
+        Uint128::new(140)
+    );
+    let resp: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(both_active_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        VotingPowerAtHeightResponse {
+            power: Uint128::new(140),
+            height: both_active_height,
         }
+    );
+
+    activate_vault(&mut app, addr.clone(), DAO_ADDR, lockdrop_vault.to_string()).unwrap();
+    app.update_block(next_block);
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(140));
+    assert_eq!(
+        get_total_power_at_height(&app, &addr, Some(deactivated_height)),
+        Uint128::new(100)
+    );
+}
+
+#[test]
+fn test_power_transforms() {
+    let mut app = mock_app();
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let bonded_vault = instantiate_neutron_vault(&mut app, vault_id, "bonded");
+    let lockdrop_vault = instantiate_neutron_vault(&mut app, vault_id, "lockdrop");
+    let addr = instantiate_voting_registry(
+        &mut app,
+        registry_id,
+        InstantiateMsg {
+            owner: DAO_ADDR.to_string(),
+            voting_vaults: vec![bonded_vault.to_string(), lockdrop_vault.to_string()],
+        },
+    );
+
+    bond_tokens(&mut app, &bonded_vault, ADDR1, 100);
+    bond_tokens(&mut app, &bonded_vault, ADDR2, 25);
+    bond_tokens(&mut app, &lockdrop_vault, ADDR1, 40);
+    app.update_block(next_block);
+
+    // Without transforms power is summed as is.
+    assert_eq!(get_voting_power(&app, &addr, ADDR1), Uint128::new(140));
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(165));
+
+    let weights = vec![
+        VaultWeight {
+            vault: bonded_vault.to_string(),
+            transform: PowerTransform::Multiplier {
+                factor: Decimal::percent(200),
+            },
+        },
+        VaultWeight {
+            vault: lockdrop_vault.to_string(),
+            transform: PowerTransform::Multiplier {
+                factor: Decimal::percent(50),
+            },
+        },
+    ];
+    let err = update_vault_weights(&mut app, addr.clone(), ADDR1, weights.clone()).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    update_vault_weights(&mut app, addr.clone(), DAO_ADDR, weights.clone()).unwrap();
+
+    let resp: Vec<VaultWeight> = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::VaultWeights {})
+        .unwrap();
+    assert_eq!(resp, weights);
+
+    // 100 * 2 + 40 * 0.5
+    assert_eq!(get_voting_power(&app, &addr, ADDR1), Uint128::new(220));
+    // 25 * 2
+    assert_eq!(get_voting_power(&app, &addr, ADDR2), Uint128::new(50));
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(270));
+
+    app.update_block(next_block);
+    let doubled_height = app.block_info().height;
+    update_vault_weights(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![VaultWeight {
+            vault: bonded_vault.to_string(),
+            transform: PowerTransform::Linear {},
+        }],
+    )
+    .unwrap();
+    assert_eq!(get_voting_power(&app, &addr, ADDR1), Uint128::new(120));
+    assert_eq!(get_voting_power(&app, &addr, ADDR2), Uint128::new(25));
+    assert_eq!(get_total_power(&app, &addr), Uint128::new(145));
+    // Past heights keep the transforms in place then.
+    assert_eq!(
+        get_total_power_at_height(&app, &addr, Some(doubled_height)),
+        Uint128::new(270)
+    );
+    let resp: VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::VotingPowerAtHeight {
+                address: ADDR1.to_string(),
+                height: Some(doubled_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.power, Uint128::new(220));
+
+    let err = update_vault_weights(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![VaultWeight {
+            vault: bonded_vault.to_string(),
+            transform: PowerTransform::Multiplier {
+                factor: Decimal::zero(),
+            },
+        }],
     )
+    .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Power transform must not zero the voting power"
+    );
+    let err = update_vault_weights(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        vec![VaultWeight {
+            vault: "unknown_vault".to_string(),
+            transform: PowerTransform::Linear {},
+        }],
+    )
+    .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Voting vault does not exist");
 }
 
 #[test]
-#[should_panic(expected = "Removing last vault is forbidden")]
-fn test_remove_last_vault_owner() {
+fn test_delegated_power() {
     let mut app = mock_app();
-    let vault_id = app.store_code(vault_contract());
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let bonded_vault = instantiate_neutron_vault(&mut app, vault_id, "bonded");
+    let lockdrop_vault = instantiate_neutron_vault(&mut app, vault_id, "lockdrop");
     let addr = instantiate_voting_registry(
         &mut app,
-        vault_id,
+        registry_id,
         InstantiateMsg {
             owner: DAO_ADDR.to_string(),
-            voting_vaults: vec![VAULT_ADDR.to_string()],
+            voting_vaults: vec![bonded_vault.to_string(), lockdrop_vault.to_string()],
         },
     );
+    update_vault_weights(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![VaultWeight {
+            vault: bonded_vault.to_string(),
+            transform: PowerTransform::Multiplier {
+                factor: Decimal::percent(50),
+            },
+        }],
+    )
+    .unwrap();
 
-    let new_vault: &str = "new_vault";
-    remove_vault(&mut app, addr, DAO_ADDR, new_vault.to_string()).unwrap();
+    bond_tokens(&mut app, &bonded_vault, ADDR1, 64);
+    bond_tokens(&mut app, &bonded_vault, ADDR2, 36);
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        bonded_vault.clone(),
+        &neutron_vault::msg::ExecuteMsg::Delegate {
+            to: ADDR2.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let get_delegated_power = |app: &App, address: &str| -> DelegatedPowerResponse {
+        app.wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::DelegatedPowerAtHeight {
+                    vault: bonded_vault.to_string(),
+                    address: address.to_string(),
+                    height: None,
+                },
+            )
+            .unwrap()
+    };
+
+    // The delegation adds 64 * 0.5 to the delegate.
+    assert_eq!(get_voting_power(&app, &addr, ADDR2), Uint128::new(50));
+    assert_eq!(
+        get_delegated_power(&app, ADDR1),
+        DelegatedPowerResponse {
+            delegate: Some(Addr::unchecked(ADDR2)),
+            power: Uint128::new(32),
+            height: app.block_info().height,
+        }
+    );
+    assert_eq!(
+        get_delegated_power(&app, ADDR2),
+        DelegatedPowerResponse {
+            delegate: None,
+            power: Uint128::zero(),
+            height: app.block_info().height,
+        }
+    );
+
+    // Nothing is delegated in inactive vaults.
+    deactivate_vault(&mut app, addr.clone(), DAO_ADDR, bonded_vault.to_string()).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_delegated_power(&app, ADDR1),
+        DelegatedPowerResponse {
+            delegate: None,
+            power: Uint128::zero(),
+            height: app.block_info().height,
+        }
+    );
+}
+
+#[test]
+fn test_power_breakdown() {
+    let mut app = mock_app();
+    let registry_id = app.store_code(vault_contract());
+    let vault_id = app.store_code(neutron_vault_contract());
+    let bonded_vault = instantiate_neutron_vault(&mut app, vault_id, "bonded");
+    let lockdrop_vault = instantiate_neutron_vault(&mut app, vault_id, "lockdrop");
+    let addr = instantiate_voting_registry(
+        &mut app,
+        registry_id,
+        InstantiateMsg {
+            owner: DAO_ADDR.to_string(),
+            voting_vaults: vec![bonded_vault.to_string(), lockdrop_vault.to_string()],
+        },
+    );
+    update_vault_weights(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        vec![VaultWeight {
+            vault: bonded_vault.to_string(),
+            transform: PowerTransform::Multiplier {
+                factor: Decimal::percent(200),
+            },
+        }],
+    )
+    .unwrap();
+
+    bond_tokens(&mut app, &bonded_vault, ADDR1, 100);
+    bond_tokens(&mut app, &bonded_vault, ADDR2, 25);
+    bond_tokens(&mut app, &lockdrop_vault, ADDR1, 40);
+    app.update_block(next_block);
+    let height = app.block_info().height;
+
+    let resp: PowerBreakdownResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::VotingPowerBreakdownAtHeight {
+                address: ADDR1.to_string(),
+                height: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resp,
+        PowerBreakdownResponse {
+            vaults: vec![
+                VaultPower {
+                    address: bonded_vault.to_string(),
+                    name: "bonded".to_string(),
+                    power: Uint128::new(200),
+                },
+                VaultPower {
+                    address: lockdrop_vault.to_string(),
+                    name: "lockdrop".to_string(),
+                    power: Uint128::new(40),
+                },
+            ],
+            power: get_voting_power(&app, &addr, ADDR1),
+            height,
+        }
+    );
+    assert_eq!(resp.power, Uint128::new(240));
+
+    // Deactivated vaults are left out of the breakdown.
+    deactivate_vault(&mut app, addr.clone(), DAO_ADDR, lockdrop_vault.to_string()).unwrap();
+    app.update_block(next_block);
+    let resp: PowerBreakdownResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::TotalPowerBreakdownAtHeight { height: None },
+        )
+        .unwrap();
+    assert_eq!(
+        resp.vaults,
+        vec![VaultPower {
+            address: bonded_vault.to_string(),
+            name: "bonded".to_string(),
+            // (100 + 25) * 2
+            power: Uint128::new(250),
+        }]
+    );
+    assert_eq!(resp.power, get_total_power(&app, &addr));
+
+    let resp: PowerBreakdownResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr,
+            &QueryMsg::TotalPowerBreakdownAtHeight {
+                height: Some(height),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.vaults.len(), 2);
+    assert_eq!(resp.power, Uint128::new(290));
+}
+
+#[test]
+pub fn test_migrate_legacy_config() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    LEGACY_CONFIG
+        .save(
+            &mut deps.storage,
+            &LegacyConfig {
+                owner: Addr::unchecked(DAO_ADDR),
+                voting_vaults: Some(vec![Addr::unchecked(VAULT_ADDR)]),
+            },
+        )
+        .unwrap();
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap(),
+        Config {
+            owner: Addr::unchecked(DAO_ADDR),
+        }
+    );
+    assert_eq!(
+        LEGACY_CONFIG.load(&deps.storage).unwrap().voting_vaults,
+        None
+    );
+    assert_eq!(
+        VOTING_VAULTS
+            .load(&deps.storage, &Addr::unchecked(VAULT_ADDR))
+            .unwrap(),
+        VotingVaultState::Active
+    );
+    assert_eq!(
+        VOTING_VAULTS
+            .may_load_at_height(
+                &deps.storage,
+                &Addr::unchecked(VAULT_ADDR),
+                env.block.height + 1
+            )
+            .unwrap(),
+        Some(VotingVaultState::Active)
+    );
+
+    // The migrated vaults still count at heights before the migration.
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == VAULT_ADDR => {
+            let height = match from_binary(msg).unwrap() {
+                voting::Query::TotalPowerAtHeight { height } => height.unwrap(),
+                _ => panic!("unexpected vault query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&TotalPowerAtHeightResponse {
+                    power: Uint128::new(100),
+                    height,
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+    for height in [env.block.height - 1, env.block.height, env.block.height + 1] {
+        let resp: TotalPowerAtHeightResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::TotalPowerAtHeight {
+                    height: Some(height),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(resp.power, Uint128::new(100));
+    }
 }
 
 #[test]
@@ -1,3 +1,7 @@
+use admin_nomination::{
+    execute_accept_admin_nomination, execute_nominate_admin, execute_withdraw_admin_nomination,
+    query_nomination, ADMIN,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
@@ -6,16 +10,18 @@ use cosmwasm_std::{
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw_paginate::{paginate_map, paginate_map_values};
-use cw_utils::parse_reply_instantiate_data;
+use cw_utils::{parse_reply_instantiate_data, Duration};
 use cwd_interface::{voting, ModuleInstantiateInfo};
 use cwd_voting::pre_propose::ProposalCreationPolicy;
 use exec_control::pause::{
-    can_pause, can_unpause, validate_duration, PauseError, PauseInfoResponse,
+    get_max_pause_durations, get_pause_history, get_pause_info, is_paused, migrate_legacy_pause,
+    PauseError, PauseRequest,
 };
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_subdao_core::msg::{ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, QueryMsg};
 use neutron_subdao_core::types::{
-    Config, DumpStateResponse, GetItemResponse, ProposalModule, ProposalModuleStatus, SubDao,
+    AdminNominationResponse, Config, DumpStateResponse, GetItemResponse, ProposalModule,
+    ProposalModuleStatus, SubDao,
 };
 use neutron_subdao_pre_propose_single::msg::QueryExt as PreProposeQueryExt;
 use neutron_subdao_pre_propose_single::msg::QueryMsg as PreProposeQueryMsg;
@@ -23,7 +29,7 @@ use neutron_subdao_proposal_single::msg::QueryMsg as ProposeQueryMsg;
 
 use crate::error::ContractError;
 use crate::state::{
-    ACTIVE_PROPOSAL_MODULE_COUNT, CONFIG, ITEMS, PAUSED_UNTIL, PROPOSAL_MODULES, SUBDAO_LIST,
+    ACTIVE_PROPOSAL_MODULE_COUNT, CONFIG, ITEMS, PROPOSAL_MODULES, SUBDAO_LIST,
     TOTAL_PROPOSAL_MODULE_COUNT, VOTE_MODULE,
 };
 
@@ -52,7 +58,7 @@ pub fn instantiate(
     };
     config.validate()?;
     CONFIG.save(deps.storage, &config)?;
-    PAUSED_UNTIL.save(deps.storage, &None)?;
+    ADMIN.save(deps.storage, &env.contract.address)?;
 
     let vote_module_msg = msg
         .vote_module_instantiate_info
@@ -91,15 +97,19 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response<NeutronMsg>, ContractError> {
-    match get_pause_info(deps.as_ref(), &env)? {
-        PauseInfoResponse::Paused { .. } => {
-            return match msg {
-                ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
-                ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
-                _ => Err(ContractError::PauseError(PauseError::Paused {})),
-            };
-        }
-        PauseInfoResponse::Unpaused {} => (),
+    if is_paused(deps.storage, &env.block, msg.operation_name())? {
+        return match msg {
+            ExecuteMsg::Pause {
+                duration,
+                reason,
+                operations,
+            } => execute_pause(deps, env, info.sender, duration, reason, operations),
+            ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+            ExecuteMsg::UpdateMaxPauseDuration { duration } => {
+                execute_update_max_pause_duration(deps, info.sender, duration)
+            }
+            _ => Err(ContractError::PauseError(PauseError::Paused {})),
+        };
     }
 
     match msg {
@@ -109,8 +119,15 @@ pub fn execute(
         ExecuteMsg::ExecuteTimelockedMsgs { msgs } => {
             execute_timelocked_msgs(deps.as_ref(), env, info.sender, msgs)
         }
-        ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
+        ExecuteMsg::Pause {
+            duration,
+            reason,
+            operations,
+        } => execute_pause(deps, env, info.sender, duration, reason, operations),
         ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+        ExecuteMsg::UpdateMaxPauseDuration { duration } => {
+            execute_update_max_pause_duration(deps, info.sender, duration)
+        }
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::SetItem { key, addr } => execute_set_item(deps, env, info.sender, key, addr),
         ExecuteMsg::UpdateConfig {
@@ -127,6 +144,15 @@ pub fn execute(
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::NominateAdmin { admin } => {
+            Ok(execute_nominate_admin(deps, info.sender, admin)?)
+        }
+        ExecuteMsg::AcceptAdminNomination {} => {
+            Ok(execute_accept_admin_nomination(deps, env, info.sender)?)
+        }
+        ExecuteMsg::WithdrawAdminNomination {} => {
+            Ok(execute_withdraw_admin_nomination(deps, info.sender)?)
+        }
     }
 }
 
@@ -134,44 +160,55 @@ pub fn execute_pause(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    duration: u64,
+    duration: Duration,
+    reason: Option<String>,
+    operations: Option<Vec<String>>,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    can_pause(&sender, &config.main_dao, &config.security_dao)?;
-    validate_duration(duration)?;
-
-    let paused_until_height: u64 = env.block.height + duration;
-
-    let already_paused_until = PAUSED_UNTIL.load(deps.storage)?;
-    if already_paused_until.unwrap_or(0u64) >= paused_until_height {
-        return Err(ContractError::PauseError(PauseError::InvalidDuration(
-            "contracts are already paused for a greater or equal duration".to_string(),
-        )));
-    }
-
-    PAUSED_UNTIL.save(deps.storage, &Some(paused_until_height))?;
-
-    Ok(Response::new()
-        .add_attribute("action", "execute_pause")
-        .add_attribute("sender", sender)
-        .add_attribute("paused_until_height", paused_until_height.to_string()))
+    Ok(exec_control::pause::execute_pause(
+        deps.storage,
+        &env.block,
+        sender,
+        &config.main_dao,
+        &config.security_dao,
+        PauseRequest {
+            duration,
+            reason,
+            operations,
+            known_operations: ExecuteMsg::PAUSABLE_OPERATIONS,
+        },
+    )?)
 }
 
 pub fn execute_unpause(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     sender: Addr,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    can_unpause(&sender, &config.main_dao)?;
+    Ok(exec_control::pause::execute_unpause(
+        deps.storage,
+        &env.block,
+        sender,
+        &config.main_dao,
+    )?)
+}
 
-    PAUSED_UNTIL.save(deps.storage, &None)?;
+pub fn execute_update_max_pause_duration(
+    deps: DepsMut,
+    sender: Addr,
+    duration: Duration,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "execute_unpause")
-        .add_attribute("sender", sender))
+    Ok(exec_control::pause::execute_update_max_pause_duration(
+        deps.storage,
+        sender,
+        &config.main_dao,
+        duration,
+    )?)
 }
 
 pub fn execute_proposal_hook(
@@ -378,12 +415,18 @@ pub fn execute_update_sub_daos_list(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Admin {} => query_admin(deps),
+        QueryMsg::AdminNomination {} => query_admin_nomination(deps),
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::DumpState {} => query_dump_state(deps, env),
         QueryMsg::GetItem { key } => query_get_item(deps, key),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ListItems { start_after, limit } => query_list_items(deps, start_after, limit),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
+        QueryMsg::MaxPauseDuration {} => to_binary(&get_max_pause_durations(deps.storage)?),
+        QueryMsg::PauseHistory { start_after, limit } => {
+            to_binary(&get_pause_history(deps.storage, start_after, limit)?)
+        }
         QueryMsg::ProposalModules { start_after, limit } => {
             query_proposal_modules(deps, start_after, limit)
         }
@@ -409,6 +452,16 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
     to_binary(&config)
 }
 
+pub fn query_admin(deps: Deps) -> StdResult<Binary> {
+    let admin = ADMIN.load(deps.storage)?;
+    to_binary(&admin)
+}
+
+pub fn query_admin_nomination(deps: Deps) -> StdResult<Binary> {
+    let nomination = query_nomination(deps.storage)?;
+    to_binary(&AdminNominationResponse { nomination })
+}
+
 pub fn query_voting_module(deps: Deps) -> StdResult<Binary> {
     let voting_module = VOTE_MODULE.load(deps.storage)?;
     to_binary(&voting_module)
@@ -471,38 +524,25 @@ pub fn query_active_proposal_modules(
     )
 }
 
-fn get_pause_info(deps: Deps, env: &Env) -> StdResult<PauseInfoResponse> {
-    Ok(match PAUSED_UNTIL.may_load(deps.storage)?.unwrap_or(None) {
-        Some(paused_until_height) => {
-            if env.block.height.ge(&paused_until_height) {
-                PauseInfoResponse::Unpaused {}
-            } else {
-                PauseInfoResponse::Paused {
-                    until_height: paused_until_height,
-                }
-            }
-        }
-        None => PauseInfoResponse::Unpaused {},
-    })
-}
-
 pub fn query_paused(deps: Deps, env: Env) -> StdResult<Binary> {
-    to_binary(&get_pause_info(deps, &env)?)
+    to_binary(&get_pause_info(deps.storage, &env.block)?)
 }
 
 pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
+    let admin = ADMIN.load(deps.storage)?;
     let voting_registry_module = VOTE_MODULE.load(deps.storage)?;
     let proposal_modules = PROPOSAL_MODULES
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
         .map(|kv| Ok(kv?.1))
         .collect::<StdResult<Vec<ProposalModule>>>()?;
-    let pause_info = get_pause_info(deps, &env)?;
+    let pause_info = get_pause_info(deps.storage, &env.block)?;
     let version = get_contract_version(deps.storage)?;
     let active_proposal_module_count = ACTIVE_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
     let total_proposal_module_count = TOTAL_PROPOSAL_MODULE_COUNT.load(deps.storage)?;
     to_binary(&DumpStateResponse {
         config,
+        admin,
         version,
         pause_info,
         proposal_modules,
@@ -601,7 +641,14 @@ pub fn query_verify_timelock(deps: Deps, timelock: String) -> StdResult<Binary>
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Contracts instantiated before the admin was introduced are
+    // their own admin.
+    if ADMIN.may_load(deps.storage)?.is_none() {
+        ADMIN.save(deps.storage, &env.contract.address)?;
+    }
+    let config = CONFIG.load(deps.storage)?;
+    migrate_legacy_pause(deps.storage, &env.block, &config.main_dao)?;
     Ok(Response::default())
 }
 
@@ -703,7 +750,16 @@ pub(crate) fn derive_proposal_module_prefix(mut dividend: usize) -> StdResult<St
 
 #[cfg(test)]
 mod test {
-    use crate::contract::derive_proposal_module_prefix;
+    use crate::contract::{derive_proposal_module_prefix, execute, query};
+    use crate::error::ContractError;
+    use admin_nomination::{NominationError, ADMIN};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockQuerier};
+    use cosmwasm_std::{
+        from_binary, to_binary, Addr, ContractInfoResponse, ContractResult, CosmosMsg, Deps,
+        SystemResult, WasmMsg, WasmQuery,
+    };
+    use neutron_subdao_core::msg::{ExecuteMsg, QueryMsg};
+    use neutron_subdao_core::types::AdminNominationResponse;
     use std::collections::HashSet;
 
     #[test]
@@ -736,4 +792,141 @@ mod test {
             seen.insert(prefix);
         }
     }
+
+    /// Makes the DAO in `mock_env` its own contract admin.
+    fn set_dao_as_contract_admin(querier: &mut MockQuerier) {
+        querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { contract_addr } => {
+                let mut info = ContractInfoResponse::new(1, "creator");
+                info.admin = Some(contract_addr.clone());
+                SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+    }
+
+    fn query_nomination(deps: Deps) -> Option<Addr> {
+        let res: AdminNominationResponse =
+            from_binary(&query(deps, mock_env(), QueryMsg::AdminNomination {}).unwrap()).unwrap();
+        res.nomination
+    }
+
+    #[test]
+    fn test_admin_nomination() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let dao = env.contract.address.to_string();
+        ADMIN
+            .save(deps.as_mut().storage, &env.contract.address)
+            .unwrap();
+        set_dao_as_contract_admin(&mut deps.querier);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("nominee", &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "nominee".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::Unauthorized {})
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "typo".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            query_nomination(deps.as_ref()),
+            Some(Addr::unchecked("typo"))
+        );
+
+        // A pending nomination has to be withdrawn before nominating
+        // someone else.
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "nominee".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::PendingNomination {})
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::WithdrawAdminNomination {},
+        )
+        .unwrap();
+        assert_eq!(query_nomination(deps.as_ref()), None);
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("typo", &[]),
+            ExecuteMsg::AcceptAdminNomination {},
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::NoNomination {})
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "nominee".to_string(),
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("nominee", &[]),
+            ExecuteMsg::AcceptAdminNomination {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr: dao.clone(),
+                admin: "nominee".to_string(),
+            })
+        );
+
+        let admin: Addr =
+            from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Admin {}).unwrap()).unwrap();
+        assert_eq!(admin, Addr::unchecked("nominee"));
+        assert_eq!(query_nomination(deps.as_ref()), None);
+
+        // The DAO is no longer the admin.
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info(&dao, &[]),
+            ExecuteMsg::NominateAdmin {
+                admin: "other".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Nomination(NominationError::Unauthorized {})
+        );
+    }
 }
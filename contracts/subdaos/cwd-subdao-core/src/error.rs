@@ -1,3 +1,4 @@
+use admin_nomination::NominationError;
 use cosmwasm_std::{Addr, StdError};
 use cw_utils::ParseReplyError;
 use exec_control::pause::PauseError;
@@ -41,4 +42,7 @@ pub enum ContractError {
 
     #[error("Proposal module with address is disabled and cannot execute messages.")]
     ModuleDisabledCannotExecute { address: Addr },
+
+    #[error(transparent)]
+    Nomination(#[from] NominationError),
 }
@@ -5,9 +5,6 @@ use neutron_subdao_core::types::{Config, ProposalModule};
 /// The current configuration of the module.
 pub const CONFIG: Item<Config> = Item::new("config_v2");
 
-/// The height the subDAO is paused until. If it's None, the subDAO is not paused.
-pub const PAUSED_UNTIL: Item<Option<u64>> = Item::new("paused_until");
-
 /// The voting module associated with this contract.
 pub const VOTE_MODULE: Item<Addr> = Item::new("voting_module");
 
@@ -14,9 +14,7 @@ use cwd_pre_propose_base::{
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
     state::PreProposeContract,
 };
-use neutron_security_subdao_pre_propose::msg::{
-    ExecuteMsgPauseTypedDuration, ExecuteMsgPauseUntypedDuration, MigrateMsg,
-};
+use neutron_security_subdao_pre_propose::msg::{ExecuteMsgPause, MigrateMsg};
 use neutron_security_subdao_pre_propose::types::ProposeMessage;
 pub type InstantiateMsg = InstantiateBase;
 pub type ExecuteMsg = ExecuteBase<ProposeMessage>;
@@ -74,26 +72,7 @@ pub fn execute(
                     msgs,
                 },
         } => {
-            for msg in &msgs {
-                match msg {
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: _contract_addr,
-                        msg,
-                        funds,
-                    }) => {
-                        if (from_binary::<ExecuteMsgPauseTypedDuration>(msg).is_err()
-                            && from_binary::<ExecuteMsgPauseUntypedDuration>(msg).is_err())
-                            || !funds.is_empty()
-                        {
-                            return Err(PreProposeError::MalformedProposal {});
-                        }
-                    }
-                    CosmosMsg::Custom(NeutronMsg::RemoveSchedule { name: _name }) => {
-                        continue;
-                    }
-                    _ => return Err(PreProposeError::MalformedProposal {}),
-                }
-            }
+            validate_msgs(&msgs)?;
 
             ExecuteInternal::Propose {
                 msg: ProposeMessageInternal::Propose {
@@ -132,6 +111,29 @@ pub fn execute(
     PrePropose::default().execute(deps, env, info, internalized)
 }
 
+/// Checks that the proposal only pauses contracts, either one by one
+/// or all at once through the main DAO, and removes schedules.
+fn validate_msgs(msgs: &[CosmosMsg<NeutronMsg>]) -> Result<(), PreProposeError> {
+    for msg in msgs {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: _contract_addr,
+                msg,
+                funds,
+            }) => {
+                if from_binary::<ExecuteMsgPause>(msg).is_err() || !funds.is_empty() {
+                    return Err(PreProposeError::MalformedProposal {});
+                }
+            }
+            CosmosMsg::Custom(NeutronMsg::RemoveSchedule { name: _name }) => {
+                continue;
+            }
+            _ => return Err(PreProposeError::MalformedProposal {}),
+        }
+    }
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     PrePropose::default().query(deps, env, msg)
@@ -141,3 +143,67 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, PreProposeError> {
     Ok(Response::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::validate_msgs;
+    use cosmwasm_std::{coins, to_binary, BankMsg, CosmosMsg, Empty, WasmMsg};
+    use cw_utils::Duration;
+    use cwd_pre_propose_base::error::PreProposeError;
+    use neutron_sdk::bindings::msg::NeutronMsg;
+    use neutron_security_subdao_pre_propose::msg::ExecuteMsgPause;
+
+    fn execute(contract: &str, msg: &ExecuteMsgPause, funds: u128) -> CosmosMsg<NeutronMsg> {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_binary(msg).unwrap(),
+            funds: if funds > 0 {
+                coins(funds, "untrn")
+            } else {
+                vec![]
+            },
+        })
+    }
+
+    #[test]
+    fn test_validate_msgs() {
+        let pause = ExecuteMsgPause::Pause {
+            duration: Duration::Height(10),
+            reason: Some("incident".to_string()),
+            operations: None,
+        };
+        let pause_all = ExecuteMsgPause::PauseAll {
+            duration: Duration::Height(10),
+            reason: Some("incident".to_string()),
+            operations: Some(vec!["distribute".to_string()]),
+        };
+
+        validate_msgs(&[
+            execute("reserve", &pause, 0),
+            execute("main_dao", &pause_all, 0),
+            CosmosMsg::Custom(NeutronMsg::RemoveSchedule {
+                name: "schedule".to_string(),
+            }),
+        ])
+        .unwrap();
+
+        let malformed = [
+            execute("main_dao", &pause_all, 1),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "main_dao".to_string(),
+                msg: to_binary(&Empty {}).unwrap(),
+                funds: vec![],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "someone".to_string(),
+                amount: coins(1, "untrn"),
+            }),
+        ];
+        for msg in malformed {
+            assert_eq!(
+                validate_msgs(&[execute("reserve", &pause, 0), msg]).unwrap_err(),
+                PreProposeError::MalformedProposal {}
+            );
+        }
+    }
+}
@@ -409,6 +409,7 @@ pub fn execute_vote(
         proposal_id,
         info.sender.to_string(),
         vote.to_string(),
+        None,
     )?;
 
     Ok(Response::default()
@@ -14,12 +14,13 @@
 
 use cosmwasm_schema::write_api;
 
-use neutron_distribution::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use neutron_distribution::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         query: QueryMsg,
         execute: ExecuteMsg,
+        migrate: MigrateMsg,
     }
 }
@@ -1,14 +1,16 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG, FUND_COUNTER, PAUSED_UNTIL, PENDING_DISTRIBUTION, SHARES};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Config, CONFIG, FUND_COUNTER, PENDING_DISTRIBUTION, SHARES};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
     Response, StdResult, Storage, Uint128,
 };
+use cw_utils::Duration;
 use exec_control::pause::{
-    can_pause, can_unpause, validate_duration, PauseError, PauseInfoResponse,
+    get_max_pause_durations, get_pause_history, get_pause_info, is_paused, migrate_legacy_pause,
+    PauseError, PauseRequest,
 };
 
 //--------------------------------------------------------------------------------------------------
@@ -28,7 +30,6 @@ pub fn instantiate(
         security_dao_address: deps.api.addr_validate(&msg.security_dao_address)?,
     };
     CONFIG.save(deps.storage, &config)?;
-    PAUSED_UNTIL.save(deps.storage, &None)?;
     Ok(Response::new())
 }
 
@@ -43,20 +44,31 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    match get_pause_info(deps.as_ref(), &env)? {
-        PauseInfoResponse::Paused { .. } => {
-            return match msg {
-                ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
-                ExecuteMsg::Unpause {} => execute_unpause(deps, info.sender),
-                _ => Err(ContractError::PauseError(PauseError::Paused {})),
-            };
-        }
-        PauseInfoResponse::Unpaused {} => (),
+    if is_paused(deps.storage, &env.block, msg.operation_name())? {
+        return match msg {
+            ExecuteMsg::Pause {
+                duration,
+                reason,
+                operations,
+            } => execute_pause(deps, env, info.sender, duration, reason, operations),
+            ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+            ExecuteMsg::UpdateMaxPauseDuration { duration } => {
+                execute_update_max_pause_duration(deps, info.sender, duration)
+            }
+            _ => Err(ContractError::PauseError(PauseError::Paused {})),
+        };
     }
 
     match msg {
-        ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
-        ExecuteMsg::Unpause {} => execute_unpause(deps, info.sender),
+        ExecuteMsg::Pause {
+            duration,
+            reason,
+            operations,
+        } => execute_pause(deps, env, info.sender, duration, reason, operations),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+        ExecuteMsg::UpdateMaxPauseDuration { duration } => {
+            execute_update_max_pause_duration(deps, info.sender, duration)
+        }
         // permissioned - owner
         ExecuteMsg::TransferOwnership(new_owner) => {
             let new_owner_addr = deps.api.addr_validate(&new_owner)?;
@@ -78,44 +90,51 @@ pub fn execute_pause(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    duration: u64,
+    duration: Duration,
+    reason: Option<String>,
+    operations: Option<Vec<String>>,
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    can_pause(
-        &sender,
+    Ok(exec_control::pause::execute_pause(
+        deps.storage,
+        &env.block,
+        sender,
         &config.main_dao_address,
         &config.security_dao_address,
-    )?;
-    validate_duration(duration)?;
-
-    let paused_until_height: u64 = env.block.height + duration;
-
-    let already_paused_until = PAUSED_UNTIL.load(deps.storage)?;
-    if already_paused_until.unwrap_or(0u64) >= paused_until_height {
-        return Err(ContractError::PauseError(PauseError::InvalidDuration(
-            "contracts are already paused for a greater or equal duration".to_string(),
-        )));
-    }
-
-    PAUSED_UNTIL.save(deps.storage, &Some(paused_until_height))?;
-
-    Ok(Response::new()
-        .add_attribute("action", "execute_pause")
-        .add_attribute("sender", sender)
-        .add_attribute("paused_until_height", paused_until_height.to_string()))
+        PauseRequest {
+            duration,
+            reason,
+            operations,
+            known_operations: ExecuteMsg::PAUSABLE_OPERATIONS,
+        },
+    )?)
 }
 
-pub fn execute_unpause(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+pub fn execute_unpause(deps: DepsMut, env: Env, sender: Addr) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    can_unpause(&sender, &config.main_dao_address)?;
+    Ok(exec_control::pause::execute_unpause(
+        deps.storage,
+        &env.block,
+        sender,
+        &config.main_dao_address,
+    )?)
+}
 
-    PAUSED_UNTIL.save(deps.storage, &None)?;
+pub fn execute_update_max_pause_duration(
+    deps: DepsMut,
+    sender: Addr,
+    duration: Duration,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "execute_unpause")
-        .add_attribute("sender", sender))
+    Ok(exec_control::pause::execute_update_max_pause_duration(
+        deps.storage,
+        sender,
+        &config.main_dao_address,
+        duration,
+    )?)
 }
 
 pub fn execute_transfer_ownership(
@@ -266,6 +285,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Pending {} => to_binary(&query_pending(deps)?),
         QueryMsg::Shares {} => to_binary(&query_shares(deps)?),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
+        QueryMsg::MaxPauseDuration {} => to_binary(&get_max_pause_durations(deps.storage)?),
+        QueryMsg::PauseHistory { start_after, limit } => {
+            to_binary(&get_pause_history(deps.storage, start_after, limit)?)
+        }
     }
 }
 
@@ -289,20 +312,16 @@ pub fn query_pending(deps: Deps) -> StdResult<Vec<(Addr, Uint128)>> {
 }
 
 pub fn query_paused(deps: Deps, env: Env) -> StdResult<Binary> {
-    to_binary(&get_pause_info(deps, &env)?)
+    to_binary(&get_pause_info(deps.storage, &env.block)?)
 }
 
-fn get_pause_info(deps: Deps, env: &Env) -> StdResult<PauseInfoResponse> {
-    Ok(match PAUSED_UNTIL.may_load(deps.storage)?.unwrap_or(None) {
-        Some(paused_until_height) => {
-            if env.block.height.ge(&paused_until_height) {
-                PauseInfoResponse::Unpaused {}
-            } else {
-                PauseInfoResponse::Paused {
-                    until_height: paused_until_height,
-                }
-            }
-        }
-        None => PauseInfoResponse::Unpaused {},
-    })
+//--------------------------------------------------------------------------------------------------
+// Migration
+//--------------------------------------------------------------------------------------------------
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    migrate_legacy_pause(deps.storage, &env.block, &config.main_dao_address)?;
+    Ok(Response::default())
 }
@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
 use cwd_macros::{pausable, pausable_query};
-use exec_control::pause::PauseInfoResponse;
+use exec_control::pause::{MaxPauseDurationResponse, PauseInfoResponse, PauseState};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -16,7 +16,7 @@ pub struct InstantiateMsg {
 }
 
 #[pausable]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Transfer the contract's ownership to another account
@@ -47,3 +47,6 @@ pub enum QueryMsg {
     #[returns(Vec<(Addr, Uint128)>)]
     Shares {},
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -24,6 +24,3 @@ pub const CONFIG: Item<Config> = Item::new("config");
 /// Counts times `Fund` has been called successfully.
 /// Used to determine what address to fund non-dividable remainder after weight distribution.
 pub const FUND_COUNTER: Item<u64> = Item::new("fund_counter");
-
-/// The height the contract is paused until. If it's None, the contract is not paused.
-pub const PAUSED_UNTIL: Item<Option<u64>> = Item::new("paused_until");
@@ -1,7 +1,7 @@
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, migrate, query},
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state::{CONFIG, FUND_COUNTER, PENDING_DISTRIBUTION, SHARES},
     testing::mock_querier::mock_dependencies,
 };
@@ -10,7 +10,9 @@ use cosmwasm_std::{
     testing::{mock_env, mock_info},
     Addr, BankMsg, CosmosMsg, DepsMut, Empty, Uint128,
 };
-use exec_control::pause::{PauseError, PauseInfoResponse};
+use cw_storage_plus::Item;
+use cw_utils::{Duration, Expiration};
+use exec_control::pause::{PauseError, PauseInfoResponse, PauseState};
 
 const DENOM: &str = "denom";
 const MAIN_DAO_ADDR: &str = "main_dao";
@@ -336,7 +338,11 @@ fn test_pause() {
     init_base_contract(deps.as_mut());
 
     // pause contracts for 10 blocks from main dao
-    let msg = ExecuteMsg::Pause { duration: 10u64 };
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Height(10),
+        reason: None,
+        operations: None,
+    };
     let res = execute(
         deps.as_mut(),
         mock_env(),
@@ -349,7 +355,8 @@ fn test_pause() {
     assert_eq!(
         pause_info,
         PauseInfoResponse::Paused {
-            until_height: mock_env().block.height + 10
+            until: Expiration::AtHeight(mock_env().block.height + 10),
+            operations: None,
         }
     );
 
@@ -395,7 +402,11 @@ fn test_pause() {
     env.block.height += 15;
 
     // pause contracts for 10 blocks from security dao
-    let msg = ExecuteMsg::Pause { duration: 10u64 };
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Height(10),
+        reason: Some("incident".to_string()),
+        operations: None,
+    };
     let res = execute(
         deps.as_mut(),
         env.clone(),
@@ -408,7 +419,8 @@ fn test_pause() {
     assert_eq!(
         pause_info,
         PauseInfoResponse::Paused {
-            until_height: env.block.height + 10
+            until: Expiration::AtHeight(env.block.height + 10),
+            operations: None,
         }
     );
 
@@ -424,4 +436,162 @@ fn test_pause() {
     let pause_info: PauseInfoResponse =
         from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PauseInfo {}).unwrap()).unwrap();
     assert_eq!(pause_info, PauseInfoResponse::Unpaused {});
+
+    // both pauses are recorded in the history
+    let history: Vec<(u64, PauseState)> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PauseHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        history,
+        vec![
+            (
+                0,
+                PauseState {
+                    paused_by: Addr::unchecked(MAIN_DAO_ADDR),
+                    reason: None,
+                    operations: None,
+                    from_height: mock_env().block.height,
+                    until: Expiration::AtHeight(mock_env().block.height + 10),
+                    unpaused_by: None,
+                    unpaused_at_height: None,
+                }
+            ),
+            (
+                1,
+                PauseState {
+                    paused_by: Addr::unchecked(SECURITY_DAO_ADDR),
+                    reason: Some("incident".to_string()),
+                    operations: None,
+                    from_height: env.block.height,
+                    until: Expiration::AtHeight(env.block.height + 10),
+                    unpaused_by: Some(Addr::unchecked(NEW_MAIN_DAO_ADDR)),
+                    unpaused_at_height: Some(mock_env().block.height),
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_pause_operations() {
+    let mut deps = mock_dependencies(&[]);
+    init_base_contract(deps.as_mut());
+
+    // unknown operations can't be paused
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Height(10),
+        reason: None,
+        operations: Some(vec!["withdraw".to_string()]),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SECURITY_DAO_ADDR, &[]),
+        msg,
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::PauseError(PauseError::InvalidOperations(
+            "unknown operation: withdraw".to_string()
+        ))
+    );
+
+    // pause claims only
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Height(10),
+        reason: None,
+        operations: Some(vec!["claim".to_string()]),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SECURITY_DAO_ADDR, &[]),
+        msg,
+    );
+    assert!(res.is_ok());
+    let pause_info: PauseInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PauseInfo {}).unwrap()).unwrap();
+    assert_eq!(
+        pause_info,
+        PauseInfoResponse::Paused {
+            until: Expiration::AtHeight(mock_env().block.height + 10),
+            operations: Some(vec!["claim".to_string()]),
+        }
+    );
+
+    // claims are paused
+    let msg = ExecuteMsg::Claim {};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("address1", &[]), msg);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::PauseError(PauseError::Paused {})
+    );
+
+    // other operations are not
+    let msg = ExecuteMsg::Fund {};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("someone", &[]), msg);
+    assert_eq!(res.unwrap_err(), ContractError::NoFundsSent {});
+
+    let msg = ExecuteMsg::TransferOwnership(NEW_MAIN_DAO_ADDR.to_string());
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MAIN_DAO_ADDR, &[]),
+        msg,
+    );
+    assert!(res.is_ok());
+}
+
+#[test]
+fn test_migrate_legacy_pause() {
+    let mut deps = mock_dependencies(&[]);
+    init_base_contract(deps.as_mut());
+    let env = mock_env();
+
+    // contracts paused before the pause history was introduced stay paused
+    Item::<Option<u64>>::new("paused_until")
+        .save(deps.as_mut().storage, &Some(env.block.height + 10))
+        .unwrap();
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    let pause_info: PauseInfoResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::PauseInfo {}).unwrap()).unwrap();
+    assert_eq!(
+        pause_info,
+        PauseInfoResponse::Paused {
+            until: Expiration::AtHeight(env.block.height + 10),
+            operations: None,
+        }
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("someone", &[]),
+        ExecuteMsg::Fund {},
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::PauseError(PauseError::Paused {})
+    );
+
+    // the main dao can lift the migrated pause
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MAIN_DAO_ADDR, &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    let pause_info: PauseInfoResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::PauseInfo {}).unwrap()).unwrap();
+    assert_eq!(pause_info, PauseInfoResponse::Unpaused {});
 }
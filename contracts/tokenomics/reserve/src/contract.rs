@@ -6,15 +6,17 @@ use cosmwasm_std::{
     coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
     StdResult, Uint128, WasmMsg,
 };
+use cw_utils::Duration;
 use exec_control::pause::{
-    can_pause, can_unpause, validate_duration, PauseError, PauseInfoResponse,
+    get_max_pause_durations, get_pause_history, get_pause_info, is_paused, migrate_legacy_pause,
+    PauseError, PauseRequest,
 };
 use neutron_sdk::bindings::query::NeutronQuery;
 
-use crate::msg::{DistributeMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StatsResponse};
+use crate::msg::{DistributeMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StatsResponse};
 use crate::state::{
-    Config, CONFIG, LAST_BURNED_COINS_AMOUNT, LAST_DISTRIBUTION_TIME, PAUSED_UNTIL,
-    TOTAL_DISTRIBUTED, TOTAL_RESERVED,
+    Config, CONFIG, LAST_BURNED_COINS_AMOUNT, LAST_DISTRIBUTION_TIME, TOTAL_DISTRIBUTED,
+    TOTAL_RESERVED,
 };
 use crate::vesting::{
     get_burned_coins, safe_burned_coins_for_period, update_distribution_stats, vesting_function,
@@ -46,7 +48,6 @@ pub fn instantiate(
     TOTAL_DISTRIBUTED.save(deps.storage, &Uint128::zero())?;
     TOTAL_RESERVED.save(deps.storage, &Uint128::zero())?;
     LAST_DISTRIBUTION_TIME.save(deps.storage, &0)?;
-    PAUSED_UNTIL.save(deps.storage, &None)?;
     LAST_BURNED_COINS_AMOUNT.save(deps.storage, &Uint128::zero())?;
 
     Ok(Response::new())
@@ -56,62 +57,55 @@ pub fn execute_pause(
     deps: DepsMut<NeutronQuery>,
     env: Env,
     sender: Addr,
-    duration: u64,
+    duration: Duration,
+    reason: Option<String>,
+    operations: Option<Vec<String>>,
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    can_pause(
-        &sender,
+    Ok(exec_control::pause::execute_pause(
+        deps.storage,
+        &env.block,
+        sender,
         &config.main_dao_address,
         &config.security_dao_address,
-    )?;
-    validate_duration(duration)?;
-
-    let paused_until_height: u64 = env.block.height + duration;
-
-    let already_paused_until = PAUSED_UNTIL.load(deps.storage)?;
-    if already_paused_until.unwrap_or(0u64) >= paused_until_height {
-        return Err(ContractError::PauseError(PauseError::InvalidDuration(
-            "contracts are already paused for a greater or equal duration".to_string(),
-        )));
-    }
-
-    PAUSED_UNTIL.save(deps.storage, &Some(paused_until_height))?;
-
-    Ok(Response::new()
-        .add_attribute("action", "execute_pause")
-        .add_attribute("sender", sender)
-        .add_attribute("paused_until_height", paused_until_height.to_string()))
+        PauseRequest {
+            duration,
+            reason,
+            operations,
+            known_operations: ExecuteMsg::PAUSABLE_OPERATIONS,
+        },
+    )?)
 }
 
 pub fn execute_unpause(
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     sender: Addr,
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
-    can_unpause(&sender, &config.main_dao_address)?;
-
-    PAUSED_UNTIL.save(deps.storage, &None)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "execute_unpause")
-        .add_attribute("sender", sender))
+    Ok(exec_control::pause::execute_unpause(
+        deps.storage,
+        &env.block,
+        sender,
+        &config.main_dao_address,
+    )?)
 }
 
-fn get_pause_info(deps: Deps<NeutronQuery>, env: &Env) -> StdResult<PauseInfoResponse> {
-    Ok(match PAUSED_UNTIL.may_load(deps.storage)?.unwrap_or(None) {
-        Some(paused_until_height) => {
-            if env.block.height.ge(&paused_until_height) {
-                PauseInfoResponse::Unpaused {}
-            } else {
-                PauseInfoResponse::Paused {
-                    until_height: paused_until_height,
-                }
-            }
-        }
-        None => PauseInfoResponse::Unpaused {},
-    })
+pub fn execute_update_max_pause_duration(
+    deps: DepsMut<NeutronQuery>,
+    sender: Addr,
+    duration: Duration,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(exec_control::pause::execute_update_max_pause_duration(
+        deps.storage,
+        sender,
+        &config.main_dao_address,
+        duration,
+    )?)
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -127,15 +121,19 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let api = deps.api;
 
-    match get_pause_info(deps.as_ref(), &env)? {
-        PauseInfoResponse::Paused { .. } => {
-            return match msg {
-                ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
-                ExecuteMsg::Unpause {} => execute_unpause(deps, info.sender),
-                _ => Err(ContractError::PauseError(PauseError::Paused {})),
-            };
-        }
-        PauseInfoResponse::Unpaused {} => (),
+    if is_paused(deps.storage, &env.block, msg.operation_name())? {
+        return match msg {
+            ExecuteMsg::Pause {
+                duration,
+                reason,
+                operations,
+            } => execute_pause(deps, env, info.sender, duration, reason, operations),
+            ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+            ExecuteMsg::UpdateMaxPauseDuration { duration } => {
+                execute_update_max_pause_duration(deps, info.sender, duration)
+            }
+            _ => Err(ContractError::PauseError(PauseError::Paused {})),
+        };
     }
 
     match msg {
@@ -166,8 +164,15 @@ pub fn execute(
                 vesting_denominator,
             },
         ),
-        ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
-        ExecuteMsg::Unpause {} => execute_unpause(deps, info.sender),
+        ExecuteMsg::Pause {
+            duration,
+            reason,
+            operations,
+        } => execute_pause(deps, env, info.sender, duration, reason, operations),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info.sender),
+        ExecuteMsg::UpdateMaxPauseDuration { duration } => {
+            execute_update_max_pause_duration(deps, info.sender, duration)
+        }
     }
 }
 
@@ -305,11 +310,15 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Bin
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::Stats {} => to_binary(&query_stats(deps)?),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
+        QueryMsg::MaxPauseDuration {} => to_binary(&get_max_pause_durations(deps.storage)?),
+        QueryMsg::PauseHistory { start_after, limit } => {
+            to_binary(&get_pause_history(deps.storage, start_after, limit)?)
+        }
     }
 }
 
 pub fn query_paused(deps: Deps<NeutronQuery>, env: Env) -> StdResult<Binary> {
-    to_binary(&get_pause_info(deps, &env)?)
+    to_binary(&get_pause_info(deps.storage, &env.block)?)
 }
 
 pub fn query_config(deps: Deps<NeutronQuery>) -> StdResult<Config> {
@@ -329,6 +338,17 @@ pub fn query_stats(deps: Deps<NeutronQuery>) -> StdResult<StatsResponse> {
     })
 }
 
+//--------------------------------------------------------------------------------------------------
+// Migration
+//--------------------------------------------------------------------------------------------------
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut<NeutronQuery>, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    migrate_legacy_pause(deps.storage, &env.block, &config.main_dao_address)?;
+    Ok(Response::default())
+}
+
 //--------------------------------------------------------------------------------------------------
 // Helpers
 //--------------------------------------------------------------------------------------------------
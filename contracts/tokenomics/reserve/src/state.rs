@@ -52,9 +52,6 @@ pub const LAST_DISTRIBUTION_TIME: Item<u64> = Item::new("last_grab_time");
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
-/// The height the contract is paused until. If it's None, the contract is not paused.
-pub const PAUSED_UNTIL: Item<Option<u64>> = Item::new("paused_until");
-
 #[cfg(test)]
 mod tests {
     use super::Config;
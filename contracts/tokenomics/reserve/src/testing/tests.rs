@@ -5,7 +5,8 @@ use cosmwasm_std::{
     testing::{mock_env, mock_info},
     to_binary, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, StdError, Uint128, WasmMsg,
 };
-use exec_control::pause::{PauseError, PauseInfoResponse};
+use cw_utils::{Duration, Expiration};
+use exec_control::pause::{MaxPauseDurationResponse, PauseError, PauseInfoResponse};
 use neutron_sdk::bindings::query::NeutronQuery;
 
 use crate::contract::query;
@@ -54,7 +55,11 @@ fn test_pause() {
     init_base_contract(deps.as_mut(), "0.23");
 
     // pause contracts for 10 blocks from main dao
-    let msg = ExecuteMsg::Pause { duration: 10u64 };
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Height(10),
+        reason: None,
+        operations: None,
+    };
     let res = execute(deps.as_mut(), mock_env(), mock_info("main_dao", &[]), msg);
     assert!(res.is_ok());
     let pause_info: PauseInfoResponse =
@@ -62,7 +67,8 @@ fn test_pause() {
     assert_eq!(
         pause_info,
         PauseInfoResponse::Paused {
-            until_height: mock_env().block.height + 10
+            until: Expiration::AtHeight(mock_env().block.height + 10),
+            operations: None,
         }
     );
 
@@ -98,7 +104,11 @@ fn test_pause() {
     env.block.height += 15;
 
     // pause contracts for 10 blocks from security dao
-    let msg = ExecuteMsg::Pause { duration: 10u64 };
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Height(10),
+        reason: None,
+        operations: None,
+    };
     let res = execute(
         deps.as_mut(),
         env.clone(),
@@ -111,7 +121,8 @@ fn test_pause() {
     assert_eq!(
         pause_info,
         PauseInfoResponse::Paused {
-            until_height: env.block.height + 10
+            until: Expiration::AtHeight(env.block.height + 10),
+            operations: None,
         }
     );
 
@@ -124,6 +135,92 @@ fn test_pause() {
     assert_eq!(pause_info, PauseInfoResponse::Unpaused {});
 }
 
+#[test]
+fn test_pause_for_time() {
+    let mut deps = mock_dependencies(&[]);
+    init_base_contract(deps.as_mut(), "0.23");
+
+    // pauses set in seconds are limited by default as well
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Time(1209601),
+        reason: None,
+        operations: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("security_dao_address", &[]),
+        msg,
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::PauseError(PauseError::InvalidDuration(
+            "Pause duration is too big: it's only possible to pause the contract for 1209600 seconds"
+                .to_string()
+        ))
+    );
+
+    // only main dao can update the max pause duration
+    let msg = ExecuteMsg::UpdateMaxPauseDuration {
+        duration: Duration::Time(86400),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("security_dao_address", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::PauseError(PauseError::Unauthorized {})
+    );
+    let res = execute(deps.as_mut(), mock_env(), mock_info("main_dao", &[]), msg);
+    assert!(res.is_ok());
+    let max_pause_duration: MaxPauseDurationResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::MaxPauseDuration {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        max_pause_duration,
+        MaxPauseDurationResponse {
+            blocks: 200000,
+            seconds: 86400,
+        }
+    );
+
+    // pause contracts for an hour from security dao
+    let msg = ExecuteMsg::Pause {
+        duration: Duration::Time(3600),
+        reason: None,
+        operations: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("security_dao_address", &[]),
+        msg,
+    );
+    assert!(res.is_ok());
+    let pause_info: PauseInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PauseInfo {}).unwrap()).unwrap();
+    assert_eq!(
+        pause_info,
+        PauseInfoResponse::Paused {
+            until: Expiration::AtTime(mock_env().block.time.plus_seconds(3600)),
+            operations: None,
+        }
+    );
+
+    // the pause expires after an hour
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(3600);
+    let msg = ExecuteMsg::Distribute {};
+    let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg);
+    assert_ne!(
+        res.err(),
+        Some(ContractError::PauseError(PauseError::Paused {}))
+    );
+}
+
 #[test]
 fn test_distribute_success() {
     let mut deps = mock_dependencies(&[coin(1000000, DENOM)]);
@@ -0,0 +1,244 @@
+//! Two-step handover of the admin rights of a DAO core contract. The
+//! admin nominates a new admin, who then has to accept the nomination
+//! before it takes effect, so a mistyped address can never take over
+//! the admin rights.
+
+use cosmwasm_std::{Addr, DepsMut, Env, Response, StdError, StdResult, Storage, WasmMsg};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+/// The address of the DAO admin. Set to the DAO itself at
+/// instantiation time.
+pub const ADMIN: Item<Addr> = Item::new("admin");
+
+/// An address nominated to become the new admin. The nomination only
+/// takes effect once the nominee accepts it.
+pub const NOMINATED_ADMIN: Item<Addr> = Item::new("nominated_admin");
+
+#[derive(Error, Debug, PartialEq)]
+pub enum NominationError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized.")]
+    Unauthorized {},
+
+    #[error("An admin nomination is already pending. Withdraw it before nominating a new admin.")]
+    PendingNomination {},
+
+    #[error("There is no pending admin nomination.")]
+    NoNomination {},
+}
+
+/// Nominates `nominee` as the new admin on behalf of `sender`, who
+/// must be the current admin.
+pub fn execute_nominate_admin<T>(
+    deps: DepsMut,
+    sender: Addr,
+    nominee: String,
+) -> Result<Response<T>, NominationError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if sender != admin {
+        return Err(NominationError::Unauthorized {});
+    }
+
+    // A pending nomination has to be withdrawn explicitly so that a
+    // nominee can't be replaced under their feet.
+    if NOMINATED_ADMIN.may_load(deps.storage)?.is_some() {
+        return Err(NominationError::PendingNomination {});
+    }
+
+    let nominee = deps.api.addr_validate(&nominee)?;
+    NOMINATED_ADMIN.save(deps.storage, &nominee)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_nominate_admin")
+        .add_attribute("nominee", nominee))
+}
+
+/// Makes `sender`, who must be the nominee, the new admin. If the
+/// contract is its own contract admin, the contract admin is handed
+/// over as well.
+pub fn execute_accept_admin_nomination<T>(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response<T>, NominationError> {
+    let nominee = NOMINATED_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(NominationError::NoNomination {})?;
+    if sender != nominee {
+        return Err(NominationError::Unauthorized {});
+    }
+
+    NOMINATED_ADMIN.remove(deps.storage);
+    ADMIN.save(deps.storage, &nominee)?;
+
+    let mut response = Response::default()
+        .add_attribute("action", "execute_accept_admin_nomination")
+        .add_attribute("new_admin", nominee.clone());
+
+    // The contract admin can only be changed by the current contract
+    // admin, so it is handed over only if the contract is its own
+    // admin.
+    let contract_info = deps
+        .querier
+        .query_wasm_contract_info(env.contract.address.clone())?;
+    if contract_info.admin.as_deref() == Some(env.contract.address.as_str()) {
+        response = response.add_message(WasmMsg::UpdateAdmin {
+            contract_addr: env.contract.address.to_string(),
+            admin: nominee.to_string(),
+        });
+    }
+
+    Ok(response)
+}
+
+/// Withdraws the pending nomination on behalf of `sender`, who must be
+/// the current admin.
+pub fn execute_withdraw_admin_nomination<T>(
+    deps: DepsMut,
+    sender: Addr,
+) -> Result<Response<T>, NominationError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if sender != admin {
+        return Err(NominationError::Unauthorized {});
+    }
+
+    if NOMINATED_ADMIN.may_load(deps.storage)?.is_none() {
+        return Err(NominationError::NoNomination {});
+    }
+    NOMINATED_ADMIN.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_withdraw_admin_nomination")
+        .add_attribute("sender", sender))
+}
+
+/// Returns the pending nomination, if any.
+pub fn query_nomination(storage: &dyn Storage) -> StdResult<Option<Addr>> {
+    NOMINATED_ADMIN.may_load(storage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, MockQuerier};
+    use cosmwasm_std::{
+        to_binary, ContractInfoResponse, ContractResult, CosmosMsg, Empty, SystemResult, WasmQuery,
+    };
+
+    /// Makes the contract in `mock_env` report `admin` as its contract
+    /// admin.
+    fn set_contract_admin(querier: &mut MockQuerier, admin: Option<&'static str>) {
+        querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { .. } => {
+                let mut info = ContractInfoResponse::new(1, "creator");
+                info.admin = admin.map(str::to_string);
+                SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+    }
+
+    #[test]
+    fn test_nomination_flow() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let dao = env.contract.address.clone();
+        ADMIN.save(deps.as_mut().storage, &dao).unwrap();
+        set_contract_admin(&mut deps.querier, Some("cosmos2contract"));
+
+        // Only the admin may nominate.
+        let err = execute_nominate_admin::<Empty>(
+            deps.as_mut(),
+            Addr::unchecked("nominee"),
+            "nominee".to_string(),
+        )
+        .unwrap_err();
+        assert_eq!(err, NominationError::Unauthorized {});
+
+        execute_nominate_admin::<Empty>(deps.as_mut(), dao.clone(), "typo".to_string()).unwrap();
+        assert_eq!(
+            query_nomination(deps.as_ref().storage).unwrap(),
+            Some(Addr::unchecked("typo"))
+        );
+
+        // A pending nomination can't be replaced, only withdrawn.
+        let err =
+            execute_nominate_admin::<Empty>(deps.as_mut(), dao.clone(), "nominee".to_string())
+                .unwrap_err();
+        assert_eq!(err, NominationError::PendingNomination {});
+        let err =
+            execute_withdraw_admin_nomination::<Empty>(deps.as_mut(), Addr::unchecked("typo"))
+                .unwrap_err();
+        assert_eq!(err, NominationError::Unauthorized {});
+        execute_withdraw_admin_nomination::<Empty>(deps.as_mut(), dao.clone()).unwrap();
+        assert_eq!(query_nomination(deps.as_ref().storage).unwrap(), None);
+        let err =
+            execute_withdraw_admin_nomination::<Empty>(deps.as_mut(), dao.clone()).unwrap_err();
+        assert_eq!(err, NominationError::NoNomination {});
+        let err = execute_accept_admin_nomination::<Empty>(
+            deps.as_mut(),
+            env.clone(),
+            Addr::unchecked("typo"),
+        )
+        .unwrap_err();
+        assert_eq!(err, NominationError::NoNomination {});
+
+        execute_nominate_admin::<Empty>(deps.as_mut(), dao.clone(), "nominee".to_string()).unwrap();
+
+        // Only the nominee may accept.
+        let err = execute_accept_admin_nomination::<Empty>(deps.as_mut(), env.clone(), dao.clone())
+            .unwrap_err();
+        assert_eq!(err, NominationError::Unauthorized {});
+
+        let res = execute_accept_admin_nomination::<Empty>(
+            deps.as_mut(),
+            env.clone(),
+            Addr::unchecked("nominee"),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr: dao.to_string(),
+                admin: "nominee".to_string(),
+            })
+        );
+        assert_eq!(
+            ADMIN.load(deps.as_ref().storage).unwrap(),
+            Addr::unchecked("nominee")
+        );
+        assert_eq!(query_nomination(deps.as_ref().storage).unwrap(), None);
+
+        // The previous admin lost its rights.
+        let err =
+            execute_nominate_admin::<Empty>(deps.as_mut(), dao, "other".to_string()).unwrap_err();
+        assert_eq!(err, NominationError::Unauthorized {});
+    }
+
+    #[test]
+    fn test_accept_without_contract_admin_rights() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let dao = env.contract.address.clone();
+        ADMIN.save(deps.as_mut().storage, &dao).unwrap();
+        set_contract_admin(&mut deps.querier, Some("someone_else"));
+
+        execute_nominate_admin::<Empty>(deps.as_mut(), dao, "nominee".to_string()).unwrap();
+        let res = execute_accept_admin_nomination::<Empty>(
+            deps.as_mut(),
+            env,
+            Addr::unchecked("nominee"),
+        )
+        .unwrap();
+
+        // The DAO can't hand over a contract admin it doesn't hold.
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            ADMIN.load(deps.as_ref().storage).unwrap(),
+            Addr::unchecked("nominee")
+        );
+    }
+}
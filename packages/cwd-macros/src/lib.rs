@@ -537,6 +537,12 @@ pub fn limit_variant_count(metadata: TokenStream, input: TokenStream) -> TokenSt
 /// #[cw_serde]
 /// struct PauseInfoResponse{}
 ///
+/// #[cw_serde]
+/// struct MaxPauseDurationResponse{}
+///
+/// #[cw_serde]
+/// struct PauseState{}
+///
 /// #[pausable_query]
 /// #[cw_serde]
 /// #[derive(QueryResponses)]
@@ -549,6 +555,13 @@ pub fn limit_variant_count(metadata: TokenStream, input: TokenStream) -> TokenSt
 /// enum QueryMsg {
 ///     /// Returns information about if the contract is currently paused.
 ///     PauseInfo {},
+///     /// Returns the maximum allowed durations for pause calls.
+///     MaxPauseDuration {},
+///     /// Returns the contract's pauses along with their ids, oldest first.
+///     PauseHistory {
+///         start_after: Option<u64>,
+///         limit: Option<u32>,
+///     },
 /// }
 /// ```
 ///
@@ -563,6 +576,8 @@ pub fn limit_variant_count(metadata: TokenStream, input: TokenStream) -> TokenSt
 /// use cosmwasm_std::Empty;
 ///
 /// struct PauseInfoResponse{}
+/// struct MaxPauseDurationResponse{}
+/// struct PauseState{}
 ///
 /// #[derive(Clone)]
 /// #[pausable_query]
@@ -596,8 +611,23 @@ pub fn pausable_query(metadata: TokenStream, input: TokenStream) -> TokenStream
                 PauseInfo {}
             })
             .unwrap();
+            let max_pause_duration: Variant = syn::parse2(quote! {
+                #[returns(MaxPauseDurationResponse)]
+                MaxPauseDuration {}
+            })
+            .unwrap();
+            let pause_history: Variant = syn::parse2(quote! {
+                #[returns(Vec<(u64, PauseState)>)]
+                PauseHistory {
+                    start_after: ::std::option::Option<::std::primitive::u64>,
+                    limit: ::std::option::Option<::std::primitive::u32>
+                }
+            })
+            .unwrap();
 
             variants.push(pause_info);
+            variants.push(max_pause_duration);
+            variants.push(pause_history);
         }
         _ => {
             return syn::Error::new(
@@ -631,9 +661,35 @@ pub fn pausable_query(metadata: TokenStream, input: TokenStream) -> TokenStream
 ///
 /// ```
 /// enum ExecuteMsg {
-///     Pause { duration: u64 },
+///     Pause {
+///         duration: cw_utils::Duration,
+///         reason: Option<String>,
+///         operations: Option<Vec<String>>,
+///     },
 ///     Unpause {},
+///     UpdateMaxPauseDuration {
+///         duration: cw_utils::Duration,
+///     },
+/// }
+/// ```
+///
+/// The enum also gets a `PAUSABLE_OPERATIONS` constant listing the
+/// snake_case names of its original variants, and an `operation_name`
+/// method returning the name of the operation a message performs, so
+/// that single operations of the contract can be paused:
+///
+/// ```
+/// use cwd_macros::pausable;
+///
+/// #[pausable]
+/// enum ExecuteMsg {
+///     Fund {},
+///     ClaimRewards { amount: u64 },
 /// }
+///
+/// assert_eq!(ExecuteMsg::PAUSABLE_OPERATIONS, &["fund", "claim_rewards"]);
+/// assert_eq!(ExecuteMsg::Fund {}.operation_name(), "fund");
+/// assert_eq!(ExecuteMsg::Unpause {}.operation_name(), "unpause");
 /// ```
 ///
 /// Note that other derive macro invocations must occur after this
@@ -664,16 +720,32 @@ pub fn pausable(metadata: TokenStream, input: TokenStream) -> TokenStream {
     }
 
     let mut ast: DeriveInput = parse_macro_input!(input);
-    match &mut ast.data {
+    let operations = match &mut ast.data {
         syn::Data::Enum(DataEnum { variants, .. }) => {
+            let operations: Vec<String> = variants
+                .iter()
+                .map(|variant| to_snake_case(&variant.ident.to_string()))
+                .collect();
+
             let pause: Variant = syn::parse2(quote! { Pause {
-                duration: ::std::primitive::u64
+                duration: ::cw_utils::Duration,
+                reason: ::std::option::Option<::std::string::String>,
+                operations: ::std::option::Option<::std::vec::Vec<::std::string::String>>
             } })
             .unwrap();
             let unpause: Variant = syn::parse2(quote! { Unpause {} }).unwrap();
+            let update_max_pause_duration: Variant = syn::parse2(quote! {
+                UpdateMaxPauseDuration {
+                    duration: ::cw_utils::Duration
+                }
+            })
+            .unwrap();
 
             variants.push(pause);
             variants.push(unpause);
+            variants.push(update_max_pause_duration);
+
+            operations
         }
         _ => {
             return syn::Error::new(
@@ -685,12 +757,51 @@ pub fn pausable(metadata: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let arms = match &ast.data {
+        syn::Data::Enum(DataEnum { variants, .. }) => variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let name = to_snake_case(&variant_ident.to_string());
+            quote! { Self::#variant_ident { .. } => #name }
+        }),
+        _ => unreachable!(),
+    };
+
     quote! {
     #ast
+
+    impl #impl_generics #ident #ty_generics #where_clause {
+        /// The names of the operations which can be paused one by one.
+        pub const PAUSABLE_OPERATIONS: &'static [&'static str] = &[#(#operations),*];
+
+        /// Returns the name of the operation the message performs.
+        pub fn operation_name(&self) -> &'static str {
+            match self {
+                #(#arms),*
+            }
+        }
+    }
     }
     .into()
 }
 
+// converts a CamelCase variant name to snake_case, the way serde names the variants.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    for (i, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
+}
+
 /// Adds the necessary fields to an enum such that the enum implements the
 /// voting vault execution interface.
 ///
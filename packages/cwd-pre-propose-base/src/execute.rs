@@ -240,7 +240,10 @@ where
         }
 
         // These are the only proposal statuses we handle deposits for.
-        if new_status != Status::Closed && new_status != Status::Executed {
+        if new_status != Status::Closed
+            && new_status != Status::Vetoed
+            && new_status != Status::Executed
+        {
             return Err(PreProposeError::NotClosedOrExecuted { status: new_status });
         }
 
@@ -248,8 +251,9 @@ where
             Some((deposit_info, proposer)) => {
                 let messages = if let Some(ref deposit_info) = deposit_info {
                     // Refund can be issued if proposal if it is going to
-                    // closed or executed.
-                    let should_refund_to_proposer = (new_status == Status::Closed
+                    // closed, vetoed or executed.
+                    let should_refund_to_proposer = ((new_status == Status::Closed
+                        || new_status == Status::Vetoed)
                         && deposit_info.refund_policy == DepositRefundPolicy::Always)
                         || (new_status == Status::Executed
                             && deposit_info.refund_policy != DepositRefundPolicy::Never);
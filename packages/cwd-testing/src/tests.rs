@@ -1,4 +1,5 @@
 use cosmwasm_std::{Decimal, Uint128};
+use cwd_voting::multiple_choice::MAX_RANKING_LENGTH;
 use cwd_voting::status::Status;
 use cwd_voting::threshold::{PercentageThreshold, Threshold};
 use cwd_voting::voting::Vote;
@@ -26,6 +27,21 @@ pub struct TestSingleChoiceVote {
     pub should_execute: ShouldExecute,
 }
 
+/// The number of options on the proposals ranked choice votes are
+/// cast on. The last option is "None of the above".
+pub const RANKED_CHOICE_OPTIONS: u32 = 4;
+
+pub struct TestRankedChoiceVote {
+    /// The address casting the vote.
+    pub voter: String,
+    /// Option IDs from the most to the least preferred.
+    pub ranking: Vec<u32>,
+    /// Voting power of the address.
+    pub weight: Uint128,
+    /// If this vote is expected to execute.
+    pub should_execute: ShouldExecute,
+}
+
 pub fn test_simple_votes<F>(do_votes: F)
 where
     F: Fn(Vec<TestSingleChoiceVote>, Threshold, Status, Option<Uint128>),
@@ -593,3 +609,171 @@ where
         );
     }
 }
+
+pub fn test_ranked_choice_runoff<F>(do_votes: F)
+where
+    F: Fn(Vec<TestRankedChoiceVote>, PercentageThreshold, Status, Option<Uint128>),
+{
+    let vote = |voter: &str, ranking: Vec<u32>, weight: u128| TestRankedChoiceVote {
+        voter: voter.to_string(),
+        ranking,
+        weight: Uint128::new(weight),
+        should_execute: ShouldExecute::Yes,
+    };
+
+    // Option 2 is eliminated and its votes move to option 1, which
+    // then beats the first choice leader.
+    do_votes(
+        vec![
+            vote("ekez", vec![0], 40),
+            vote("keze", vec![1, 2], 35),
+            vote("zeke", vec![2, 1], 25),
+        ],
+        PercentageThreshold::Majority {},
+        Status::Passed,
+        None,
+    );
+
+    // Eliminated votes move to "None of the above", which wins.
+    do_votes(
+        vec![
+            vote("ekez", vec![0], 40),
+            vote("keze", vec![3], 35),
+            vote("zeke", vec![2, 3], 25),
+        ],
+        PercentageThreshold::Majority {},
+        Status::Rejected,
+        None,
+    );
+
+    // Exhausted votes leave the count.
+    do_votes(
+        vec![
+            vote("ekez", vec![0], 40),
+            vote("keze", vec![1], 35),
+            vote("zeke", vec![2], 25),
+        ],
+        PercentageThreshold::Majority {},
+        Status::Passed,
+        None,
+    );
+
+    do_votes(
+        vec![vote("ekez", vec![0, 2], 10), vote("keze", vec![1, 2], 10)],
+        PercentageThreshold::Majority {},
+        Status::Rejected,
+        None,
+    );
+
+    // Quorum counts everyone who voted, whatever they ranked.
+    do_votes(
+        vec![vote("ekez", vec![0, 1], 10)],
+        PercentageThreshold::Percent(Decimal::percent(20)),
+        Status::Rejected,
+        Some(Uint128::new(100)),
+    );
+}
+
+/// Ranks `first` followed by a random selection of the other options
+/// in random order, up to `MAX_RANKING_LENGTH` options in all.
+fn random_ranking(rng: &mut impl Rng, first: u32) -> Vec<u32> {
+    let mut rest: Vec<u32> = (0..RANKED_CHOICE_OPTIONS)
+        .filter(|option_id| *option_id != first)
+        .collect();
+    rest.shuffle(rng);
+    rest.truncate(rng.gen_range(0..MAX_RANKING_LENGTH));
+    std::iter::once(first).chain(rest).collect()
+}
+
+pub fn fuzz_ranked_choice_voting<F>(do_votes: F)
+where
+    F: Fn(Vec<TestRankedChoiceVote>, PercentageThreshold, Status, Option<Uint128>),
+{
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::<u64>::new(1, 200);
+    let none_option = RANKED_CHOICE_OPTIONS - 1;
+
+    // An option that is the first choice of a majority wins, whatever
+    // the rest of the rankings are.
+    for _ in 0..10 {
+        let winner = rng.gen_range(0..RANKED_CHOICE_OPTIONS);
+        let mut votes = vec![];
+        let mut others_sum = 0;
+        for idx in 0..50 {
+            let first = (winner + rng.gen_range(1..RANKED_CHOICE_OPTIONS)) % RANKED_CHOICE_OPTIONS;
+            let weight = rng.sample(dist);
+            others_sum += weight;
+            votes.push(TestRankedChoiceVote {
+                voter: format!("other_{}", idx),
+                ranking: random_ranking(&mut rng, first),
+                weight: Uint128::new(weight as u128),
+                should_execute: ShouldExecute::Meh,
+            });
+        }
+        let mut winner_sum = 0;
+        let mut idx = 0;
+        while winner_sum <= others_sum {
+            let weight = rng.sample(dist);
+            winner_sum += weight;
+            votes.push(TestRankedChoiceVote {
+                voter: format!("winner_{}", idx),
+                ranking: random_ranking(&mut rng, winner),
+                weight: Uint128::new(weight as u128),
+                should_execute: ShouldExecute::Meh,
+            });
+            idx += 1;
+        }
+        votes.shuffle(&mut rng);
+
+        let expected_status = if winner == none_option {
+            Status::Rejected
+        } else {
+            Status::Passed
+        };
+        do_votes(
+            votes,
+            PercentageThreshold::Majority {},
+            expected_status,
+            None,
+        );
+    }
+
+    // With a single option ranked per vote the plurality option wins.
+    for _ in 0..10 {
+        let weights: Vec<Vec<u64>> = (0..RANKED_CHOICE_OPTIONS)
+            .map(|_| (0..20).map(|_| rng.sample(dist)).collect())
+            .collect();
+        let sums: Vec<u64> = weights.iter().map(|w| w.iter().sum()).collect();
+        let max = *sums.iter().max().unwrap();
+        let leaders: Vec<usize> = (0..sums.len()).filter(|o| sums[*o] == max).collect();
+        let expected_status = if leaders.len() > 1 || leaders[0] == none_option as usize {
+            Status::Rejected
+        } else {
+            Status::Passed
+        };
+
+        let mut votes = weights
+            .into_iter()
+            .enumerate()
+            .flat_map(|(option_id, weights)| {
+                weights
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(idx, weight)| TestRankedChoiceVote {
+                        voter: format!("option_{}_{}", option_id, idx),
+                        ranking: vec![option_id as u32],
+                        weight: Uint128::new(weight as u128),
+                        should_execute: ShouldExecute::Meh,
+                    })
+            })
+            .collect::<Vec<_>>();
+        votes.shuffle(&mut rng);
+
+        do_votes(
+            votes,
+            PercentageThreshold::Majority {},
+            expected_status,
+            None,
+        );
+    }
+}
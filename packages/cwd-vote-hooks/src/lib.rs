@@ -11,6 +11,8 @@ pub enum VoteHookMsg {
         proposal_id: u64,
         voter: String,
         vote: String,
+        /// The reasoning the voter published with the vote, if any.
+        rationale: Option<String>,
     },
 }
 
@@ -30,11 +32,13 @@ pub fn new_vote_hooks(
     proposal_id: u64,
     voter: String,
     vote: String,
+    rationale: Option<String>,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_binary(&VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
         proposal_id,
         voter,
         vote,
+        rationale,
     }))?;
     let mut index: u64 = 0;
     hooks.prepare_hooks(storage, |a| {
@@ -11,4 +11,10 @@ pub enum VotingError {
 
     #[error("Min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
+
+    #[error("Weights of a weighted vote must sum to one")]
+    InvalidWeightedVote {},
+
+    #[error("Vote rationale is ({length}) bytes, must be <= ({max}) bytes")]
+    RationaleTooLong { length: u64, max: u64 },
 }
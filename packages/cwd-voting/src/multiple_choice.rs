@@ -1,31 +1,102 @@
-use cosmwasm_std::{CosmosMsg, StdError, StdResult, Uint128};
+use cosmwasm_std::{CosmosMsg, Decimal, StdError, StdResult, Uint128};
 use neutron_sdk::bindings::msg::NeutronMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
 
-/// Maximum number of choices for multiple choice votes
+/// Default maximum number of choices for multiple choice votes
 pub const MAX_NUM_CHOICES: u32 = 10;
+/// Upper bound for the configurable maximum number of choices, which
+/// keeps the tally of a proposal small enough to load with each vote.
+pub const MAX_NUM_CHOICES_LIMIT: u32 = 100;
+/// Maximum number of options a ranked choice vote may rank. Together
+/// with `MAX_NUM_CHOICES` this bounds the number of distinct rankings a
+/// ranked choice proposal holds, and so the cost of the tally.
+pub const MAX_RANKING_LENGTH: usize = 2;
 const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 
 /// Determines how many choices may be selected.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters rank the options in order of preference and the winner
+    /// is found by instant-runoff elimination.
+    RankedChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters approve up to `max_selections` options, each receiving
+    /// the full voting power of the voter. The option with the most
+    /// approvals wins.
+    Approval {
+        quorum: PercentageThreshold,
+        max_selections: u32,
+    },
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
-            VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::SingleChoice { quorum } | VotingStrategy::RankedChoice { quorum } => {
+                validate_quorum(quorum)
+            }
+            VotingStrategy::Approval {
+                quorum,
+                max_selections,
+            } => {
+                if *max_selections == 0 {
+                    return Err(ThresholdError::ZeroMaxSelections {});
+                }
+                validate_quorum(quorum)
+            }
         }
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
-            VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::SingleChoice { quorum }
+            | VotingStrategy::RankedChoice { quorum }
+            | VotingStrategy::Approval { quorum, .. } => *quorum,
+        }
+    }
+}
+
+/// Determines which options of a passed proposal are executed.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// Only the winning option is executed.
+    Winner {},
+    /// The `n` options with the most votes are executed.
+    TopN { n: u32 },
+    /// Every option receiving at least `percent` of the voting power
+    /// that voted is executed.
+    Threshold { percent: Decimal },
+}
+
+impl ExecutionMode {
+    pub fn validate(&self) -> Result<(), ThresholdError> {
+        match self {
+            ExecutionMode::Winner {} => Ok(()),
+            ExecutionMode::TopN { n } => {
+                if *n == 0 {
+                    Err(ThresholdError::ZeroWinners {})
+                } else {
+                    Ok(())
+                }
+            }
+            ExecutionMode::Threshold { percent } => {
+                if percent.is_zero() {
+                    Err(ThresholdError::ZeroThreshold {})
+                } else if *percent > Decimal::one() {
+                    Err(ThresholdError::UnreachableThreshold {})
+                } else {
+                    Ok(())
+                }
+            }
         }
     }
 }
@@ -43,6 +114,176 @@ impl std::fmt::Display for MultipleChoiceVote {
     }
 }
 
+/// A ranked choice vote, listing option IDs from the most to the
+/// least preferred. Options left out of the ranking are never
+/// preferred by the vote.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RankedChoiceVote {
+    pub ranking: Vec<u32>,
+}
+
+impl RankedChoiceVote {
+    /// Checks that the ranking holds between one and
+    /// `MAX_RANKING_LENGTH` options, ranks no option twice and only
+    /// ranks options that exist out of `num_choices`.
+    pub fn is_valid(&self, num_choices: usize) -> bool {
+        !self.ranking.is_empty()
+            && self.ranking.len() <= MAX_RANKING_LENGTH
+            && self.ranking.iter().enumerate().all(|(idx, option_id)| {
+                (*option_id as usize) < num_choices && !self.ranking[..idx].contains(option_id)
+            })
+    }
+
+    /// The most preferred option of the vote, if any.
+    pub fn first_choice(&self) -> Option<MultipleChoiceVote> {
+        self.ranking.first().map(|option_id| MultipleChoiceVote {
+            option_id: *option_id,
+        })
+    }
+}
+
+impl std::fmt::Display for RankedChoiceVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranking: Vec<String> = self.ranking.iter().map(u32::to_string).collect();
+        write!(f, "{}", ranking.join(","))
+    }
+}
+
+/// An approval vote, listing the option IDs the voter approves of.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ApprovalVote {
+    pub option_ids: Vec<u32>,
+}
+
+impl ApprovalVote {
+    /// Checks that between one and `max_selections` options are
+    /// approved, none twice and only options that exist out of
+    /// `num_choices`. "None of the above", the last option, may only
+    /// be approved on its own.
+    pub fn is_valid(&self, num_choices: usize, max_selections: u32) -> bool {
+        let none_of_the_above = num_choices.saturating_sub(1);
+        !self.option_ids.is_empty()
+            && self.option_ids.len() <= max_selections as usize
+            && self.option_ids.iter().enumerate().all(|(idx, option_id)| {
+                (*option_id as usize) < num_choices
+                    && !self.option_ids[..idx].contains(option_id)
+                    && (self.option_ids.len() == 1 || *option_id as usize != none_of_the_above)
+            })
+    }
+}
+
+impl std::fmt::Display for ApprovalVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let option_ids: Vec<String> = self.option_ids.iter().map(u32::to_string).collect();
+        write!(f, "{}", option_ids.join(","))
+    }
+}
+
+/// The voting power behind one ranking.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RankedChoiceTally {
+    pub ranking: Vec<u32>,
+    pub power: Uint128,
+}
+
+/// Holds the voting power behind each distinct ranking cast on a
+/// ranked choice proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct RankedChoiceVotes {
+    pub rankings: Vec<RankedChoiceTally>,
+}
+
+impl RankedChoiceVotes {
+    // Add a ranked vote to the tally
+    pub fn add_vote(&mut self, vote: &RankedChoiceVote, weight: Uint128) -> StdResult<()> {
+        match self
+            .rankings
+            .iter_mut()
+            .find(|tally| tally.ranking == vote.ranking)
+        {
+            Some(tally) => {
+                tally.power = tally
+                    .power
+                    .checked_add(weight)
+                    .map_err(StdError::overflow)?
+            }
+            None => self.rankings.push(RankedChoiceTally {
+                ranking: vote.ranking.clone(),
+                power: weight,
+            }),
+        }
+        Ok(())
+    }
+
+    // Remove a ranked vote from the tally
+    pub fn remove_vote(&mut self, vote: &RankedChoiceVote, weight: Uint128) -> StdResult<()> {
+        let idx = self
+            .rankings
+            .iter()
+            .position(|tally| tally.ranking == vote.ranking)
+            .ok_or_else(|| StdError::not_found("ranking"))?;
+        let power = self.rankings[idx]
+            .power
+            .checked_sub(weight)
+            .map_err(StdError::overflow)?;
+        if power.is_zero() {
+            self.rankings.remove(idx);
+        } else {
+            self.rankings[idx].power = power;
+        }
+        Ok(())
+    }
+
+    /// Runs instant-runoff elimination over `num_choices` options and
+    /// returns the winning option, or `None` on a tie.
+    ///
+    /// Each round the power behind a ranking counts for its most
+    /// preferred option that has not been eliminated. An option
+    /// counted by more than half of the power still in the count
+    /// wins. Otherwise all options with the lowest count are
+    /// eliminated. If every remaining option has the lowest count,
+    /// the vote is tied.
+    pub fn instant_runoff(&self, num_choices: usize) -> Option<u32> {
+        let mut eliminated = vec![false; num_choices];
+        loop {
+            let mut counts = vec![Uint128::zero(); num_choices];
+            for RankedChoiceTally { ranking, power } in self.rankings.iter() {
+                if let Some(option_id) = ranking
+                    .iter()
+                    .map(|option_id| *option_id as usize)
+                    .find(|option_id| !eliminated[*option_id])
+                {
+                    counts[option_id] += *power;
+                }
+            }
+            let continuing: Uint128 = counts.iter().sum();
+            let remaining: Vec<usize> = (0..num_choices)
+                .filter(|option_id| !eliminated[*option_id])
+                .collect();
+
+            if let Some(winner) = remaining
+                .iter()
+                .find(|option_id| counts[**option_id] > continuing - counts[**option_id])
+            {
+                return Some(*winner as u32);
+            }
+
+            let lowest = remaining.iter().map(|option_id| counts[*option_id]).min()?;
+            let lowest: Vec<usize> = remaining
+                .iter()
+                .copied()
+                .filter(|option_id| counts[*option_id] == lowest)
+                .collect();
+            if lowest.len() == remaining.len() {
+                return None;
+            }
+            for option_id in lowest {
+                eliminated[option_id] = true;
+            }
+        }
+    }
+}
+
 // Holds the vote weights for each option
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct MultipleChoiceVotes {
@@ -114,7 +355,7 @@ pub struct CheckedMultipleChoiceOptions {
 /// A verified option that has all fields needed for voting.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct CheckedMultipleChoiceOption {
-    // This is the index of the option in both the vote_weights and proposal.choices vectors.
+    // This is the index of the option in both the vote_weights vector and the options of the proposal.
     // Workaround due to not being able to use HashMaps in Cosmwasm.
     pub index: u32,
     pub option_type: MultipleChoiceOptionType,
@@ -124,8 +365,8 @@ pub struct CheckedMultipleChoiceOption {
 }
 
 impl MultipleChoiceOptions {
-    pub fn into_checked(self) -> StdResult<CheckedMultipleChoiceOptions> {
-        if self.options.len() < 2 || self.options.len() > MAX_NUM_CHOICES as usize {
+    pub fn into_checked(self, max_num_choices: u32) -> StdResult<CheckedMultipleChoiceOptions> {
+        if self.options.len() < 2 || self.options.len() > max_num_choices as usize {
             return Err(StdError::GenericErr {
                 msg: "Wrong number of choices".to_string(),
             });
@@ -216,7 +457,7 @@ mod test {
 
         let mc_options = super::MultipleChoiceOptions { options };
 
-        let checked_mc_options = mc_options.into_checked().unwrap();
+        let checked_mc_options = mc_options.into_checked(MAX_NUM_CHOICES).unwrap();
         assert_eq!(checked_mc_options.options.len(), 3);
         assert_eq!(
             checked_mc_options.options[0].option_type,
@@ -244,6 +485,153 @@ mod test {
         );
     }
 
+    fn ranked(ranking: &[u32], power: u128) -> RankedChoiceTally {
+        RankedChoiceTally {
+            ranking: ranking.to_vec(),
+            power: Uint128::new(power),
+        }
+    }
+
+    #[test]
+    fn test_ranked_choice_vote_validation() {
+        let vote = |ranking: &[u32]| RankedChoiceVote {
+            ranking: ranking.to_vec(),
+        };
+        assert!(vote(&[0, 2]).is_valid(3));
+        assert!(vote(&[1]).is_valid(3));
+        assert!(!vote(&[]).is_valid(3));
+        assert!(!vote(&[0, 3]).is_valid(3));
+        assert!(!vote(&[1, 1]).is_valid(3));
+        // Rankings longer than `MAX_RANKING_LENGTH` are rejected.
+        assert!(!vote(&[0, 2, 1]).is_valid(3));
+        assert_eq!(vote(&[2, 0, 1]).to_string(), "2,0,1");
+        assert_eq!(
+            vote(&[2, 0, 1]).first_choice(),
+            Some(MultipleChoiceVote { option_id: 2 })
+        );
+        assert_eq!(vote(&[]).first_choice(), None);
+    }
+
+    #[test]
+    fn test_approval_vote_validation() {
+        let vote = |option_ids: &[u32]| ApprovalVote {
+            option_ids: option_ids.to_vec(),
+        };
+        assert!(vote(&[1, 0]).is_valid(3, 2));
+        assert!(!vote(&[0, 1]).is_valid(3, 1));
+        // "None of the above" may only be approved on its own.
+        assert!(vote(&[2]).is_valid(3, 2));
+        assert!(!vote(&[0, 2]).is_valid(3, 2));
+        assert!(!vote(&[2, 1]).is_valid(3, 2));
+        assert!(!vote(&[]).is_valid(3, 2));
+        assert!(!vote(&[3]).is_valid(3, 2));
+        assert!(!vote(&[1, 1]).is_valid(3, 2));
+        assert_eq!(vote(&[2, 0]).to_string(), "2,0");
+    }
+
+    #[test]
+    fn test_voting_strategy_validation() {
+        let quorum = PercentageThreshold::Majority {};
+        VotingStrategy::Approval {
+            quorum,
+            max_selections: 1,
+        }
+        .validate()
+        .unwrap();
+        assert_eq!(
+            VotingStrategy::Approval {
+                quorum,
+                max_selections: 0,
+            }
+            .validate(),
+            Err(ThresholdError::ZeroMaxSelections {})
+        );
+    }
+
+    #[test]
+    fn test_execution_mode_validation() {
+        ExecutionMode::Winner {}.validate().unwrap();
+        ExecutionMode::TopN { n: 3 }.validate().unwrap();
+        ExecutionMode::Threshold {
+            percent: Decimal::percent(20),
+        }
+        .validate()
+        .unwrap();
+        assert_eq!(
+            ExecutionMode::TopN { n: 0 }.validate(),
+            Err(ThresholdError::ZeroWinners {})
+        );
+        assert_eq!(
+            ExecutionMode::Threshold {
+                percent: Decimal::zero()
+            }
+            .validate(),
+            Err(ThresholdError::ZeroThreshold {})
+        );
+        assert_eq!(
+            ExecutionMode::Threshold {
+                percent: Decimal::percent(101)
+            }
+            .validate(),
+            Err(ThresholdError::UnreachableThreshold {})
+        );
+    }
+
+    #[test]
+    fn test_ranked_choice_votes() {
+        let first = RankedChoiceVote {
+            ranking: vec![0, 1],
+        };
+        let second = RankedChoiceVote {
+            ranking: vec![1, 0],
+        };
+        let mut votes = RankedChoiceVotes::default();
+        votes.add_vote(&first, Uint128::new(10)).unwrap();
+        votes.add_vote(&second, Uint128::new(5)).unwrap();
+        votes.add_vote(&first, Uint128::new(1)).unwrap();
+        assert_eq!(
+            votes.rankings,
+            vec![ranked(&[0, 1], 11), ranked(&[1, 0], 5)]
+        );
+
+        votes.remove_vote(&first, Uint128::new(11)).unwrap();
+        assert_eq!(votes.rankings, vec![ranked(&[1, 0], 5)]);
+        votes.remove_vote(&first, Uint128::new(1)).unwrap_err();
+    }
+
+    #[test]
+    fn test_instant_runoff() {
+        // A first choice majority wins in the first round.
+        let votes = RankedChoiceVotes {
+            rankings: vec![ranked(&[0, 1], 6), ranked(&[1, 0], 5)],
+        };
+        assert_eq!(votes.instant_runoff(3), Some(0));
+
+        // 2 is eliminated and its votes transfer to 1, which overtakes 0.
+        let votes = RankedChoiceVotes {
+            rankings: vec![ranked(&[0], 40), ranked(&[1, 2], 35), ranked(&[2, 1], 25)],
+        };
+        assert_eq!(votes.instant_runoff(4), Some(1));
+
+        // Exhausted rankings are left out of the later rounds.
+        let votes = RankedChoiceVotes {
+            rankings: vec![ranked(&[0], 40), ranked(&[1], 35), ranked(&[2], 25)],
+        };
+        assert_eq!(votes.instant_runoff(3), Some(0));
+
+        // Options tied for last are eliminated together.
+        let votes = RankedChoiceVotes {
+            rankings: vec![ranked(&[0], 4), ranked(&[1, 0], 3), ranked(&[2, 0], 3)],
+        };
+        assert_eq!(votes.instant_runoff(3), Some(0));
+
+        let votes = RankedChoiceVotes {
+            rankings: vec![ranked(&[0, 2], 5), ranked(&[1, 2], 5)],
+        };
+        assert_eq!(votes.instant_runoff(3), None);
+        assert_eq!(RankedChoiceVotes::default().instant_runoff(3), None);
+    }
+
     #[should_panic(expected = "Wrong number of choices")]
     #[test]
     fn test_into_checked_wrong_num_choices() {
@@ -253,6 +641,6 @@ mod test {
         }];
 
         let mc_options = super::MultipleChoiceOptions { options };
-        mc_options.into_checked().unwrap();
+        mc_options.into_checked(MAX_NUM_CHOICES).unwrap();
     }
 }
@@ -1,10 +1,29 @@
 use cosmwasm_std::Addr;
 
+use crate::error::VotingError;
 use crate::status::Status;
 
 /// Default limit for proposal pagination.
 pub const DEFAULT_LIMIT: u64 = 30;
 pub const MAX_PROPOSAL_SIZE: u64 = 30_000;
+/// Default maximum length of vote rationales in bytes.
+pub const DEFAULT_MAX_RATIONALE_LENGTH: u64 = 1024;
+
+/// Checks that a vote rationale is no longer than `max_length`, or
+/// `DEFAULT_MAX_RATIONALE_LENGTH` if no maximum is configured.
+pub fn validate_rationale(
+    rationale: Option<&str>,
+    max_length: Option<u64>,
+) -> Result<(), VotingError> {
+    let max = max_length.unwrap_or(DEFAULT_MAX_RATIONALE_LENGTH);
+    match rationale {
+        Some(rationale) if rationale.len() as u64 > max => Err(VotingError::RationaleTooLong {
+            length: rationale.len() as u64,
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
 
 pub trait Proposal {
     fn proposer(&self) -> Addr;
@@ -11,6 +11,9 @@ pub enum Status {
     Rejected,
     /// The proposal has been passed but has not been executed.
     Passed,
+    /// The proposal has been passed and may not be executed until its
+    /// timelock expires.
+    PassedTimelocked,
     /// The proposal has been passed and executed.
     Executed,
     /// The proposal has failed or expired and has been closed. A
@@ -18,6 +21,8 @@ pub enum Status {
     Closed,
     /// The proposal's execution failed.
     ExecutionFailed,
+    /// The proposal has been vetoed.
+    Vetoed,
 }
 
 impl std::fmt::Display for Status {
@@ -26,9 +31,11 @@ impl std::fmt::Display for Status {
             Status::Open => write!(f, "open"),
             Status::Rejected => write!(f, "rejected"),
             Status::Passed => write!(f, "passed"),
+            Status::PassedTimelocked => write!(f, "passed_timelocked"),
             Status::Executed => write!(f, "executed"),
             Status::Closed => write!(f, "closed"),
             Status::ExecutionFailed => write!(f, "execution_failed"),
+            Status::Vetoed => write!(f, "vetoed"),
         }
     }
 }
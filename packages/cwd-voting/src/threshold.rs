@@ -11,6 +11,12 @@ pub enum ThresholdError {
 
     #[error("Not possible to reach required (passing) threshold")]
     UnreachableThreshold {},
+
+    #[error("Max selections cannot be zero")]
+    ZeroMaxSelections {},
+
+    #[error("Number of winning options cannot be zero")]
+    ZeroWinners {},
 }
 
 /// A percentage of voting power that must vote yes for a proposal to
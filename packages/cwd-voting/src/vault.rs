@@ -1,4 +1,4 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -16,3 +16,85 @@ pub struct BonderBalanceResponse {
     pub address: String,
     pub balance: Uint128,
 }
+
+/// Represents a response model for voting vault's Delegation calls.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DelegationResponse {
+    /// The address the bonder delegates their voting power to, if any.
+    pub delegate: Option<Addr>,
+    /// The bonded balance of the bonder.
+    pub power: Uint128,
+}
+
+/// The query of voting vaults supporting delegation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegationQuery {
+    Delegation {
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+/// Queries the delegate and bonded balance of `address` at `height`
+/// on a voting vault supporting delegation.
+pub fn get_delegation(
+    deps: Deps,
+    vault: &Addr,
+    address: &Addr,
+    height: u64,
+) -> StdResult<DelegationResponse> {
+    deps.querier.query_wasm_smart(
+        vault,
+        &DelegationQuery::Delegation {
+            address: address.to_string(),
+            height: Some(height),
+        },
+    )
+}
+
+/// Represents a response model for voting registry's
+/// DelegatedPowerAtHeight calls.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DelegatedPowerResponse {
+    /// The address the bonder delegates their voting power in the
+    /// vault to, if any and if the vault is active at the height.
+    pub delegate: Option<Addr>,
+    /// The voting power the delegation adds to the delegate, after the
+    /// power transform of the vault is applied.
+    pub power: Uint128,
+    pub height: u64,
+}
+
+/// The query of voting registries exposing the voting power delegated
+/// in one of their vaults.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegatedPowerQuery {
+    DelegatedPowerAtHeight {
+        vault: String,
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+/// Queries the delegate of `address` in `vault` at `height` and the
+/// voting power the delegation adds to them on a voting registry.
+pub fn get_delegated_power(
+    deps: Deps,
+    registry: &Addr,
+    vault: &Addr,
+    address: &Addr,
+    height: u64,
+) -> StdResult<DelegatedPowerResponse> {
+    deps.querier.query_wasm_smart(
+        registry,
+        &DelegatedPowerQuery::DelegatedPowerAtHeight {
+            vault: vault.to_string(),
+            address: address.to_string(),
+            height: Some(height),
+        },
+    )
+}
@@ -30,6 +30,68 @@ pub enum Vote {
     Abstain,
 }
 
+/// A vote splitting the voting power of the voter between the
+/// positions, for example on behalf of the clients of a custodian.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+pub struct WeightedVote {
+    /// The fraction of the voting power supporting the proposal.
+    pub yes: Decimal,
+    /// The fraction of the voting power opposing the proposal.
+    pub no: Decimal,
+    /// The fraction of the voting power abstaining.
+    pub abstain: Decimal,
+}
+
+impl WeightedVote {
+    /// Checks that the weights sum to one.
+    pub fn validate(&self) -> Result<(), crate::error::VotingError> {
+        let total = self
+            .yes
+            .checked_add(self.no)
+            .and_then(|total| total.checked_add(self.abstain));
+        match total {
+            Ok(total) if total == Decimal::one() => Ok(()),
+            _ => Err(crate::error::VotingError::InvalidWeightedVote {}),
+        }
+    }
+
+    /// The position with the most weight. Ties are resolved in favour
+    /// of yes, then no.
+    pub fn predominant(&self) -> Vote {
+        if self.yes >= self.no && self.yes >= self.abstain {
+            Vote::Yes
+        } else if self.no >= self.abstain {
+            Vote::No
+        } else {
+            Vote::Abstain
+        }
+    }
+
+    /// Splits `power` between the positions by their weights. Power
+    /// lost to rounding down is given to the predominant position so
+    /// that the split always sums to `power`.
+    pub fn split(&self, power: Uint128) -> Votes {
+        let mut votes = Votes {
+            yes: power * self.yes,
+            no: power * self.no,
+            abstain: power * self.abstain,
+        };
+        let remainder = power - votes.total();
+        votes.add_vote(self.predominant(), remainder);
+        votes
+    }
+}
+
+impl std::fmt::Display for WeightedVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "yes:{},no:{},abstain:{}",
+            self.yes, self.no, self.abstain
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
 pub struct MultipleChoiceVote {
     // A vote indicates which option the user has selected.
@@ -214,6 +276,25 @@ impl Votes {
         }
     }
 
+    /// Adds a weighted vote to the votes, splitting `power` between
+    /// the positions.
+    pub fn add_weighted_vote(&mut self, vote: &WeightedVote, power: Uint128) {
+        let split = vote.split(power);
+        self.add_vote(Vote::Yes, split.yes);
+        self.add_vote(Vote::No, split.no);
+        self.add_vote(Vote::Abstain, split.abstain);
+    }
+
+    /// Removes a weighted vote from the votes. The vote being removed
+    /// must have been previously added with the same power or this
+    /// method will cause an overflow.
+    pub fn remove_weighted_vote(&mut self, vote: &WeightedVote, power: Uint128) {
+        let split = vote.split(power);
+        self.remove_vote(Vote::Yes, split.yes);
+        self.remove_vote(Vote::No, split.no);
+        self.remove_vote(Vote::Abstain, split.abstain);
+    }
+
     /// Computes the total number of votes cast.
     ///
     /// NOTE: The total number of votes avaliable from a voting module
@@ -302,6 +383,49 @@ mod test {
         assert_eq!(votes.abstain, Uint128::new(40));
     }
 
+    #[test]
+    fn count_weighted_votes() {
+        let vote = WeightedVote {
+            yes: Decimal::percent(50),
+            no: Decimal::percent(30),
+            abstain: Decimal::percent(20),
+        };
+        vote.validate().unwrap();
+
+        let mut votes = Votes::with_yes(Uint128::new(5));
+        votes.add_weighted_vote(&vote, Uint128::new(11));
+        // 5.5, 3.3 and 2.2 round down, the remainder goes to yes.
+        assert_eq!(
+            votes,
+            Votes {
+                yes: Uint128::new(5 + 6),
+                no: Uint128::new(3),
+                abstain: Uint128::new(2),
+            }
+        );
+        votes.remove_weighted_vote(&vote, Uint128::new(11));
+        assert_eq!(votes, Votes::with_yes(Uint128::new(5)));
+
+        assert_eq!(
+            WeightedVote {
+                yes: Decimal::percent(50),
+                no: Decimal::percent(30),
+                abstain: Decimal::percent(30),
+            }
+            .validate(),
+            Err(crate::error::VotingError::InvalidWeightedVote {})
+        );
+        assert_eq!(
+            WeightedVote {
+                yes: Decimal::percent(20),
+                no: Decimal::percent(40),
+                abstain: Decimal::percent(40),
+            }
+            .predominant(),
+            Vote::No
+        );
+    }
+
     #[test]
     fn vote_comparisons() {
         assert!(!compare_vote_count(
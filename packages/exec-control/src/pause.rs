@@ -1,10 +1,43 @@
-use cosmwasm_std::Addr;
+use std::cmp::Ordering;
+
+use cosmwasm_std::{Addr, BlockInfo, Order, Response, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-/// The maximum allowed duration for pause calls in blocks.
-pub const MAX_PAUSE_DURATION: u64 = 200000;
+/// The maximum allowed number of blocks for pause calls unless the contract configures its own.
+pub const DEFAULT_MAX_PAUSE_BLOCKS: u64 = 200000;
+
+/// The maximum allowed number of seconds for pause calls unless the contract configures its own.
+/// Roughly matches DEFAULT_MAX_PAUSE_BLOCKS at a block time of 6 seconds.
+pub const DEFAULT_MAX_PAUSE_SECONDS: u64 = 1209600;
+
+/// The maximum allowed length of a pause reason, in bytes.
+pub const MAX_PAUSE_REASON_LENGTH: usize = 1024;
+
+/// The number of pauses returned by the pause history query if no limit is set.
+pub const DEFAULT_PAUSE_HISTORY_LIMIT: u32 = 30;
+
+/// The maximum number of pauses returned by the pause history query.
+pub const MAX_PAUSE_HISTORY_LIMIT: u32 = 100;
+
+/// All the pauses the contract has ever been through, keyed by a sequential id. The last
+/// entry describes the current pause, if any.
+pub const PAUSE_HISTORY: Map<u64, PauseState> = Map::new("pause_history");
+
+/// The maximum allowed number of blocks for pause calls configured for the contract. Falls back to
+/// DEFAULT_MAX_PAUSE_BLOCKS if not set.
+pub const MAX_PAUSE_BLOCKS: Item<u64> = Item::new("max_pause_blocks");
+
+/// The maximum allowed number of seconds for pause calls configured for the contract. Falls back to
+/// DEFAULT_MAX_PAUSE_SECONDS if not set.
+pub const MAX_PAUSE_SECONDS: Item<u64> = Item::new("max_pause_seconds");
+
+/// The height until which the contract was paused before pauses were recorded in PAUSE_HISTORY.
+/// Only read when migrating such contracts.
+const LEGACY_PAUSED_UNTIL: Item<Option<u64>> = Item::new("paused_until");
 
 // checks whether the sender is capable to pause a contract
 pub fn can_pause(
@@ -30,33 +63,780 @@ pub fn can_unpause(sender: &Addr, main_dao_address: &Addr) -> Result<(), PauseEr
     Ok(())
 }
 
-/// checks whether the duration is not greater than MAX_PAUSE_DURATION.
-pub fn validate_duration(duration: u64) -> Result<(), PauseError> {
-    if duration.gt(&MAX_PAUSE_DURATION) {
-        return Err(PauseError::InvalidDuration(format!(
-            "Pause duration is too big: it's only possible to pause the contract for {} blocks",
-            MAX_PAUSE_DURATION
-        )));
+/// checks whether the duration is not zero and does not exceed the maximum duration. Both
+/// durations must be set in the same units.
+pub fn validate_duration(duration: Duration, max_duration: Duration) -> Result<(), PauseError> {
+    match (duration, max_duration) {
+        (Duration::Height(0), _) | (Duration::Time(0), _) => Err(PauseError::InvalidDuration(
+            "Pause duration must not be zero".to_string(),
+        )),
+        (Duration::Height(blocks), Duration::Height(max_blocks)) if blocks > max_blocks => {
+            Err(PauseError::InvalidDuration(format!(
+                "Pause duration is too big: it's only possible to pause the contract for {} blocks",
+                max_blocks
+            )))
+        }
+        (Duration::Time(seconds), Duration::Time(max_seconds)) if seconds > max_seconds => {
+            Err(PauseError::InvalidDuration(format!(
+                "Pause duration is too big: it's only possible to pause the contract for {} seconds",
+                max_seconds
+            )))
+        }
+        (Duration::Height(_), Duration::Time(_)) => Err(PauseError::InvalidDuration(
+            "Pause duration must be set in seconds".to_string(),
+        )),
+        (Duration::Time(_), Duration::Height(_)) => Err(PauseError::InvalidDuration(
+            "Pause duration must be set in blocks".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// checks whether the reason is not longer than MAX_PAUSE_REASON_LENGTH.
+pub fn validate_reason(reason: &Option<String>) -> Result<(), PauseError> {
+    if let Some(reason) = reason {
+        if reason.len() > MAX_PAUSE_REASON_LENGTH {
+            return Err(PauseError::InvalidReason(format!(
+                "Pause reason is too long: it's only possible to use up to {} bytes",
+                MAX_PAUSE_REASON_LENGTH
+            )));
+        }
     }
     Ok(())
 }
 
-#[derive(Error, Debug, PartialEq, Eq)]
+/// checks whether the operations are not empty and are known to the contract.
+pub fn validate_operations(
+    operations: &Option<Vec<String>>,
+    known_operations: &[&str],
+) -> Result<(), PauseError> {
+    if let Some(operations) = operations {
+        if operations.is_empty() {
+            return Err(PauseError::InvalidOperations(
+                "the list of operations to pause cannot be empty".to_string(),
+            ));
+        }
+        if let Some(unknown) = operations
+            .iter()
+            .find(|op| !known_operations.contains(&op.as_str()))
+        {
+            return Err(PauseError::InvalidOperations(format!(
+                "unknown operation: {}",
+                unknown
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A request to pause a contract.
+pub struct PauseRequest<'a> {
+    /// For how long to pause the contract.
+    pub duration: Duration,
+    /// Why the contract is paused.
+    pub reason: Option<String>,
+    /// The operations to pause. `None` pauses the whole contract.
+    pub operations: Option<Vec<String>>,
+    /// The names of the contract's operations which can be paused one by one.
+    pub known_operations: &'a [&'a str],
+}
+
+/// Pauses the contract on behalf of `sender` and records the pause in the pause history. The
+/// sender is expected to be either the main DAO or the security DAO.
+///
+/// A pause that is already in effect is never shortened nor narrowed, and no operation is paused
+/// for longer than requested: a new pause only replaces the current one if it pauses at least the
+/// same operations until at least the same time.
+pub fn execute_pause<T>(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    sender: Addr,
+    main_dao_address: &Addr,
+    security_dao_address: &Addr,
+    request: PauseRequest,
+) -> Result<Response<T>, PauseError> {
+    can_pause(&sender, main_dao_address, security_dao_address)?;
+    validate_duration(
+        request.duration,
+        get_max_pause_duration(storage, &request.duration)?,
+    )?;
+    validate_reason(&request.reason)?;
+    validate_operations(&request.operations, request.known_operations)?;
+
+    let paused_until = request.duration.after(block);
+    let operations = request.operations;
+
+    let last = last_pause(storage)?;
+    if let Some((_, current)) = &last {
+        if current.is_active(block) {
+            let pauses_new_operations = match (&current.operations, &operations) {
+                (None, _) => false,
+                (Some(_), None) => true,
+                (Some(current_ops), Some(new_ops)) => {
+                    new_ops.iter().any(|op| !current_ops.contains(op))
+                }
+            };
+            let keeps_current_operations = match (&current.operations, &operations) {
+                (_, None) => true,
+                (None, Some(_)) => false,
+                (Some(current_ops), Some(new_ops)) => {
+                    current_ops.iter().all(|op| new_ops.contains(op))
+                }
+            };
+            let ordering = paused_until.partial_cmp(&current.until).ok_or_else(|| {
+                PauseError::InvalidDuration(
+                    "pause duration must be set in the same units as the current pause".to_string(),
+                )
+            })?;
+            if !pauses_new_operations && ordering != Ordering::Greater {
+                return Err(PauseError::InvalidDuration(
+                    "contracts are already paused for a greater or equal duration".to_string(),
+                ));
+            }
+            // Combining the pauses would pause some operations for longer than requested.
+            if !keeps_current_operations || ordering == Ordering::Less {
+                return Err(PauseError::InvalidOperations(
+                    "a new pause must cover the paused operations until at least the end of the current pause"
+                        .to_string(),
+                ));
+            }
+        }
+    }
+
+    let id = last.map_or(0, |(id, _)| id + 1);
+    PAUSE_HISTORY.save(
+        storage,
+        id,
+        &PauseState {
+            paused_by: sender.clone(),
+            reason: request.reason.clone(),
+            operations: operations.clone(),
+            from_height: block.height,
+            until: paused_until,
+            unpaused_by: None,
+            unpaused_at_height: None,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_pause")
+        .add_attribute("sender", sender)
+        .add_attribute("paused_until", paused_until.to_string())
+        .add_attribute("reason", request.reason.unwrap_or_default())
+        .add_attribute(
+            "operations",
+            operations.map_or_else(|| "all".to_string(), |ops| ops.join(",")),
+        ))
+}
+
+/// Unpauses the contract on behalf of `sender`, who is expected to be the main DAO. The
+/// current pause, if any, is marked as lifted in the pause history.
+pub fn execute_unpause<T>(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    sender: Addr,
+    main_dao_address: &Addr,
+) -> Result<Response<T>, PauseError> {
+    can_unpause(&sender, main_dao_address)?;
+
+    if let Some((id, mut current)) = last_pause(storage)? {
+        if current.is_active(block) {
+            current.unpaused_by = Some(sender.clone());
+            current.unpaused_at_height = Some(block.height);
+            PAUSE_HISTORY.save(storage, id, &current)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_unpause")
+        .add_attribute("sender", sender))
+}
+
+/// Sets the maximum allowed duration for pause calls on behalf of `sender`, who is expected to be
+/// the main DAO. Only the maximum for the units `max_duration` is set in is changed.
+pub fn execute_update_max_pause_duration<T>(
+    storage: &mut dyn Storage,
+    sender: Addr,
+    main_dao_address: &Addr,
+    max_duration: Duration,
+) -> Result<Response<T>, PauseError> {
+    can_unpause(&sender, main_dao_address)?;
+    match max_duration {
+        Duration::Height(0) | Duration::Time(0) => {
+            return Err(PauseError::InvalidDuration(
+                "Max pause duration must not be zero".to_string(),
+            ))
+        }
+        Duration::Height(blocks) => MAX_PAUSE_BLOCKS.save(storage, &blocks)?,
+        Duration::Time(seconds) => MAX_PAUSE_SECONDS.save(storage, &seconds)?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_update_max_pause_duration")
+        .add_attribute("sender", sender)
+        .add_attribute("max_pause_duration", max_duration.to_string()))
+}
+
+/// Moves a pause recorded by a contract version predating PAUSE_HISTORY into the pause history,
+/// so that a contract paused at migration time stays paused. The legacy pause didn't record who
+/// paused the contract, so it is attributed to `paused_by`.
+pub fn migrate_legacy_pause(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    paused_by: &Addr,
+) -> StdResult<()> {
+    let legacy_paused_until = LEGACY_PAUSED_UNTIL.may_load(storage)?.flatten();
+    LEGACY_PAUSED_UNTIL.remove(storage);
+
+    if let Some(paused_until) = legacy_paused_until {
+        if paused_until > block.height && last_pause(storage)?.is_none() {
+            PAUSE_HISTORY.save(
+                storage,
+                0,
+                &PauseState {
+                    paused_by: paused_by.clone(),
+                    reason: None,
+                    operations: None,
+                    from_height: block.height,
+                    until: Expiration::AtHeight(paused_until),
+                    unpaused_by: None,
+                    unpaused_at_height: None,
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the maximum allowed duration for pause calls set in the same units as `duration`.
+pub fn get_max_pause_duration(storage: &dyn Storage, duration: &Duration) -> StdResult<Duration> {
+    let max_durations = get_max_pause_durations(storage)?;
+    Ok(match duration {
+        Duration::Height(_) => Duration::Height(max_durations.blocks),
+        Duration::Time(_) => Duration::Time(max_durations.seconds),
+    })
+}
+
+/// Returns the maximum allowed durations for pause calls in both units.
+pub fn get_max_pause_durations(storage: &dyn Storage) -> StdResult<MaxPauseDurationResponse> {
+    Ok(MaxPauseDurationResponse {
+        blocks: MAX_PAUSE_BLOCKS
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_MAX_PAUSE_BLOCKS),
+        seconds: MAX_PAUSE_SECONDS
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_MAX_PAUSE_SECONDS),
+    })
+}
+
+/// Returns whether the contract is currently paused.
+pub fn get_pause_info(storage: &dyn Storage, block: &BlockInfo) -> StdResult<PauseInfoResponse> {
+    Ok(match current_pause(storage, block)? {
+        Some(pause) => PauseInfoResponse::Paused {
+            until: pause.until,
+            operations: pause.operations,
+        },
+        None => PauseInfoResponse::Unpaused {},
+    })
+}
+
+/// Returns whether the given operation is currently paused.
+pub fn is_paused(storage: &dyn Storage, block: &BlockInfo, operation: &str) -> StdResult<bool> {
+    Ok(current_pause(storage, block)?.map_or(false, |pause| pause.covers(operation)))
+}
+
+/// Returns the pauses recorded in the pause history along with their ids, oldest first. Returns
+/// DEFAULT_PAUSE_HISTORY_LIMIT pauses if no limit is set and at most MAX_PAUSE_HISTORY_LIMIT.
+pub fn get_pause_history(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, PauseState)>> {
+    let start = start_after.map(Bound::exclusive);
+    let limit = limit
+        .unwrap_or(DEFAULT_PAUSE_HISTORY_LIMIT)
+        .min(MAX_PAUSE_HISTORY_LIMIT);
+    PAUSE_HISTORY
+        .range(storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect()
+}
+
+/// Returns the pause currently in effect, if any.
+fn current_pause(storage: &dyn Storage, block: &BlockInfo) -> StdResult<Option<PauseState>> {
+    Ok(last_pause(storage)?
+        .map(|(_, pause)| pause)
+        .filter(|pause| pause.is_active(block)))
+}
+
+fn last_pause(storage: &dyn Storage) -> StdResult<Option<(u64, PauseState)>> {
+    PAUSE_HISTORY
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+}
+
+#[derive(Error, Debug, PartialEq)]
 pub enum PauseError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
     #[error("Unauthorized.")]
     Unauthorized {},
 
     #[error("invalid duration")]
     InvalidDuration(String),
 
+    #[error("invalid reason")]
+    InvalidReason(String),
+
+    #[error("invalid operations")]
+    InvalidOperations(String),
+
     #[error("Contract execution is paused.")]
     Paused {},
 }
 
 /// Information about if the contract is currently paused.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum PauseInfoResponse {
-    Paused { until_height: u64 },
+    Paused {
+        until: Expiration,
+        /// The operations which are paused. `None` means the whole contract is paused.
+        operations: Option<Vec<String>>,
+    },
     Unpaused {},
 }
+
+/// The maximum allowed durations for pause calls. Pauses set in blocks are limited by `blocks`,
+/// pauses set in seconds by `seconds`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MaxPauseDurationResponse {
+    /// The maximum allowed number of blocks to pause the contract for.
+    pub blocks: u64,
+    /// The maximum allowed number of seconds to pause the contract for.
+    pub seconds: u64,
+}
+
+/// A record of a single pause of a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseState {
+    /// The address that paused the contract.
+    pub paused_by: Addr,
+    /// Why the contract was paused.
+    pub reason: Option<String>,
+    /// The operations which were paused. `None` means the whole contract was paused.
+    pub operations: Option<Vec<String>>,
+    /// The height the contract was paused at.
+    pub from_height: u64,
+    /// When the pause expires.
+    pub until: Expiration,
+    /// The address that unpaused the contract before the pause expired, if any.
+    pub unpaused_by: Option<Addr>,
+    /// The height the contract was unpaused at, if it was unpaused before the pause expired.
+    pub unpaused_at_height: Option<u64>,
+}
+
+impl PauseState {
+    /// checks whether the pause is in effect at the given block.
+    pub fn is_active(&self, block: &BlockInfo) -> bool {
+        self.unpaused_at_height.is_none() && !self.until.is_expired(block)
+    }
+
+    /// checks whether the pause applies to the given operation.
+    pub fn covers(&self, operation: &str) -> bool {
+        match &self.operations {
+            Some(operations) => operations.iter().any(|op| op == operation),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+    use cosmwasm_std::Empty;
+
+    const OPERATIONS: &[&str] = &["fund", "claim"];
+
+    fn request(
+        duration: Duration,
+        reason: Option<&str>,
+        operations: Option<&[&str]>,
+    ) -> PauseRequest<'static> {
+        PauseRequest {
+            duration,
+            reason: reason.map(|r| r.to_string()),
+            operations: operations.map(|ops| ops.iter().map(|op| op.to_string()).collect()),
+            known_operations: OPERATIONS,
+        }
+    }
+
+    #[test]
+    fn test_pause_history() {
+        let mut storage = MockStorage::new();
+        let main_dao = Addr::unchecked("main_dao");
+        let security_dao = Addr::unchecked("security_dao");
+        let mut block = mock_env().block;
+
+        execute_pause::<Empty>(
+            &mut storage,
+            &block,
+            security_dao.clone(),
+            &main_dao,
+            &security_dao,
+            request(Duration::Height(10), Some("incident"), None),
+        )
+        .unwrap();
+        assert_eq!(
+            get_pause_info(&storage, &block).unwrap(),
+            PauseInfoResponse::Paused {
+                until: Expiration::AtHeight(block.height + 10),
+                operations: None,
+            }
+        );
+
+        // a shorter pause can't override the current one
+        assert_eq!(
+            execute_pause::<Empty>(
+                &mut storage,
+                &block,
+                main_dao.clone(),
+                &main_dao,
+                &security_dao,
+                request(Duration::Height(5), None, None),
+            )
+            .unwrap_err(),
+            PauseError::InvalidDuration(
+                "contracts are already paused for a greater or equal duration".to_string()
+            )
+        );
+
+        block.height += 2;
+        execute_unpause::<Empty>(&mut storage, &block, main_dao.clone(), &main_dao).unwrap();
+        assert_eq!(
+            get_pause_info(&storage, &block).unwrap(),
+            PauseInfoResponse::Unpaused {}
+        );
+
+        execute_pause::<Empty>(
+            &mut storage,
+            &block,
+            main_dao.clone(),
+            &main_dao,
+            &security_dao,
+            request(Duration::Height(5), None, None),
+        )
+        .unwrap();
+
+        let history = get_pause_history(&storage, None, None).unwrap();
+        assert_eq!(
+            history,
+            vec![
+                (
+                    0,
+                    PauseState {
+                        paused_by: security_dao,
+                        reason: Some("incident".to_string()),
+                        operations: None,
+                        from_height: block.height - 2,
+                        until: Expiration::AtHeight(block.height + 8),
+                        unpaused_by: Some(main_dao.clone()),
+                        unpaused_at_height: Some(block.height),
+                    }
+                ),
+                (
+                    1,
+                    PauseState {
+                        paused_by: main_dao,
+                        reason: None,
+                        operations: None,
+                        from_height: block.height,
+                        until: Expiration::AtHeight(block.height + 5),
+                        unpaused_by: None,
+                        unpaused_at_height: None,
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            get_pause_history(&storage, Some(0), Some(1)).unwrap(),
+            history[1..].to_vec()
+        );
+
+        // the history is read in pages
+        for id in 2..=MAX_PAUSE_HISTORY_LIMIT as u64 + 1 {
+            PAUSE_HISTORY.save(&mut storage, id, &history[1].1).unwrap();
+        }
+        assert_eq!(
+            get_pause_history(&storage, None, None).unwrap().len(),
+            DEFAULT_PAUSE_HISTORY_LIMIT as usize
+        );
+        assert_eq!(
+            get_pause_history(&storage, None, Some(u32::MAX))
+                .unwrap()
+                .len(),
+            MAX_PAUSE_HISTORY_LIMIT as usize
+        );
+    }
+
+    #[test]
+    fn test_pause_operations() {
+        let mut storage = MockStorage::new();
+        let main_dao = Addr::unchecked("main_dao");
+        let security_dao = Addr::unchecked("security_dao");
+        let mut block = mock_env().block;
+
+        // unknown operations can't be paused
+        assert_eq!(
+            execute_pause::<Empty>(
+                &mut storage,
+                &block,
+                security_dao.clone(),
+                &main_dao,
+                &security_dao,
+                request(Duration::Height(10), None, Some(&["withdraw"])),
+            )
+            .unwrap_err(),
+            PauseError::InvalidOperations("unknown operation: withdraw".to_string())
+        );
+
+        execute_pause::<Empty>(
+            &mut storage,
+            &block,
+            security_dao.clone(),
+            &main_dao,
+            &security_dao,
+            request(Duration::Height(10), None, Some(&["claim"])),
+        )
+        .unwrap();
+        assert!(is_paused(&storage, &block, "claim").unwrap());
+        assert!(!is_paused(&storage, &block, "fund").unwrap());
+
+        // pauses which would pause an operation for longer than requested are rejected:
+        // another operation or the whole contract for a shorter period, or another operation
+        // for a longer period
+        block.height += 2;
+        let combination_err = PauseError::InvalidOperations(
+            "a new pause must cover the paused operations until at least the end of the current pause"
+                .to_string(),
+        );
+        for (duration, operations) in [
+            (5, Some(&["fund"][..])),
+            (5, None),
+            (5, Some(&["claim", "fund"][..])),
+            (20, Some(&["fund"][..])),
+        ] {
+            assert_eq!(
+                execute_pause::<Empty>(
+                    &mut storage,
+                    &block,
+                    security_dao.clone(),
+                    &main_dao,
+                    &security_dao,
+                    request(Duration::Height(duration), None, operations),
+                )
+                .unwrap_err(),
+                combination_err
+            );
+        }
+
+        // pausing another operation along with the current ones until the current end replaces
+        // the current pause
+        execute_pause::<Empty>(
+            &mut storage,
+            &block,
+            security_dao.clone(),
+            &main_dao,
+            &security_dao,
+            request(Duration::Height(8), None, Some(&["claim", "fund"])),
+        )
+        .unwrap();
+        assert_eq!(
+            get_pause_info(&storage, &block).unwrap(),
+            PauseInfoResponse::Paused {
+                until: Expiration::AtHeight(block.height + 8),
+                operations: Some(vec!["claim".to_string(), "fund".to_string()]),
+            }
+        );
+
+        // pausing the whole contract for longer covers every operation
+        execute_pause::<Empty>(
+            &mut storage,
+            &block,
+            security_dao.clone(),
+            &main_dao,
+            &security_dao,
+            request(Duration::Height(10), None, None),
+        )
+        .unwrap();
+        assert!(is_paused(&storage, &block, "pause").unwrap());
+
+        // an operation can't be paused for longer than the whole contract is
+        assert_eq!(
+            execute_pause::<Empty>(
+                &mut storage,
+                &block,
+                security_dao.clone(),
+                &main_dao,
+                &security_dao,
+                request(Duration::Height(20), None, Some(&["claim"])),
+            )
+            .unwrap_err(),
+            combination_err
+        );
+
+        block.height += 10;
+        assert!(!is_paused(&storage, &block, "claim").unwrap());
+    }
+
+    #[test]
+    fn test_pause_duration() {
+        let mut storage = MockStorage::new();
+        let main_dao = Addr::unchecked("main_dao");
+        let security_dao = Addr::unchecked("security_dao");
+        let block = mock_env().block;
+
+        // both units have a default max duration
+        assert_eq!(
+            execute_pause::<Empty>(
+                &mut storage,
+                &block,
+                security_dao.clone(),
+                &main_dao,
+                &security_dao,
+                request(Duration::Height(DEFAULT_MAX_PAUSE_BLOCKS + 1), None, None),
+            )
+            .unwrap_err(),
+            PauseError::InvalidDuration(
+                "Pause duration is too big: it's only possible to pause the contract for 200000 blocks"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            execute_pause::<Empty>(
+                &mut storage,
+                &block,
+                security_dao.clone(),
+                &main_dao,
+                &security_dao,
+                request(Duration::Time(DEFAULT_MAX_PAUSE_SECONDS + 1), None, None),
+            )
+            .unwrap_err(),
+            PauseError::InvalidDuration(
+                "Pause duration is too big: it's only possible to pause the contract for 1209600 seconds"
+                    .to_string()
+            )
+        );
+
+        // only the main dao can change the max duration
+        assert_eq!(
+            execute_update_max_pause_duration::<Empty>(
+                &mut storage,
+                security_dao.clone(),
+                &main_dao,
+                Duration::Time(86400),
+            )
+            .unwrap_err(),
+            PauseError::Unauthorized {}
+        );
+        execute_update_max_pause_duration::<Empty>(
+            &mut storage,
+            main_dao.clone(),
+            &main_dao,
+            Duration::Time(86400),
+        )
+        .unwrap();
+        // the max duration in blocks is left untouched
+        assert_eq!(
+            get_max_pause_durations(&storage).unwrap(),
+            MaxPauseDurationResponse {
+                blocks: DEFAULT_MAX_PAUSE_BLOCKS,
+                seconds: 86400,
+            }
+        );
+        assert_eq!(
+            get_max_pause_duration(&storage, &Duration::Height(1)).unwrap(),
+            Duration::Height(DEFAULT_MAX_PAUSE_BLOCKS)
+        );
+
+        assert_eq!(
+            execute_pause::<Empty>(
+                &mut storage,
+                &block,
+                security_dao.clone(),
+                &main_dao,
+                &security_dao,
+                request(Duration::Time(86401), None, None),
+            )
+            .unwrap_err(),
+            PauseError::InvalidDuration(
+                "Pause duration is too big: it's only possible to pause the contract for 86400 seconds"
+                    .to_string()
+            )
+        );
+
+        execute_pause::<Empty>(
+            &mut storage,
+            &block,
+            security_dao.clone(),
+            &main_dao,
+            &security_dao,
+            request(Duration::Time(3600), None, None),
+        )
+        .unwrap();
+        assert_eq!(
+            get_pause_info(&storage, &block).unwrap(),
+            PauseInfoResponse::Paused {
+                until: Expiration::AtTime(block.time.plus_seconds(3600)),
+                operations: None,
+            }
+        );
+
+        let mut later = block.clone();
+        later.time = later.time.plus_seconds(3600);
+        assert!(!is_paused(&storage, &later, "fund").unwrap());
+    }
+
+    #[test]
+    fn test_migrate_legacy_pause() {
+        let mut storage = MockStorage::new();
+        let main_dao = Addr::unchecked("main_dao");
+        let block = mock_env().block;
+
+        // an expired legacy pause is dropped
+        LEGACY_PAUSED_UNTIL
+            .save(&mut storage, &Some(block.height))
+            .unwrap();
+        migrate_legacy_pause(&mut storage, &block, &main_dao).unwrap();
+        assert_eq!(LEGACY_PAUSED_UNTIL.may_load(&storage).unwrap(), None);
+        assert!(get_pause_history(&storage, None, None).unwrap().is_empty());
+
+        // an active legacy pause is carried over
+        LEGACY_PAUSED_UNTIL
+            .save(&mut storage, &Some(block.height + 10))
+            .unwrap();
+        migrate_legacy_pause(&mut storage, &block, &main_dao).unwrap();
+        assert_eq!(LEGACY_PAUSED_UNTIL.may_load(&storage).unwrap(), None);
+        assert_eq!(
+            get_pause_info(&storage, &block).unwrap(),
+            PauseInfoResponse::Paused {
+                until: Expiration::AtHeight(block.height + 10),
+                operations: None,
+            }
+        );
+        assert_eq!(
+            get_pause_history(&storage, None, None).unwrap()[0]
+                .1
+                .paused_by,
+            main_dao
+        );
+
+        // migrating again is a no-op
+        migrate_legacy_pause(&mut storage, &block, &main_dao).unwrap();
+        assert_eq!(get_pause_history(&storage, None, None).unwrap().len(), 1);
+    }
+}
@@ -8,15 +8,17 @@ pub struct MigrateMsg {}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub enum ExecuteMsgPauseTypedDuration {
-    /// Pauses the DAO contract for a set duration.
-    /// When paused the DAO is unable to execute proposals
-    Pause { duration: Duration },
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum ExecuteMsgPauseUntypedDuration {
-    /// Pauses the cntract for blocks
-    Pause { duration: u64 },
+pub enum ExecuteMsgPause {
+    /// Pauses the contract for a set duration, either in blocks or in seconds.
+    Pause {
+        duration: Duration,
+        reason: Option<String>,
+        operations: Option<Vec<String>>,
+    },
+    /// Pauses the main DAO along with all the contracts registered with it as pausable.
+    PauseAll {
+        duration: Duration,
+        reason: Option<String>,
+        operations: Option<Vec<String>>,
+    },
 }
@@ -4,7 +4,7 @@ use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use cwd_interface::ModuleInstantiateInfo;
-use exec_control::pause::PauseInfoResponse;
+use exec_control::pause::{MaxPauseDurationResponse, PauseInfoResponse, PauseState};
 use neutron_sdk::bindings::msg::NeutronMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -53,7 +53,7 @@ pub struct InstantiateMsg {
 }
 
 #[pausable]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Callable by proposal modules. The DAO will execute the
@@ -93,6 +93,17 @@ pub enum ExecuteMsg {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Callable by the admin. Nominates a new admin. The nominee
+    /// must accept the nomination with `AcceptAdminNomination`
+    /// before it takes effect.
+    NominateAdmin { admin: String },
+    /// Callable by the nominated admin. Makes the sender the new
+    /// admin of the subDAO. If the subDAO is its own contract admin,
+    /// the contract admin is transferred to the sender as well.
+    AcceptAdminNomination {},
+    /// Callable by the admin. Withdraws the pending admin
+    /// nomination.
+    WithdrawAdminNomination {},
 }
 
 #[pausable_query]
@@ -104,6 +115,12 @@ pub enum QueryMsg {
     /// Gets the contract's config. Returns Config.
     #[returns(crate::types::Config)]
     Config {},
+    /// Gets the current admin of the subDAO. Returns Addr.
+    #[returns(Addr)]
+    Admin {},
+    /// Gets the pending admin nomination, if any.
+    #[returns(crate::types::AdminNominationResponse)]
+    AdminNomination {},
     /// Dumps all of the core contract's state in a single
     /// query. Useful for frontends as performance for queries is more
     /// limited by network times than compute times. Returns
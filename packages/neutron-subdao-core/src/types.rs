@@ -11,6 +11,8 @@ use serde::{Deserialize, Serialize};
 pub struct DumpStateResponse {
     /// The governance contract's config.
     pub config: Config,
+    /// The admin of the governance contract.
+    pub admin: Addr,
     /// Whether the contract is currently paused.
     pub pause_info: PauseInfoResponse,
     /// The governance contract's version.